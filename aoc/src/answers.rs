@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+use solutionlib::Part;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+/// How a computed answer compares to the one on record.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Status {
+    Pass,
+    Fail,
+    Unknown,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::Unknown => "UNKNOWN",
+        };
+        return write!(f, "{}", s);
+    }
+}
+
+/// Known good answers, as stored in `answers.toml`.
+///
+/// Keyed by day, then input file, then part:
+/// ```toml
+/// [day01.input]
+/// a = "11"
+/// b = "31"
+/// ```
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    days: BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+impl Answers {
+    pub fn new() -> Answers {
+        return Answers::default();
+    }
+
+    /// Load answers from disk, a missing file has no answers recorded.
+    pub fn load(filename: &Path) -> Answers {
+        if !filename.exists() {
+            return Answers::new();
+        }
+        let contents = fs::read_to_string(filename).expect("Something went wrong reading");
+        return Answers::from_toml(&contents);
+    }
+
+    pub fn save(&self, filename: &Path) {
+        fs::write(filename, self.to_toml()).expect("Something went wrong writing");
+    }
+
+    /// ```
+    /// let answers = aoc::Answers::from_toml("[day01.input]\na = \"11\"\n");
+    /// assert_eq!(answers.get("day01", "input", solutionlib::Part::A), Some(&"11".to_string()));
+    /// assert_eq!(answers.get("day01", "input", solutionlib::Part::B), None);
+    /// ```
+    pub fn from_toml(contents: &str) -> Answers {
+        return toml::from_str(contents).expect("answers file is not valid");
+    }
+
+    pub fn to_toml(&self) -> String {
+        return toml::to_string(self).unwrap();
+    }
+
+    pub fn get(&self, day: &str, input: &str, part: Part) -> Option<&String> {
+        return self.days.get(day)?.get(input)?.get(&part.to_string());
+    }
+
+    pub fn set(&mut self, day: &str, input: &str, part: Part, answer: &str) {
+        self.days
+            .entry(day.to_string())
+            .or_default()
+            .entry(input.to_string())
+            .or_default()
+            .insert(part.to_string(), answer.to_string());
+    }
+
+    /// Compare an answer against the one on record.
+    /// ```
+    /// use aoc::{Answers, Status};
+    /// use solutionlib::Part;
+    /// let mut answers = Answers::new();
+    /// answers.set("day02", "input", Part::A, "2");
+    /// assert_eq!(answers.check("day02", "input", Part::A, "2"), Status::Pass);
+    /// assert_eq!(answers.check("day02", "input", Part::A, "4"), Status::Fail);
+    /// assert_eq!(answers.check("day02", "input", Part::B, "4"), Status::Unknown);
+    /// ```
+    pub fn check(&self, day: &str, input: &str, part: Part, answer: &str) -> Status {
+        return match self.get(day, input, part) {
+            Some(expected) if expected == answer => Status::Pass,
+            Some(_) => Status::Fail,
+            None => Status::Unknown,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_status() {
+        assert_eq!(format!("{}", Status::Pass), "PASS");
+        assert_eq!(format!("{}", Status::Fail), "FAIL");
+        assert_eq!(format!("{}", Status::Unknown), "UNKNOWN");
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let mut answers = Answers::new();
+        answers.set("day01", "input", Part::A, "11");
+        answers.set("day01", "input", Part::B, "31");
+        answers.set("day18", "input", Part::B, "6,1");
+        let text = answers.to_toml();
+        assert_eq!(
+            text,
+            "[day01.input]\na = \"11\"\nb = \"31\"\n\n[day18.input]\nb = \"6,1\"\n"
+        );
+        assert_eq!(Answers::from_toml(&text), answers);
+    }
+
+    #[test]
+    fn test_record_overwrites() {
+        let mut answers = Answers::new();
+        answers.set("day01", "input", Part::A, "11");
+        answers.set("day01", "input", Part::A, "12");
+        assert_eq!(answers.check("day01", "input", Part::A, "12"), Status::Pass);
+    }
+}
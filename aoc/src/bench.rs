@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+use solutionlib::{Part, Solution};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    A,
+    B,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Phase::Parse => "parse",
+            Phase::A => "a",
+            Phase::B => "b",
+        };
+        return write!(f, "{}", s);
+    }
+}
+
+/// How long one phase of one day took over several iterations.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: String,
+    pub input: String,
+    pub phase: Phase,
+    pub iterations: u32,
+    pub min_ns: u64,
+    pub median_ns: u64,
+}
+
+impl Timing {
+    fn new(day: &str, input: &str, phase: Phase, mut samples: Vec<Duration>) -> Timing {
+        samples.sort();
+        return Timing {
+            day: day.to_string(),
+            input: input.to_string(),
+            phase: phase,
+            iterations: samples.len() as u32,
+            min_ns: samples[0].as_nanos() as u64,
+            median_ns: samples[samples.len() / 2].as_nanos() as u64,
+        };
+    }
+
+    fn same_benchmark(&self, other: &Timing) -> bool {
+        return self.day == other.day && self.input == other.input && self.phase == other.phase;
+    }
+}
+
+/// A timing that got slower than the previous run allows.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Regression {
+    pub current: Timing,
+    pub previous: Timing,
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(
+            f,
+            "{} {} {}: {:.2?} -> {:.2?}",
+            self.current.day,
+            self.current.input,
+            self.current.phase,
+            Duration::from_nanos(self.previous.median_ns),
+            Duration::from_nanos(self.current.median_ns)
+        );
+    }
+}
+
+/// Time parsing and the given parts of a day, each phase `iterations` times.
+///
+/// Parsing is splitting the text and the day's `check_input`, which runs its
+/// parsers, with the first part's params. Each part parses again as it
+/// solves, so its time includes that too. Each part is run with the params
+/// paired with it. `iterations` must be at least 1, which the command line
+/// checks.
+pub fn time_solution(
+    solution: &Solution,
+    input_name: &str,
+    text: &str,
+    parts: &[(Part, Vec<usize>)],
+    iterations: u32,
+) -> Vec<Timing> {
+    assert!(iterations > 0);
+    let name = solution.name();
+    let check_params = parts.first().map_or(solution.params, |(_, p)| p.as_slice());
+    let mut parse_samples = vec![];
+    let mut part_samples = vec![vec![]; parts.len()];
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let input = solution.parse(text);
+        // Already checked by the caller, this is only for the timing
+        let _ = solution.check_with(&input, check_params);
+        parse_samples.push(start.elapsed());
+
+        for ((part, params), samples) in parts.iter().zip(part_samples.iter_mut()) {
+            let start = Instant::now();
+            solution.solve_with(*part, &input, params);
+            samples.push(start.elapsed());
+        }
+    }
+    let mut result = vec![Timing::new(&name, input_name, Phase::Parse, parse_samples)];
+    for ((part, _), samples) in parts.iter().zip(part_samples) {
+        let phase = match part {
+            Part::A => Phase::A,
+            Part::B => Phase::B,
+        };
+        result.push(Timing::new(&name, input_name, phase, samples));
+    }
+    return result;
+}
+
+/// Find every benchmark whose median got more than `threshold` slower.
+///
+/// `threshold` is a fraction, so 0.1 allows a 10% slowdown.
+/// Benchmarks missing from the previous run are ignored.
+pub fn find_regressions(
+    current: &[Timing],
+    previous: &[Timing],
+    threshold: f64,
+) -> Vec<Regression> {
+    let mut result = vec![];
+    for timing in current {
+        let Some(old) = previous.iter().find(|t| t.same_benchmark(timing)) else {
+            continue;
+        };
+        let allowed = old.median_ns as f64 * (1.0 + threshold);
+        if timing.median_ns as f64 > allowed {
+            result.push(Regression {
+                current: timing.clone(),
+                previous: old.clone(),
+            });
+        }
+    }
+    return result;
+}
+
+/// One row per day and input, one column per phase.
+pub fn summary_table(timings: &[Timing]) -> String {
+    let mut lines = vec![format!(
+        "{:<8} {:<12} {:>12} {:>12} {:>12}",
+        "day", "input", "parse", "a", "b"
+    )];
+    let mut rows: Vec<(&String, &String)> = vec![];
+    for timing in timings {
+        if !rows.contains(&(&timing.day, &timing.input)) {
+            rows.push((&timing.day, &timing.input));
+        }
+    }
+    for (day, input) in rows {
+        let cell = |phase: Phase| -> String {
+            return match timings
+                .iter()
+                .find(|t| &t.day == day && &t.input == input && t.phase == phase)
+            {
+                Some(t) => format!("{:.2?}", Duration::from_nanos(t.median_ns)),
+                None => "-".to_string(),
+            };
+        };
+        lines.push(format!(
+            "{:<8} {:<12} {:>12} {:>12} {:>12}",
+            day,
+            input,
+            cell(Phase::Parse),
+            cell(Phase::A),
+            cell(Phase::B)
+        ));
+    }
+    return lines.join("\n");
+}
+
+pub fn load_timings(filename: &Path) -> Vec<Timing> {
+    let contents = fs::read_to_string(filename).expect("Something went wrong reading");
+    return serde_json::from_str(&contents).expect("timings file is not valid");
+}
+
+pub fn save_timings(filename: &Path, timings: &[Timing]) {
+    let contents = serde_json::to_string_pretty(timings).unwrap();
+    fs::write(filename, contents).expect("Something went wrong writing");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(day: &str, phase: Phase, median_ns: u64) -> Timing {
+        return Timing {
+            day: day.to_string(),
+            input: "input".to_string(),
+            phase: phase,
+            iterations: 1,
+            min_ns: median_ns,
+            median_ns: median_ns,
+        };
+    }
+
+    #[test]
+    fn test_timing_median() {
+        let samples = vec![
+            Duration::from_nanos(30),
+            Duration::from_nanos(10),
+            Duration::from_nanos(20),
+        ];
+        let t = Timing::new("day01", "input", Phase::A, samples);
+        assert_eq!(t.iterations, 3);
+        assert_eq!(t.min_ns, 10);
+        assert_eq!(t.median_ns, 20);
+    }
+
+    #[test]
+    fn test_find_regressions() {
+        let previous = vec![
+            timing("day06", Phase::B, 1000),
+            timing("day14", Phase::B, 1000),
+        ];
+        let current = vec![
+            timing("day06", Phase::B, 1200),
+            timing("day14", Phase::B, 1050),
+            timing("day22", Phase::B, 99999),
+        ];
+        let regressions = find_regressions(&current, &previous, 0.1);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].current.day, "day06");
+        assert_eq!(regressions[0].previous.median_ns, 1000);
+    }
+
+    #[test]
+    fn test_summary_table() {
+        let timings = vec![
+            timing("day01", Phase::Parse, 1500),
+            timing("day01", Phase::A, 2_000_000),
+        ];
+        let table = summary_table(&timings);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1].split_whitespace().collect::<Vec<&str>>(),
+            vec!["day01", "input", "1.50µs", "2.00ms", "-"]
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let timings = vec![timing("day01", Phase::Parse, 1500)];
+        let text = serde_json::to_string(&timings).unwrap();
+        assert!(text.contains("\"phase\":\"parse\""));
+        let back: Vec<Timing> = serde_json::from_str(&text).unwrap();
+        assert_eq!(back, timings);
+    }
+}
@@ -0,0 +1,343 @@
+use filelib::{load_fixture, Fixture};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Which input for a day, the personal puzzle input or one of the examples.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum InputVariant {
+    Real,
+    Example(u32),
+}
+
+impl Display for InputVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            InputVariant::Real => write!(f, "input"),
+            InputVariant::Example(n) => write!(f, "example-{}", n),
+        };
+    }
+}
+
+impl FromStr for InputVariant {
+    type Err = String;
+
+    /// ```
+    /// use aoc::InputVariant;
+    /// assert_eq!("input".parse::<InputVariant>(), Ok(InputVariant::Real));
+    /// assert_eq!("example-2".parse::<InputVariant>(), Ok(InputVariant::Example(2)));
+    /// assert!("example-x".parse::<InputVariant>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "input" {
+            return Ok(InputVariant::Real);
+        }
+        if let Some(n) = s.strip_prefix("example-") {
+            if let Ok(n) = n.parse() {
+                return Ok(InputVariant::Example(n));
+            }
+        }
+        return Err(format!("Unknown input {}, expected input or example-N", s));
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InputError {
+    /// Nothing in the cache and nowhere to fetch it from
+    NotFound(PathBuf),
+    /// The remote could not give us the input
+    Fetch(String),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            InputError::NotFound(path) => write!(f, "no input found at {}", path.display()),
+            InputError::Fetch(reason) => write!(f, "could not fetch input: {}", reason),
+        };
+    }
+}
+
+/// Finds puzzle inputs in a local mirror directory, laid out as
+///
+/// ```text
+/// <dir>/inputs/2024/day14.txt
+/// <dir>/examples/day14-1.txt
+/// ```
+///
+/// Real inputs that are missing can be fetched from a remote and saved in
+/// the mirror. The remote uses the same paths as the Advent of Code site,
+/// `<remote>/2024/day/14/input`, so a stub server can stand in for it.
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    dir: PathBuf,
+    year: u32,
+    remote: Option<String>,
+    session: Option<String>,
+}
+
+impl InputCache {
+    pub fn new(dir: &Path, year: u32) -> InputCache {
+        return InputCache {
+            dir: dir.to_path_buf(),
+            year: year,
+            remote: None,
+            session: None,
+        };
+    }
+
+    /// Fetch missing real inputs from this url, sending `session` as the cookie.
+    pub fn with_remote(mut self, remote: &str, session: Option<String>) -> InputCache {
+        self.remote = Some(remote.trim_end_matches('/').to_string());
+        self.session = session;
+        return self;
+    }
+
+    /// Where a given input lives in the mirror, whether or not it exists.
+    /// ```
+    /// use aoc::{InputCache, InputVariant};
+    /// use std::path::Path;
+    /// let cache = InputCache::new(Path::new("/mirror"), 2024);
+    /// assert_eq!(cache.path(14, InputVariant::Real), Path::new("/mirror/inputs/2024/day14.txt"));
+    /// assert_eq!(cache.path(14, InputVariant::Example(1)), Path::new("/mirror/examples/day14-1.txt"));
+    /// ```
+    pub fn path(&self, day: u32, variant: InputVariant) -> PathBuf {
+        return match variant {
+            InputVariant::Real => self
+                .dir
+                .join("inputs")
+                .join(self.year.to_string())
+                .join(format!("day{:02}.txt", day)),
+            InputVariant::Example(n) => self
+                .dir
+                .join("examples")
+                .join(format!("day{:02}-{}.txt", day, n)),
+        };
+    }
+
+    /// The `input` file in the day's own folder, from before there was a mirror.
+    fn legacy_path(&self, day: u32) -> PathBuf {
+        return self.dir.join(format!("day{:02}", day)).join("input");
+    }
+
+    /// Every input available locally for a day, real input first.
+    pub fn variants(&self, day: u32) -> Vec<InputVariant> {
+        let mut result = vec![];
+        if self.path(day, InputVariant::Real).exists() || self.legacy_path(day).exists() {
+            result.push(InputVariant::Real);
+        }
+        let prefix = format!("day{:02}-", day);
+        if let Ok(entries) = fs::read_dir(self.dir.join("examples")) {
+            let mut examples: Vec<InputVariant> = entries
+                .filter_map(|entry| {
+                    let name = entry.ok()?.file_name().into_string().ok()?;
+                    let n = name.strip_prefix(&prefix)?.strip_suffix(".txt")?;
+                    return Some(InputVariant::Example(n.parse().ok()?));
+                })
+                .collect();
+            examples.sort();
+            result.extend(examples);
+        }
+        return result;
+    }
+
+    /// Read an input, fetching and caching real inputs when a remote is set.
+    pub fn load(&self, day: u32, variant: InputVariant) -> Result<String, InputError> {
+        let path = self.path(day, variant);
+        if path.exists() {
+            return Ok(fs::read_to_string(&path).expect("Something went wrong reading"));
+        }
+        if variant != InputVariant::Real {
+            return Err(InputError::NotFound(path));
+        }
+        let legacy = self.legacy_path(day);
+        if legacy.exists() {
+            return Ok(fs::read_to_string(&legacy).expect("Something went wrong reading"));
+        }
+        let Some(remote) = &self.remote else {
+            return Err(InputError::NotFound(path));
+        };
+
+        let text = self.fetch(remote, day)?;
+        fs::create_dir_all(path.parent().unwrap()).expect("Could not create cache directory");
+        fs::write(&path, &text).expect("Something went wrong writing");
+        return Ok(text);
+    }
+
+    /// The expected answers and params stored next to an example, if any.
+    pub fn fixture(&self, day: u32, variant: InputVariant) -> Option<Fixture> {
+        let InputVariant::Example(n) = variant else {
+            return None;
+        };
+        let path = self.path(day, variant);
+        if !path.exists() {
+            return None;
+        }
+        let dir = path.parent().unwrap().to_str().unwrap();
+        return Some(load_fixture(dir, &format!("day{:02}-{}", day, n)));
+    }
+
+    fn fetch(&self, remote: &str, day: u32) -> Result<String, InputError> {
+        let url = format!("{}/{}/day/{}/input", remote, self.year, day);
+        let mut request = ureq::get(&url);
+        if let Some(session) = &self.session {
+            request = request.header("Cookie", &format!("session={}", session));
+        }
+        let mut response = request
+            .call()
+            .map_err(|e| InputError::Fetch(format!("{}: {}", url, e)))?;
+        return response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| InputError::Fetch(format!("{}: {}", url, e)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        return dir;
+    }
+
+    /// Serve `body` for a single request, returning the url and the request line and headers seen.
+    fn stub_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                request.push(line.trim().to_string());
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            return request;
+        });
+        return (url, handle);
+    }
+
+    #[test]
+    fn test_format_variant() {
+        assert_eq!(format!("{}", InputVariant::Real), "input");
+        assert_eq!(format!("{}", InputVariant::Example(3)), "example-3");
+    }
+
+    #[test]
+    fn test_variants_and_load() {
+        let dir = temp_dir("variants");
+        fs::create_dir_all(dir.join("inputs/2024")).unwrap();
+        fs::create_dir_all(dir.join("examples")).unwrap();
+        fs::write(dir.join("inputs/2024/day14.txt"), "real").unwrap();
+        fs::write(dir.join("examples/day14-2.txt"), "two").unwrap();
+        fs::write(dir.join("examples/day14-1.txt"), "one").unwrap();
+        fs::write(dir.join("examples/day01-1.txt"), "other day").unwrap();
+
+        let cache = InputCache::new(&dir, 2024);
+        assert_eq!(
+            cache.variants(14),
+            vec![
+                InputVariant::Real,
+                InputVariant::Example(1),
+                InputVariant::Example(2)
+            ]
+        );
+        assert_eq!(cache.load(14, InputVariant::Real), Ok("real".to_string()));
+        assert_eq!(
+            cache.load(14, InputVariant::Example(2)),
+            Ok("two".to_string())
+        );
+        assert_eq!(
+            cache.load(14, InputVariant::Example(3)),
+            Err(InputError::NotFound(dir.join("examples/day14-3.txt")))
+        );
+        assert_eq!(cache.variants(2), vec![]);
+        assert!(cache.fixture(14, InputVariant::Real).is_none());
+        assert!(cache.fixture(14, InputVariant::Example(3)).is_none());
+        fs::write(
+            dir.join("examples/day14-1.expected"),
+            "a: 12\nparams: 7 11\n",
+        )
+        .unwrap();
+        let fixture = cache.fixture(14, InputVariant::Example(1)).unwrap();
+        assert_eq!(fixture.answer("a"), Some("12"));
+        assert_eq!(fixture.params("a"), Some(vec![7, 11]));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_legacy_input() {
+        let dir = temp_dir("legacy");
+        fs::create_dir_all(dir.join("day03")).unwrap();
+        fs::write(dir.join("day03/input"), "old").unwrap();
+
+        let cache = InputCache::new(&dir, 2024);
+        assert_eq!(cache.variants(3), vec![InputVariant::Real]);
+        assert_eq!(cache.load(3, InputVariant::Real), Ok("old".to_string()));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_and_cache() {
+        let dir = temp_dir("fetch");
+        let (url, server) = stub_server("200 OK", "1 2\n3 4\n");
+        let cache = InputCache::new(&dir, 2024).with_remote(&url, Some("abc".to_string()));
+
+        assert_eq!(
+            cache.load(7, InputVariant::Real),
+            Ok("1 2\n3 4\n".to_string())
+        );
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2024/day/7/input HTTP/1.1");
+        assert!(request
+            .iter()
+            .any(|h| h.to_lowercase() == "cookie: session=abc"));
+
+        // Second load comes from the cache, there is no server any more
+        assert_eq!(
+            fs::read_to_string(dir.join("inputs/2024/day07.txt")).unwrap(),
+            "1 2\n3 4\n"
+        );
+        assert_eq!(
+            cache.load(7, InputVariant::Real),
+            Ok("1 2\n3 4\n".to_string())
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_error() {
+        let dir = temp_dir("fetch-error");
+        let (url, server) = stub_server("404 Not Found", "");
+        let cache = InputCache::new(&dir, 2024).with_remote(&url, None);
+
+        let result = cache.load(7, InputVariant::Real);
+        server.join().unwrap();
+        assert!(matches!(result, Err(InputError::Fetch(_))));
+        assert!(!dir.join("inputs/2024/day07.txt").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
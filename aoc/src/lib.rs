@@ -0,0 +1,40 @@
+mod answers;
+
+pub use crate::answers::Answers;
+pub use crate::answers::Status;
+
+mod bench;
+
+pub use crate::bench::find_regressions;
+pub use crate::bench::load_timings;
+pub use crate::bench::save_timings;
+pub use crate::bench::summary_table;
+pub use crate::bench::time_solution;
+pub use crate::bench::Phase;
+pub use crate::bench::Regression;
+pub use crate::bench::Timing;
+
+mod inputs;
+
+pub use crate::inputs::InputCache;
+pub use crate::inputs::InputError;
+pub use crate::inputs::InputVariant;
+
+mod report;
+
+pub use crate::report::Format;
+pub use crate::report::Outcome;
+pub use crate::report::Report;
+
+mod serve;
+
+pub use crate::serve::handle;
+pub use crate::serve::Reply;
+pub use crate::serve::SolveServer;
+pub use crate::serve::Solved;
+
+mod scaffold;
+
+pub use crate::scaffold::DayShape;
+pub use crate::scaffold::Scaffold;
+pub use crate::scaffold::ScaffoldError;
@@ -0,0 +1,528 @@
+use aoc::{find_regressions, load_timings, save_timings, summary_table, time_solution};
+use aoc::{Answers, DayShape, Format, InputCache, InputVariant, Outcome, Report, Scaffold};
+use aoc::{SolveServer, Status};
+use clap::{Args, Parser, Subcommand};
+use filelib::Fixture;
+use solutionlib::{solutions, Part, Solution};
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+#[derive(Parser)]
+#[command(about = "Run and check Advent of Code 2024 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Which modules log and how much, eg `aoc=info,day17=trace`
+    #[arg(long, global = true, env = tracelib::FILTER_ENV, default_value = tracelib::DEFAULT_FILTER)]
+    log: String,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve days and check them against known answers
+    Run(RunArgs),
+    /// Time parsing and both parts of each day
+    Bench(BenchArgs),
+    /// Create a new day crate from the template
+    New(NewArgs),
+    /// Write a random input for a day, for stress testing and benchmarks
+    Generate(GenerateArgs),
+    /// Solve days over HTTP, POST an input to /<year>/day/<n>/part/<a|b>
+    Serve(ServeArgs),
+    /// Show a day17 input's program as pseudo-code, or its control flow as DOT
+    Decompile(DecompileArgs),
+    /// Draw a day24 input's gates as DOT or Verilog, or turn one back into an input
+    Circuit(CircuitArgs),
+}
+
+/// Which days and inputs to use, shared by the subcommands.
+#[derive(Args)]
+struct InputArgs {
+    /// Days to use, all days if none are given
+    days: Vec<u32>,
+
+    /// Repository root containing the dayXX folders
+    #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/.."))]
+    root: PathBuf,
+
+    /// Input mirror holding inputs/<year>/dayXX.txt and examples/dayXX-N.txt, defaults to the root
+    #[arg(long)]
+    cache: Option<PathBuf>,
+
+    #[arg(long, default_value_t = 2024)]
+    year: u32,
+
+    /// Inputs to use, `input` or `example-N`
+    #[arg(long = "input", default_value = "input")]
+    inputs: Vec<InputVariant>,
+
+    /// Use every input found for each day, ignoring --input
+    #[arg(long)]
+    all_inputs: bool,
+
+    /// Fetch missing real inputs from here, eg https://adventofcode.com
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// Session cookie to send to the remote
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+}
+
+impl InputArgs {
+    fn input_cache(&self) -> InputCache {
+        let dir = self.cache.clone().unwrap_or_else(|| self.root.clone());
+        let cache = InputCache::new(&dir, self.year);
+        return match &self.remote {
+            Some(remote) => cache.with_remote(remote, self.session.clone()),
+            None => cache,
+        };
+    }
+
+    fn solutions(&self) -> Vec<Solution> {
+        return solutions()
+            .into_iter()
+            .filter(|s| self.days.is_empty() || self.days.contains(&s.day))
+            .collect();
+    }
+
+    /// Every selected day with each of its inputs that could be loaded,
+    /// and the fixture expectations for examples.
+    fn load_inputs(&self) -> Vec<(Solution, InputVariant, String, Option<Fixture>)> {
+        let cache = self.input_cache();
+        let mut result = vec![];
+        for solution in self.solutions() {
+            let variants = if self.all_inputs {
+                cache.variants(solution.day)
+            } else {
+                self.inputs.clone()
+            };
+            for variant in variants {
+                match cache.load(solution.day, variant) {
+                    Ok(text) => {
+                        let fixture = cache.fixture(solution.day, variant);
+                        result.push((solution, variant, text, fixture));
+                    }
+                    Err(e) => eprintln!("{} {}: {}, skipping", solution.name(), variant, e),
+                }
+            }
+        }
+        return result;
+    }
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Answers file, defaults to answers.toml in the root
+    #[arg(long)]
+    answers: Option<PathBuf>,
+
+    /// Write the current outputs as the known answers
+    #[arg(long)]
+    record: bool,
+
+    /// How to print results, `text`, `json` or `csv`
+    #[arg(long, default_value = "text")]
+    format: Format,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// How many times to run each phase, at least 1
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+
+    /// Write the timings as JSON to this file
+    #[arg(long)]
+    json: Option<PathBuf>,
+
+    /// A previous JSON run to compare against
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Allowed slowdown against the baseline, as a fraction
+    #[arg(long, default_value_t = 0.1)]
+    threshold: f64,
+}
+
+/// Params for every part worth running on an input.
+///
+/// Examples with expected answers only run the parts they have an answer
+/// for, some examples are only meant for one part and never finish on the other.
+fn parts_to_run(solution: &Solution, fixture: &Option<Fixture>) -> Vec<(Part, Vec<usize>)> {
+    let has_answers = |f: &Fixture| Part::iterator().any(|p| f.answer(&p.to_string()).is_some());
+    let mut result = vec![];
+    for &part in Part::iterator() {
+        let params = match fixture {
+            None => solution.params.to_vec(),
+            Some(f) => {
+                if has_answers(f) && f.answer(&part.to_string()).is_none() {
+                    continue;
+                }
+                f.params(&part.to_string())
+                    .unwrap_or(solution.params.to_vec())
+            }
+        };
+        result.push((part, params));
+    }
+    return result;
+}
+
+#[derive(Args)]
+struct NewArgs {
+    day: u32,
+
+    /// Repository root containing the template folder
+    #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/.."))]
+    root: PathBuf,
+
+    /// How the day reads its input, `lines`, `groups` or `grid`
+    #[arg(long, default_value = "lines")]
+    shape: DayShape,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    day: u32,
+
+    /// Same seed and size give the same input
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// How big to make it, eg the grid width, defaults to the real input's size
+    #[arg(long)]
+    size: Option<usize>,
+
+    /// File to write to, stdout if not given
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ServeArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:2024")]
+    addr: String,
+
+    #[arg(long, default_value_t = 2024)]
+    year: u32,
+}
+
+#[derive(Args)]
+struct DecompileArgs {
+    /// A day17 input or example
+    file: PathBuf,
+
+    /// Print the control flow graph in graphviz DOT instead
+    #[arg(long)]
+    dot: bool,
+}
+
+#[derive(Args)]
+struct CircuitArgs {
+    /// A day24 input, or a DOT or Verilog netlist with --values
+    file: PathBuf,
+
+    /// Print structural Verilog instead of DOT
+    #[arg(long)]
+    verilog: bool,
+
+    /// Read FILE as a netlist and print it as an input, with the wire values from this input
+    #[arg(long)]
+    values: Option<PathBuf>,
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    let answers_file = args
+        .answers
+        .clone()
+        .unwrap_or_else(|| args.input.root.join("answers.toml"));
+    let mut answers = Answers::load(&answers_file);
+    let mut report = Report::new(args.format);
+
+    for (solution, variant, text, fixture) in args.input.load_inputs() {
+        let name = solution.name();
+        let input_name = variant.to_string();
+        let start = Instant::now();
+        let input = solution.parse(&text);
+        let split_time = start.elapsed();
+        for (part, params) in parts_to_run(&solution, &fixture) {
+            // check_input runs the day's parsers, so it counts as parsing
+            let start = Instant::now();
+            let checked = solution.check_with(&input, &params);
+            let parse_time = split_time + start.elapsed();
+            // Input the day can't parse fails on its own, the rest still run
+            let (answer, status, solve_time) = match checked {
+                Err(e) => (e.to_string(), Status::Fail, Duration::ZERO),
+                Ok(()) => {
+                    let start = Instant::now();
+                    let answer = solution.solve_with(part, &input, &params);
+                    let solve_time = start.elapsed();
+                    let mut status = answers.check(&name, &input_name, part, &answer);
+                    if status == Status::Unknown {
+                        if let Some(expected) =
+                            fixture.as_ref().and_then(|f| f.answer(&part.to_string()))
+                        {
+                            status = if expected == answer {
+                                Status::Pass
+                            } else {
+                                Status::Fail
+                            };
+                        }
+                    }
+                    if args.record {
+                        answers.set(&name, &input_name, part, &answer);
+                    }
+                    (answer, status, solve_time)
+                }
+            };
+            let outcome = Outcome {
+                day: name.clone(),
+                input: input_name.clone(),
+                part: part.to_string(),
+                answer: answer,
+                status: status,
+                parse_ns: parse_time.as_nanos() as u64,
+                solve_ns: solve_time.as_nanos() as u64,
+            };
+            if let Some(line) = report.add(outcome) {
+                println!("{}", line);
+            }
+        }
+    }
+    if let Some(rest) = report.finish() {
+        println!("{}", rest);
+    }
+
+    if args.record {
+        answers.save(&answers_file);
+        eprintln!("Recorded answers to {}", answers_file.display());
+        return ExitCode::SUCCESS;
+    }
+    if report.failed() {
+        return ExitCode::FAILURE;
+    }
+    return ExitCode::SUCCESS;
+}
+
+fn bench(args: BenchArgs) -> ExitCode {
+    let mut timings = vec![];
+    for (solution, variant, text, fixture) in args.input.load_inputs() {
+        let input_name = variant.to_string();
+        let input = solution.parse(&text);
+        let mut parts = parts_to_run(&solution, &fixture);
+        parts.retain(|(part, params)| match solution.check_with(&input, params) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!(
+                    "{} {} {}: {}, skipping",
+                    solution.name(),
+                    input_name,
+                    part,
+                    e
+                );
+                false
+            }
+        });
+        timings.extend(time_solution(
+            &solution,
+            &input_name,
+            &text,
+            &parts,
+            args.iterations,
+        ));
+    }
+    println!("{}", summary_table(&timings));
+
+    if let Some(json) = args.json {
+        save_timings(&json, &timings);
+    }
+
+    if let Some(baseline) = args.baseline {
+        let previous = load_timings(&baseline);
+        let regressions = find_regressions(&timings, &previous, args.threshold);
+        if !regressions.is_empty() {
+            println!("Regressions against {}:", baseline.display());
+            for regression in regressions {
+                println!("  {}", regression);
+            }
+            return ExitCode::FAILURE;
+        }
+    }
+    return ExitCode::SUCCESS;
+}
+
+fn new(args: NewArgs) -> ExitCode {
+    return match Scaffold::new(&args.root, args.day, args.shape).create() {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("Could not create day {}: {}", args.day, e);
+            ExitCode::FAILURE
+        }
+    };
+}
+
+fn generate(args: GenerateArgs) -> ExitCode {
+    let Some(generator) = aocgen::find_generator(args.day) else {
+        println!("No generator for day {}", args.day);
+        return ExitCode::FAILURE;
+    };
+    let size = args.size.unwrap_or(generator.default_size);
+    let text = (generator.generate)(args.seed, size);
+    match args.out {
+        Some(path) => fs::write(&path, text).expect("Something went wrong writing"),
+        None => println!("{}", text),
+    }
+    return ExitCode::SUCCESS;
+}
+
+fn serve(args: ServeArgs) -> ExitCode {
+    let server = match SolveServer::bind(&args.addr, args.year) {
+        Ok(server) => server,
+        Err(e) => {
+            println!("Could not listen on {}: {}", args.addr, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("Listening on http://{}", server.addr());
+    server.run();
+    return ExitCode::SUCCESS;
+}
+
+fn decompile(args: DecompileArgs) -> ExitCode {
+    let text = match fs::read_to_string(&args.file) {
+        Ok(text) => text,
+        Err(e) => {
+            println!("Could not read {}: {}", args.file.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let program = match day17::decompile_input(&filelib::split_lines_by_blanks(&text)) {
+        Ok(program) => program,
+        Err(e) => {
+            println!("{}: {}", args.file.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.dot {
+        print!("{}", program.dot());
+    } else {
+        print!("{}", program.pseudo_code());
+    }
+    return ExitCode::SUCCESS;
+}
+
+fn circuit(args: CircuitArgs) -> ExitCode {
+    let read = |path: &PathBuf| {
+        let text = fs::read_to_string(path);
+        if let Err(e) = &text {
+            println!("Could not read {}: {}", path.display(), e);
+        }
+        return text.ok();
+    };
+    let Some(text) = read(&args.file) else {
+        return ExitCode::FAILURE;
+    };
+    let output = match &args.values {
+        Some(values) => {
+            let Some(values) = read(values) else {
+                return ExitCode::FAILURE;
+            };
+            let wires = filelib::split_lines_by_blanks(&values).swap_remove(0);
+            day24::from_netlist(&text, &wires).map(|groups| {
+                let groups: Vec<String> =
+                    groups.iter().map(|lines| lines.join("\n") + "\n").collect();
+                return groups.join("\n");
+            })
+        }
+        None if args.verilog => day24::to_verilog(&filelib::split_lines_by_blanks(&text)),
+        None => day24::to_dot(&filelib::split_lines_by_blanks(&text)),
+    };
+    match output {
+        Ok(output) => print!("{}", output),
+        Err(e) => {
+            println!("{}: {}", args.file.display(), e);
+            return ExitCode::FAILURE;
+        }
+    }
+    return ExitCode::SUCCESS;
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Err(e) = tracelib::init_with(&cli.log) {
+        println!("Bad --log filter {}: {}", cli.log, e);
+        return ExitCode::FAILURE;
+    }
+    return match cli.command {
+        Command::Run(args) => run(args),
+        Command::Bench(args) => bench(args),
+        Command::New(args) => new(args),
+        Command::Generate(args) => generate(args),
+        Command::Serve(args) => serve(args),
+        Command::Decompile(args) => decompile(args),
+        Command::Circuit(args) => circuit(args),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_bad_input() {
+        let dir = std::env::temp_dir().join(format!("aoc-run-bad-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("examples")).unwrap();
+        fs::write(dir.join("examples/day01-1.txt"), "3   4\n4   3\n").unwrap();
+        fs::write(dir.join("examples/day01-2.txt"), "nonsense\n").unwrap();
+        let answers = dir.join("answers.toml");
+        let run_args = |record: &[&str]| {
+            let mut argv = vec!["aoc", "run", "1", "--cache", dir.to_str().unwrap()];
+            argv.extend(["--answers", answers.to_str().unwrap()]);
+            argv.extend(["--input", "example-2", "--input", "example-1"]);
+            argv.extend(record);
+            let Command::Run(args) = Cli::try_parse_from(argv).unwrap().command else {
+                panic!("Expected run");
+            };
+            return args;
+        };
+        // The bad example fails, and the good one after it is still solved
+        assert_eq!(run(run_args(&[])), ExitCode::FAILURE);
+        assert_eq!(run(run_args(&["--record"])), ExitCode::SUCCESS);
+        let recorded = Answers::load(&answers);
+        assert_eq!(
+            recorded.check("day01", "example-1", Part::A, "0"),
+            Status::Pass
+        );
+        assert_eq!(
+            recorded.check("day01", "example-2", Part::A, "invalid input"),
+            Status::Unknown
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_bench_needs_an_iteration() {
+        assert!(Cli::try_parse_from(["aoc", "bench", "--iterations", "0"]).is_err());
+        let cli = Cli::try_parse_from(["aoc", "bench", "--iterations", "1"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Bench(BenchArgs { iterations: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::answers::Status;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How `aoc run` prints its results.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Format {
+    /// One line per part, as it finishes
+    Text,
+    /// A single array once everything has run
+    Json,
+    /// A header, then one row per part as it finishes
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    /// ```
+    /// use aoc::Format;
+    /// assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+    /// assert!("yaml".parse::<Format>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format {}, expected text, json or csv", s)),
+        };
+    }
+}
+
+/// The result of running one part of a day on one input.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Outcome {
+    pub day: String,
+    pub input: String,
+    pub part: String,
+    pub answer: String,
+    pub status: Status,
+    /// Splitting the input and the day's `check_input`, which runs its
+    /// parsers. Each part parses again as it solves, so that's in `solve_ns`
+    /// as well.
+    pub parse_ns: u64,
+    pub solve_ns: u64,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(
+            f,
+            "{} {} {}: {} {} {:.2?}",
+            self.day,
+            self.input,
+            self.part,
+            self.answer,
+            self.status,
+            Duration::from_nanos(self.solve_ns)
+        );
+    }
+}
+
+const CSV_HEADER: &str = "day,input,part,answer,status,parse_ns,solve_ns";
+
+/// Quote a CSV field if it needs it, day17's answers have commas in them.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        return format!("\"{}\"", value.replace('"', "\"\""));
+    }
+    return value.to_string();
+}
+
+impl Outcome {
+    fn csv_row(&self) -> String {
+        return [
+            csv_field(&self.day),
+            csv_field(&self.input),
+            csv_field(&self.part),
+            csv_field(&self.answer),
+            self.status.to_string(),
+            self.parse_ns.to_string(),
+            self.solve_ns.to_string(),
+        ]
+        .join(",");
+    }
+}
+
+/// Collects outcomes and decides what to print, and when, for a format.
+#[derive(Debug, Clone)]
+pub struct Report {
+    format: Format,
+    outcomes: Vec<Outcome>,
+}
+
+impl Report {
+    pub fn new(format: Format) -> Report {
+        return Report {
+            format: format,
+            outcomes: vec![],
+        };
+    }
+
+    /// Add an outcome, returning what should be printed for it straight away.
+    pub fn add(&mut self, outcome: Outcome) -> Option<String> {
+        let first = self.outcomes.is_empty();
+        let result = match self.format {
+            Format::Text => Some(outcome.to_string()),
+            Format::Json => None,
+            Format::Csv if first => Some(format!("{}\n{}", CSV_HEADER, outcome.csv_row())),
+            Format::Csv => Some(outcome.csv_row()),
+        };
+        self.outcomes.push(outcome);
+        return result;
+    }
+
+    /// Anything left to print once every part has run.
+    pub fn finish(&self) -> Option<String> {
+        return match self.format {
+            Format::Text => None,
+            Format::Json => Some(serde_json::to_string_pretty(&self.outcomes).unwrap()),
+            Format::Csv if self.outcomes.is_empty() => Some(CSV_HEADER.to_string()),
+            Format::Csv => None,
+        };
+    }
+
+    pub fn failed(&self) -> bool {
+        return self.outcomes.iter().any(|o| o.status == Status::Fail);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(day: &str, answer: &str, status: Status) -> Outcome {
+        return Outcome {
+            day: day.to_string(),
+            input: "example-1".to_string(),
+            part: "a".to_string(),
+            answer: answer.to_string(),
+            status: status,
+            parse_ns: 1500,
+            solve_ns: 2_000_000,
+        };
+    }
+
+    #[test]
+    fn test_text() {
+        let mut report = Report::new(Format::Text);
+        assert_eq!(
+            report.add(outcome("day16", "7036", Status::Pass)),
+            Some("day16 example-1 a: 7036 PASS 2.00ms".to_string())
+        );
+        assert_eq!(report.finish(), None);
+        assert!(!report.failed());
+    }
+
+    #[test]
+    fn test_json() {
+        let mut report = Report::new(Format::Json);
+        assert_eq!(report.add(outcome("day16", "7036", Status::Pass)), None);
+        assert_eq!(report.add(outcome("day17", "4,6", Status::Fail)), None);
+        assert!(report.failed());
+        let json: serde_json::Value = serde_json::from_str(&report.finish().unwrap()).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 2);
+        assert_eq!(json[1]["day"], "day17");
+        assert_eq!(json[1]["answer"], "4,6");
+        assert_eq!(json[1]["status"], "FAIL");
+        assert_eq!(json[1]["parse_ns"], 1500);
+        assert_eq!(json[1]["solve_ns"], 2_000_000);
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut report = Report::new(Format::Csv);
+        assert_eq!(report.finish(), Some(CSV_HEADER.to_string()));
+        assert_eq!(
+            report.add(outcome("day16", "7036", Status::Unknown)),
+            Some(format!(
+                "{}\nday16,example-1,a,7036,UNKNOWN,1500,2000000",
+                CSV_HEADER
+            ))
+        );
+        assert_eq!(
+            report.add(outcome("day17", "4,\"6\"", Status::Pass)),
+            Some("day17,example-1,a,\"4,\"\"6\"\"\",PASS,1500,2000000".to_string())
+        );
+        assert_eq!(report.finish(), None);
+    }
+}
@@ -0,0 +1,542 @@
+use solutionlib::InputShape;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// How a new day reads its input.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DayShape {
+    /// One entry per non-blank line, what the template already does
+    Lines,
+    /// Lines grouped by blank lines
+    Groups,
+    /// Lines parsed into a `gridlib::Grid<char>`
+    Grid,
+}
+
+impl DayShape {
+    /// The shape the runner hands the day its input in.
+    pub fn input_shape(&self) -> InputShape {
+        return match self {
+            DayShape::Lines => InputShape::Lines,
+            DayShape::Groups => InputShape::Groups,
+            DayShape::Grid => InputShape::Lines,
+        };
+    }
+}
+
+impl FromStr for DayShape {
+    type Err = String;
+
+    /// ```
+    /// use aoc::DayShape;
+    /// assert_eq!("grid".parse::<DayShape>(), Ok(DayShape::Grid));
+    /// assert!("text".parse::<DayShape>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return match s {
+            "lines" => Ok(DayShape::Lines),
+            "groups" => Ok(DayShape::Groups),
+            "grid" => Ok(DayShape::Grid),
+            _ => Err(format!(
+                "Unknown shape {}, expected lines, groups or grid",
+                s
+            )),
+        };
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ScaffoldError {
+    /// Advent of Code only has 25 days
+    InvalidDay(u32),
+    /// The day's folder is already there, nothing was written
+    AlreadyExists(PathBuf),
+    /// The template or registry no longer looks the way we expect
+    Template(PathBuf, String),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            ScaffoldError::InvalidDay(day) => write!(f, "day {} is not between 1 and 25", day),
+            ScaffoldError::AlreadyExists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Template(path, text) => {
+                write!(f, "could not find {:?} in {}", text, path.display())
+            }
+        };
+    }
+}
+
+/// Files the template crate has that a day should not get.
+const SKIPPED: [&str; 5] = ["target", "Cargo.lock", "input", "README.md", "template.day"];
+
+/// Creates a new day crate from `template/`, the way the README used to do by hand.
+///
+/// Besides the crate itself this writes a stub example fixture and registers
+/// the day with `solutionlib`, so `aoc run` picks it up straight away.
+pub struct Scaffold {
+    root: PathBuf,
+    day: u32,
+    shape: DayShape,
+}
+
+impl Scaffold {
+    pub fn new(root: &Path, day: u32, shape: DayShape) -> Scaffold {
+        return Scaffold {
+            root: root.to_path_buf(),
+            day: day,
+            shape: shape,
+        };
+    }
+
+    fn name(&self) -> String {
+        return format!("day{:02}", self.day);
+    }
+
+    /// Write everything, returning the files created or changed.
+    ///
+    /// All the edits are worked out before anything is written, so an error
+    /// leaves the tree as it was.
+    pub fn create(&self) -> Result<Vec<PathBuf>, ScaffoldError> {
+        if self.day == 0 || self.day > 25 {
+            return Err(ScaffoldError::InvalidDay(self.day));
+        }
+        let dest = self.root.join(self.name());
+        if dest.exists() {
+            return Err(ScaffoldError::AlreadyExists(dest));
+        }
+
+        let mut files = self.crate_files()?;
+        files.extend(self.fixture_files());
+        files.extend(self.registration()?);
+
+        let mut written = vec![];
+        for (path, contents) in files {
+            fs::create_dir_all(path.parent().unwrap()).expect("Could not create directory");
+            fs::write(&path, contents).expect("Something went wrong writing");
+            written.push(path);
+        }
+        return Ok(written);
+    }
+
+    /// The template crate with its name replaced, then shaped.
+    fn crate_files(&self) -> Result<Vec<(PathBuf, String)>, ScaffoldError> {
+        let template = self.root.join("template");
+        let dest = self.root.join(self.name());
+        let mut result = vec![];
+        for path in template_files(&template) {
+            let contents = fs::read_to_string(&path).expect("Something went wrong reading");
+            let relative = path.strip_prefix(&template).unwrap();
+            let mut contents = contents.replace("template", &self.name());
+            contents = self.shape_file(&path, relative, contents)?;
+            result.push((dest.join(relative), contents));
+        }
+        return Ok(result);
+    }
+
+    /// Rewrite the template's line based input handling for the chosen shape.
+    fn shape_file(
+        &self,
+        path: &Path,
+        relative: &Path,
+        contents: String,
+    ) -> Result<String, ScaffoldError> {
+        let edits = match (self.shape, relative.to_str().unwrap()) {
+            (DayShape::Groups, "src/lib.rs") => groups_lib_edits(),
+            (DayShape::Groups, "src/main.rs") => groups_main_edits(&self.name()),
+            (DayShape::Grid, "Cargo.toml") => grid_manifest_edits(),
+            (DayShape::Grid, "src/lib.rs") => grid_lib_edits(),
+            _ => vec![],
+        };
+
+        // Some template files have windows line endings, edit them as unix
+        // ones and put the endings back after.
+        let windows = contents.contains("\r\n");
+        let mut contents = contents.replace("\r\n", "\n");
+        for (from, to) in edits {
+            contents = replace_once(path, &contents, &from, &to)?;
+        }
+        if windows {
+            contents = contents.replace('\n', "\r\n");
+        }
+        return Ok(contents);
+    }
+
+    /// A stub example for the day, copied from the template's.
+    fn fixture_files(&self) -> Vec<(PathBuf, String)> {
+        let examples = self.root.join("examples");
+        let mut result = vec![];
+        for ext in ["txt", "expected"] {
+            let from = examples.join(format!("template-1.{}", ext));
+            let to = examples.join(format!("{}-1.{}", self.name(), ext));
+            if to.exists() || !from.exists() {
+                continue;
+            }
+            let contents = fs::read_to_string(&from).expect("Something went wrong reading");
+            result.push((to, contents));
+        }
+        return result;
+    }
+
+    /// Add the day to `solutionlib`'s dependencies and registry, in day order.
+    fn registration(&self) -> Result<Vec<(PathBuf, String)>, ScaffoldError> {
+        let name = self.name();
+        let solutionlib = self.root.join("solutionlib");
+
+        let manifest_path = solutionlib.join("Cargo.toml");
+        let manifest = fs::read_to_string(&manifest_path).expect("Something went wrong reading");
+        let dependency = format!("{} = {{ path = \"../{}\" }}\n", name, name);
+        let manifest = insert_sorted(&manifest_path, &manifest, "\n[dependencies]\n", &dependency)?;
+
+        let registry_path = solutionlib.join("src").join("registry.rs");
+        let registry = fs::read_to_string(&registry_path).expect("Something went wrong reading");
+        let registry = self.register(&registry_path, &registry)?;
+
+        return Ok(vec![(manifest_path, manifest), (registry_path, registry)]);
+    }
+
+    fn register(&self, path: &Path, registry: &str) -> Result<String, ScaffoldError> {
+        let name = self.name();
+        let (accessor, shape) = match self.shape.input_shape() {
+            InputShape::Groups => ("groups", "Groups"),
+            _ => ("lines", "Lines"),
+        };
+        let entry = format!(
+            "        Solution {{
+            day: {day},
+            shape: InputShape::{shape},
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| {name}::check_input(input.{accessor}()),
+            part_a: |input: &PuzzleInput, _: &[usize]| {name}::puzzle_a(input.{accessor}()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| {name}::puzzle_b(input.{accessor}()).to_string(),
+        }},
+",
+            day = self.day,
+            shape = shape,
+            name = name,
+            accessor = accessor,
+        );
+
+        let start_marker = "    return vec![\n";
+        let end_marker = "    ];\n";
+        let Some(start) = registry.find(start_marker) else {
+            return Err(ScaffoldError::Template(
+                path.to_path_buf(),
+                start_marker.to_string(),
+            ));
+        };
+        let start = start + start_marker.len();
+        let Some(end) = registry[start..].find(end_marker) else {
+            return Err(ScaffoldError::Template(
+                path.to_path_buf(),
+                end_marker.to_string(),
+            ));
+        };
+        let end = start + end;
+
+        // Go in front of the first entry for a later day
+        let mut position = end;
+        let mut offset = start;
+        for line in registry[start..end].split_inclusive('\n') {
+            if line == "        Solution {\n" {
+                let entry_day = registry[offset + line.len()..]
+                    .lines()
+                    .next()
+                    .and_then(|l| l.trim().strip_prefix("day: "))
+                    .and_then(|l| l.trim_end_matches(',').parse::<u32>().ok());
+                if entry_day.is_some_and(|d| d > self.day) {
+                    position = offset;
+                    break;
+                }
+            }
+            offset += line.len();
+        }
+        let mut result = registry.to_string();
+        result.insert_str(position, &entry);
+        return Ok(result);
+    }
+}
+
+const GRID_PARSE: &str = "fn parse(string_list: &Vec<String>) -> Grid<char> {
+    let height = string_list.len();
+    let width = string_list.first().unwrap().len();
+    let values = string_list.iter().flat_map(|line| line.chars()).collect();
+    return Grid::new(width, height, values);
+}
+";
+
+/// Pairs of text to find in a template file and what to replace it with.
+type Edits = Vec<(String, String)>;
+
+fn edit(from: &str, to: &str) -> (String, String) {
+    return (from.to_string(), to.to_string());
+}
+
+fn groups_lib_edits() -> Edits {
+    return vec![
+        edit(
+            "pub use filelib::load_no_blanks;",
+            "pub use filelib::{load, split_lines_by_blanks};",
+        ),
+        edit(
+            "check_input(string_list: &Vec<String>)",
+            "check_input(string_list: &Vec<Vec<String>>)",
+        ),
+        edit(
+            "puzzle_a(string_list: &Vec<String>)",
+            "puzzle_a(string_list: &Vec<Vec<String>>)",
+        ),
+        edit(
+            "puzzle_b(string_list: &Vec<String>)",
+            "puzzle_b(string_list: &Vec<Vec<String>>)",
+        ),
+        edit("fixture.lines()", "fixture.groups()"),
+        edit("fixture.lines()", "fixture.groups()"),
+    ];
+}
+
+fn groups_main_edits(name: &str) -> Edits {
+    return vec![
+        edit(&format!("use {}::load_no_blanks;\n", name), ""),
+        edit(
+            &format!("use {}::puzzle_b;\n", name),
+            &format!(
+                "use {}::puzzle_b;\nuse {}::{{load, split_lines_by_blanks}};\n",
+                name, name
+            ),
+        ),
+        edit(
+            "let lines = tracelib::parse_span().in_scope(|| load_no_blanks(filename));",
+            "let groups = tracelib::parse_span().in_scope(|| split_lines_by_blanks(&load(filename)));",
+        ),
+        edit("puzzle_a(&lines)", "puzzle_a(&groups)"),
+        edit("puzzle_b(&lines)", "puzzle_b(&groups)"),
+    ];
+}
+
+fn grid_manifest_edits() -> Edits {
+    return vec![edit(
+        "filelib = { path = \"../filelib\" }\n",
+        "filelib = { path = \"../filelib\" }\ngridlib = { path = \"../gridlib\" }\n",
+    )];
+}
+
+fn grid_lib_edits() -> Edits {
+    let start = "-> u32 {\n    return 0;";
+    let parsed = "-> u32 {\n    let grid = parse(string_list);\n    return 0;";
+    return vec![
+        edit(
+            "pub use filelib::load_no_blanks;\n",
+            "pub use filelib::load_no_blanks;\nuse gridlib::Grid;\n",
+        ),
+        edit(
+            "use tracelib::debug;\n",
+            &format!("use tracelib::debug;\n\n{}", GRID_PARSE),
+        ),
+        edit(start, parsed),
+        edit(start, parsed),
+    ];
+}
+
+/// Every file in the template crate worth copying.
+fn template_files(dir: &Path) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .expect("Could not read template directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_str().unwrap();
+            return !SKIPPED.contains(&name);
+        })
+        .collect();
+    entries.sort();
+    let mut result = vec![];
+    for path in entries {
+        if path.is_dir() {
+            result.extend(template_files(&path));
+        } else {
+            result.push(path);
+        }
+    }
+    return result;
+}
+
+fn replace_once(
+    path: &Path,
+    contents: &str,
+    from: &str,
+    to: &str,
+) -> Result<String, ScaffoldError> {
+    if !contents.contains(from) {
+        return Err(ScaffoldError::Template(
+            path.to_path_buf(),
+            from.to_string(),
+        ));
+    }
+    return Ok(contents.replacen(from, to, 1));
+}
+
+/// Insert `line` into the block of lines following `header`, keeping it sorted.
+fn insert_sorted(
+    path: &Path,
+    contents: &str,
+    header: &str,
+    line: &str,
+) -> Result<String, ScaffoldError> {
+    let Some(start) = contents.find(header) else {
+        return Err(ScaffoldError::Template(
+            path.to_path_buf(),
+            header.to_string(),
+        ));
+    };
+    let mut position = start + header.len();
+    for existing in contents[position..].split_inclusive('\n') {
+        if existing.trim().is_empty() || existing.as_bytes() > line.as_bytes() {
+            break;
+        }
+        position += existing.len();
+    }
+    let mut result = contents.to_string();
+    result.insert_str(position, line);
+    return Ok(result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("aoc-scaffold-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        return dir;
+    }
+
+    /// A copy of the real template, fixture and solutionlib, so the tests
+    /// notice when one of them drifts away from what the scaffold expects.
+    fn fake_root(name: &str) -> PathBuf {
+        let repo = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let dir = temp_dir(name);
+        for path in template_files(&repo.join("template")) {
+            let relative = path.strip_prefix(&repo).unwrap();
+            fs::create_dir_all(dir.join(relative).parent().unwrap()).unwrap();
+            fs::copy(&path, dir.join(relative)).unwrap();
+        }
+        fs::create_dir_all(dir.join("examples")).unwrap();
+        fs::create_dir_all(dir.join("solutionlib/src")).unwrap();
+        for file in [
+            "examples/template-1.txt",
+            "examples/template-1.expected",
+            "solutionlib/Cargo.toml",
+            "solutionlib/src/registry.rs",
+        ] {
+            fs::copy(repo.join(file), dir.join(file)).unwrap();
+        }
+        return dir;
+    }
+
+    #[test]
+    fn test_parse_shape() {
+        assert_eq!("lines".parse::<DayShape>(), Ok(DayShape::Lines));
+        assert_eq!("groups".parse::<DayShape>(), Ok(DayShape::Groups));
+        assert_eq!(DayShape::Grid.input_shape(), InputShape::Lines);
+    }
+
+    #[test]
+    fn test_new_lines_day() {
+        let root = fake_root("lines");
+        let written = Scaffold::new(&root, 25, DayShape::Lines).create().unwrap();
+        assert!(written.contains(&root.join("day25/src/lib.rs")));
+        assert!(written.contains(&root.join("day25/Makefile")));
+        assert!(!root.join("day25/input").exists());
+
+        let lib = fs::read_to_string(root.join("day25/src/lib.rs")).unwrap();
+        assert!(!lib.contains("template"));
+        assert!(lib
+            .contains("fixture_doc!(\"day25-1\", a, |fixture| day25::puzzle_a(&fixture.lines()))"));
+        let cargo = fs::read_to_string(root.join("day25/Cargo.toml")).unwrap();
+        assert!(cargo.contains("name = \"day25\""));
+        assert_eq!(
+            fs::read_to_string(root.join("examples/day25-1.expected")).unwrap(),
+            fs::read_to_string(root.join("examples/template-1.expected")).unwrap()
+        );
+
+        let manifest = fs::read_to_string(root.join("solutionlib/Cargo.toml")).unwrap();
+        assert!(manifest
+            .contains("day24 = { path = \"../day24\" }\nday25 = { path = \"../day25\" }\nfilelib"));
+        let registry = fs::read_to_string(root.join("solutionlib/src/registry.rs")).unwrap();
+        let entry = registry.find("day: 25,").unwrap();
+        assert!(registry.find("day: 24,").unwrap() < entry);
+        assert!(entry < registry.find("    ];\n").unwrap());
+        assert!(registry.contains("day25::puzzle_b(input.lines())"));
+
+        assert_eq!(
+            Scaffold::new(&root, 25, DayShape::Lines).create(),
+            Err(ScaffoldError::AlreadyExists(root.join("day25")))
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_new_groups_day() {
+        let root = fake_root("groups");
+        Scaffold::new(&root, 25, DayShape::Groups).create().unwrap();
+        let lib = fs::read_to_string(root.join("day25/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub use filelib::{load, split_lines_by_blanks};"));
+        assert!(lib.contains("pub fn puzzle_b(string_list: &Vec<Vec<String>>)"));
+        assert!(lib.contains("fixture.groups()"));
+        let main = fs::read_to_string(root.join("day25/src/main.rs")).unwrap();
+        assert!(main.contains("let groups = tracelib::parse_span().in_scope(|| split_lines_by_blanks(&load(filename)));"));
+        assert!(main.contains("puzzle_a(&groups)"));
+        let registry = fs::read_to_string(root.join("solutionlib/src/registry.rs")).unwrap();
+        assert!(registry.contains("shape: InputShape::Groups,\n            params: &[],\n            check: |input: &PuzzleInput, _: &[usize]| day25::check_input(input.groups()),\n            part_a: |input: &PuzzleInput, _: &[usize]| day25::puzzle_a(input.groups())"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_new_grid_day() {
+        let root = fake_root("grid");
+        Scaffold::new(&root, 25, DayShape::Grid).create().unwrap();
+        let lib = fs::read_to_string(root.join("day25/src/lib.rs")).unwrap();
+        assert!(lib.contains("use gridlib::Grid;"));
+        assert!(lib.contains("fn parse(string_list: &Vec<String>) -> Grid<char>"));
+        assert_eq!(lib.matches("let grid = parse(string_list);").count(), 2);
+        let cargo = fs::read_to_string(root.join("day25/Cargo.toml")).unwrap();
+        assert!(cargo.contains("gridlib = { path = \"../gridlib\" }"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_register_in_order() {
+        let registry = "    return vec![
+        Solution {
+            day: 1,
+        },
+        Solution {
+            day: 3,
+        },
+    ];
+";
+        let result = Scaffold::new(Path::new("/"), 2, DayShape::Lines)
+            .register(Path::new("registry.rs"), registry)
+            .unwrap();
+        let days: Vec<&str> = result
+            .lines()
+            .filter_map(|l| l.trim().strip_prefix("day: "))
+            .collect();
+        assert_eq!(days, vec!["1,", "2,", "3,"]);
+    }
+
+    #[test]
+    fn test_invalid_day() {
+        let root = temp_dir("invalid");
+        assert_eq!(
+            Scaffold::new(&root, 26, DayShape::Lines).create(),
+            Err(ScaffoldError::InvalidDay(26))
+        );
+        assert!(fs::read_dir(&root).unwrap().next().is_none());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,250 @@
+use serde::Serialize;
+use solutionlib::{find_solution, Part};
+use std::io;
+use std::net::SocketAddr;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+use tiny_http::{Header, Request, Response, Server};
+
+/// What `POST /<year>/day/<n>/part/<a|b>` answers with.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Solved {
+    pub day: u32,
+    pub part: String,
+    pub answer: String,
+    /// Splitting the body and the day's `check_input`, like `run`'s
+    pub parse_ns: u64,
+    pub solve_ns: u64,
+}
+
+#[derive(Serialize)]
+struct Failure {
+    error: String,
+}
+
+/// A status code and the JSON to send with it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Reply {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Reply {
+    fn ok(solved: &Solved) -> Reply {
+        return Reply {
+            status: 200,
+            body: serde_json::to_string(solved).unwrap(),
+        };
+    }
+
+    fn error(status: u16, error: String) -> Reply {
+        return Reply {
+            status: status,
+            body: serde_json::to_string(&Failure { error: error }).unwrap(),
+        };
+    }
+}
+
+/// `/2024/day/14/part/a?params=7,11` split into day, part and params.
+fn parse_path(year: u32, url: &str) -> Result<(u32, Part, Option<Vec<usize>>), Reply> {
+    let not_found = || Reply::error(404, format!("No such endpoint {}", url));
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let [y, "day", day, "part", part] = segments[..] else {
+        return Err(not_found());
+    };
+    if y != year.to_string() {
+        return Err(not_found());
+    }
+    let day: u32 = day.parse().map_err(|_| not_found())?;
+    let part = match part {
+        "a" => Part::A,
+        "b" => Part::B,
+        _ => return Err(not_found()),
+    };
+    let mut params = None;
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let Some(("params", value)) = pair.split_once('=') else {
+            return Err(Reply::error(400, format!("Unknown query {}", pair)));
+        };
+        let parsed: Result<Vec<usize>, _> = value.split(',').map(|v| v.parse()).collect();
+        let parsed = parsed.map_err(|_| Reply::error(400, format!("Bad params {}", value)))?;
+        params = Some(parsed);
+    }
+    return Ok((day, part, params));
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        return s.to_string();
+    }
+    if let Some(s) = payload.downcast_ref::<String>() {
+        return s.clone();
+    }
+    return "solver panicked".to_string();
+}
+
+/// Answer one request, kept apart from the socket so it can be tested directly.
+///
+/// The body is the puzzle input. Days that take params, like the grid size
+/// for day 14, use their real input's unless `?params=7,11` is given.
+pub fn handle(year: u32, method: &str, url: &str, body: &str) -> Reply {
+    let (day, part, params) = match parse_path(year, url) {
+        Ok(route) => route,
+        Err(reply) => return reply,
+    };
+    if method != "POST" {
+        return Reply::error(405, format!("{} is POST only", url));
+    }
+    let Some(solution) = find_solution(day) else {
+        return Reply::error(404, format!("Day {} isn't solved", day));
+    };
+    let params = params.unwrap_or(solution.params.to_vec());
+    if params.len() != solution.params.len() {
+        return Reply::error(
+            400,
+            format!(
+                "Day {} takes {} params, got {}",
+                day,
+                solution.params.len(),
+                params.len()
+            ),
+        );
+    }
+
+    let start = Instant::now();
+    let input = solution.parse(body);
+    if let Err(e) = solution.check_with(&input, &params) {
+        return Reply::error(400, e.to_string());
+    }
+    let parse_time = start.elapsed();
+
+    // Checked input shouldn't panic, but a solver that does anyway shouldn't
+    // take the server down
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let start = Instant::now();
+        let answer = solution.solve_with(part, &input, &params);
+        return (answer, parse_time, start.elapsed());
+    }));
+    return match result {
+        Ok((answer, parse_time, solve_time)) => Reply::ok(&Solved {
+            day: day,
+            part: part.to_string(),
+            answer: answer,
+            parse_ns: parse_time.as_nanos() as u64,
+            solve_ns: solve_time.as_nanos() as u64,
+        }),
+        Err(payload) => Reply::error(
+            422,
+            format!("Could not solve: {}", panic_message(payload.as_ref())),
+        ),
+    };
+}
+
+/// Serves every day over HTTP, one request at a time.
+pub struct SolveServer {
+    server: Server,
+    year: u32,
+}
+
+impl SolveServer {
+    /// Listen on `addr`, port 0 picks a free one.
+    pub fn bind(addr: &str, year: u32) -> io::Result<SolveServer> {
+        let server = Server::http(addr).map_err(io::Error::other)?;
+        return Ok(SolveServer {
+            server: server,
+            year: year,
+        });
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        return self.server.server_addr().to_ip().unwrap();
+    }
+
+    fn respond(&self, mut request: Request) {
+        let mut body = String::new();
+        let reply = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => handle(
+                self.year,
+                &request.method().to_string(),
+                request.url(),
+                &body,
+            ),
+            Err(_) => Reply::error(400, "Input is not UTF-8".to_string()),
+        };
+        let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+        let response = Response::from_string(reply.body)
+            .with_status_code(reply.status)
+            .with_header(content_type);
+        // The client going away is its problem
+        let _ = request.respond(response);
+    }
+
+    /// Handle requests until the process is stopped.
+    pub fn run(&self) {
+        for request in self.server.incoming_requests() {
+            self.respond(request);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    const DAY01: &str = "3 4\n4 3\n2 5\n1 3\n3 9\n3 3\n";
+
+    #[test]
+    fn test_handle() {
+        let reply = handle(2024, "POST", "/2024/day/1/part/b", DAY01);
+        assert_eq!(reply.status, 200);
+        let json: serde_json::Value = serde_json::from_str(&reply.body).unwrap();
+        assert_eq!(json["day"], 1);
+        assert_eq!(json["part"], "b");
+        assert_eq!(json["answer"], "31");
+        assert!(json["solve_ns"].is_u64());
+    }
+
+    #[test]
+    fn test_handle_params() {
+        let input = "p=2,4 v=2,-3\n";
+        let reply = handle(2024, "POST", "/2024/day/14/part/a?params=7,11", input);
+        assert_eq!(reply.status, 200);
+        let reply = handle(2024, "POST", "/2024/day/14/part/a?params=7", input);
+        assert_eq!(reply.status, 400);
+        let reply = handle(2024, "POST", "/2024/day/14/part/a?params=x", input);
+        assert_eq!(reply.status, 400);
+    }
+
+    #[test]
+    fn test_handle_errors() {
+        let status = |method: &str, url: &str| handle(2024, method, url, DAY01).status;
+        assert_eq!(status("POST", "/2023/day/1/part/a"), 404);
+        assert_eq!(status("POST", "/2024/day/26/part/a"), 404);
+        assert_eq!(status("POST", "/2024/day/1/part/c"), 404);
+        assert_eq!(status("POST", "/2024/day/1"), 404);
+        assert_eq!(status("GET", "/2024/day/1/part/a"), 405);
+        // A maze with no end, which check_input turns away before solving
+        let reply = handle(2024, "POST", "/2024/day/16/part/a", "#####\n#S..#\n#####\n");
+        assert_eq!(reply.status, 400);
+        assert!(reply.body.contains("maze needs a start and an end"));
+    }
+
+    #[test]
+    fn test_serve() {
+        let server = SolveServer::bind("127.0.0.1:0", 2024).unwrap();
+        let url = format!("http://{}/2024/day/1/part/a", server.addr());
+        thread::spawn(move || server.run());
+
+        let mut response = ureq::post(&url).send(DAY01).unwrap();
+        assert_eq!(response.status(), 200);
+        let json: serde_json::Value =
+            serde_json::from_str(&response.body_mut().read_to_string().unwrap()).unwrap();
+        assert_eq!(json["answer"], "11");
+
+        let missing = url.replace("day/1/", "day/26/");
+        let error = ureq::post(&missing).send(DAY01).unwrap_err();
+        assert!(matches!(error, ureq::Error::StatusCode(404)));
+    }
+}
@@ -0,0 +1,91 @@
+//! Robot swarms for day 14.
+
+use crate::seeded_rng;
+use rand::Rng;
+
+/// Robots on a `width` by `height` floor that draw a tree after `tree_at` seconds.
+///
+/// The tree is a triangle filling the top left quadrant, one robot per cell,
+/// and `noise` more robots wander at random. The tree piles nearly every
+/// robot into one quadrant, so its second has the lowest safety factor. That
+/// needs a balance: every `width` seconds the tree lines up in one half again,
+/// which beats the tree if there's too much noise, and with too little a
+/// quadrant can be empty for a safety factor of 0. About the real size, 101
+/// by 103 with 100 wandering, works.
+/// ```
+/// let robots = aocgen::day14::robots(1, 11, 7, 5, 3);
+/// assert_eq!(robots.lines().count(), 9 + 3);
+/// assert!(robots.lines().all(|line| line.starts_with("p=")));
+/// ```
+pub fn robots(seed: u64, width: usize, height: usize, tree_at: usize, noise: usize) -> String {
+    let mut rng = seeded_rng(seed);
+    let (width, height, tree_at) = (width as i64, height as i64, tree_at as i64);
+    let (quadrant_width, quadrant_height) = (width / 2, height / 2);
+    let tree_height = quadrant_height.min((quadrant_width + 1) / 2);
+
+    let mut targets = vec![];
+    for row in 0..tree_height {
+        let middle = quadrant_width / 2;
+        for x in middle - row..=middle + row {
+            targets.push((x, row));
+        }
+    }
+    for _ in 0..noise {
+        targets.push((rng.random_range(0..width), rng.random_range(0..height)));
+    }
+
+    let mut lines = vec![];
+    for (x, y) in targets {
+        let v_x = rng.random_range(-width / 2..=width / 2);
+        let v_y = rng.random_range(-height / 2..=height / 2);
+        // Run the robot backwards from where it needs to be
+        let p_x = (x - v_x * tree_at).rem_euclid(width);
+        let p_y = (y - v_y * tree_at).rem_euclid(height);
+        lines.push(format!("p={},{} v={},{}", p_x, p_y, v_x, v_y));
+    }
+    return lines.join("\n");
+}
+
+/// A `size` wide floor, two taller like the real 101 by 103, with `size` wandering robots.
+///
+/// The width is rounded up to odd, so the sides share no factor and the tree
+/// only shows once in `width * height` seconds, and to at least 5 so each
+/// quadrant has room and the tree has a robot in it.
+pub fn generate(seed: u64, size: usize) -> String {
+    let width = size.max(5) | 1;
+    let height = width + 2;
+    let tree_at = seeded_rng(seed).random_range(1..=width * height);
+    return robots(seed, width, height, tree_at, size);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filelib::remove_blanks;
+
+    #[test]
+    fn test_finds_the_tree() {
+        for (seed, tree_at) in [(0, 1), (1, 7138), (2, 101 * 103)] {
+            let lines = remove_blanks(&robots(seed, 101, 103, tree_at, 100));
+            assert_eq!(day14::check_input(&lines), Ok(()));
+            assert_eq!(day14::puzzle_b(&lines, 103, 101), tree_at);
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        let lines = remove_blanks(&generate(3, 30));
+        // 31 wide, so a tree 8 tall plus 30 wandering
+        assert_eq!(lines.len(), 64 + 30);
+        assert_eq!(generate(7, 31), generate(7, 31));
+        assert_ne!(generate(7, 31), generate(8, 31));
+    }
+
+    #[test]
+    fn test_smallest_floor() {
+        // 5 by 7, a one robot tree and nothing wandering
+        let lines = remove_blanks(&generate(3, 0));
+        assert_eq!(lines.len(), 1);
+        assert!(day14::puzzle_b(&lines, 7, 5) <= 5 * 7);
+    }
+}
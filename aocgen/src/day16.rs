@@ -0,0 +1,70 @@
+//! Reindeer mazes for day 16.
+
+use crate::maze::{add_loops, carve, odd_size};
+use crate::seeded_rng;
+use gridlib::{GridCoordinate, GridTraversable};
+
+/// A `size` square maze with S in the bottom left and E in the top right, like the real input.
+///
+/// It starts with exactly one route, then `loops` walls between corridors are
+/// knocked out so there are other routes, and ties for part b. `size` is
+/// rounded up to odd.
+/// ```
+/// let maze = aocgen::day16::maze(1, 7, 0);
+/// assert_eq!(maze.lines().count(), 7);
+/// assert!(maze.lines().nth(5).unwrap().starts_with("#S"));
+/// assert!(maze.lines().nth(1).unwrap().ends_with("E#"));
+/// ```
+pub fn maze(seed: u64, size: usize, loops: usize) -> String {
+    let size = odd_size(size);
+    let mut rng = seeded_rng(seed);
+    let mut grid = carve(&mut rng, size);
+    add_loops(&mut rng, &mut grid, loops);
+    grid.set_value(GridCoordinate::new(1, size - 2), 'S');
+    grid.set_value(GridCoordinate::new(size - 2, 1), 'E');
+    return grid.grid_strings().join("\n");
+}
+
+/// A maze with a loop for every row.
+pub fn generate(seed: u64, size: usize) -> String {
+    return maze(seed, size, size);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::route;
+    use filelib::remove_blanks;
+
+    #[test]
+    fn test_maze_is_valid() {
+        for seed in 0..5 {
+            let lines = remove_blanks(&generate(seed, 21));
+            assert_eq!(day16::check_input(&lines), Ok(()));
+            assert!(day16::puzzle_a(&lines) > 0);
+        }
+    }
+
+    #[test]
+    fn test_one_route_without_loops() {
+        let text = maze(2, 15, 0);
+        let lines = remove_blanks(&text);
+        let mut grid = carve(&mut seeded_rng(2), 15);
+        let path = route(
+            &grid,
+            GridCoordinate::new(1, 13),
+            GridCoordinate::new(13, 1),
+        );
+        // Only one route, so every tile on it is on the best path
+        assert_eq!(day16::puzzle_b(&lines), path.len());
+        grid.set_value(GridCoordinate::new(1, 13), 'S');
+        grid.set_value(GridCoordinate::new(13, 1), 'E');
+        assert_eq!(grid.grid_strings().join("\n"), text);
+    }
+
+    #[test]
+    fn test_same_seed_same_maze() {
+        assert_eq!(generate(7, 31), generate(7, 31));
+        assert_ne!(generate(7, 31), generate(8, 31));
+    }
+}
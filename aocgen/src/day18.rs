@@ -0,0 +1,95 @@
+//! Falling bytes for day 18.
+
+use crate::maze::{carve, odd_size, route};
+use crate::seeded_rng;
+use gridlib::GridCoordinate;
+use rand::seq::SliceRandom;
+use std::collections::HashSet;
+
+/// Bytes falling into a `size` square memory space, one `x,y` per line.
+///
+/// Every cell but the two corners falls eventually, so part b always has an
+/// answer. The first `safe` bytes keep clear of a winding route between the
+/// corners, so part a has a path using up to that many. `safe` is capped at
+/// the number of cells off the route. `size` is rounded up to odd.
+/// ```
+/// let bytes = aocgen::day18::bytes(1, 7, 12);
+/// assert_eq!(bytes.lines().count(), 7 * 7 - 2);
+/// assert!(!bytes.lines().any(|line| line == "0,0" || line == "6,6"));
+/// ```
+pub fn bytes(seed: u64, size: usize, safe: usize) -> String {
+    let size = odd_size(size);
+    let mut rng = seeded_rng(seed);
+    // Carve a maze two bigger and drop its border, so its cells land on the
+    // even coordinates and the corners are open.
+    let maze = carve(&mut rng, size + 2);
+    let mut kept: HashSet<GridCoordinate> = HashSet::new();
+    for pos in route(
+        &maze,
+        GridCoordinate::new(1, 1),
+        GridCoordinate::new(size, size),
+    ) {
+        kept.insert(GridCoordinate::new(pos.x - 1, pos.y - 1));
+    }
+
+    let all: Vec<GridCoordinate> = (0..size)
+        .flat_map(|y| (0..size).map(move |x| GridCoordinate::new(x, y)))
+        .filter(|&pos| pos != GridCoordinate::new(0, 0))
+        .filter(|&pos| pos != GridCoordinate::new(size - 1, size - 1))
+        .collect();
+    let mut off_route: Vec<GridCoordinate> = all
+        .iter()
+        .copied()
+        .filter(|pos| !kept.contains(pos))
+        .collect();
+    off_route.shuffle(&mut rng);
+    let safe = safe.min(off_route.len());
+    let mut rest: Vec<GridCoordinate> = off_route.split_off(safe);
+    rest.extend(all.iter().copied().filter(|pos| kept.contains(pos)));
+    rest.shuffle(&mut rng);
+
+    let lines: Vec<String> = off_route
+        .into_iter()
+        .chain(rest)
+        .map(|pos| format!("{},{}", pos.x, pos.y))
+        .collect();
+    return lines.join("\n");
+}
+
+/// Safe for the 1024 bytes the real part a drops, when there's room.
+pub fn generate(seed: u64, size: usize) -> String {
+    return bytes(seed, size, 1024);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filelib::remove_blanks;
+
+    #[test]
+    fn test_bytes_are_valid() {
+        for seed in 0..5 {
+            let lines = remove_blanks(&bytes(seed, 15, 100));
+            assert_eq!(day18::check_input(&lines, 15, 15), Ok(()));
+            assert!(day18::puzzle_a(&lines, 15, 15, 100) >= 2 * 14);
+            let (x, y) = day18::puzzle_b(&lines, 15, 15, 100);
+            let blocker = lines
+                .iter()
+                .position(|line| *line == format!("{},{}", x, y));
+            assert!(blocker.unwrap() >= 100);
+        }
+    }
+
+    #[test]
+    fn test_every_cell_but_the_corners() {
+        let lines = remove_blanks(&generate(1, 9));
+        let unique: HashSet<&String> = lines.iter().collect();
+        assert_eq!(unique.len(), 9 * 9 - 2);
+    }
+
+    #[test]
+    fn test_same_seed_same_bytes() {
+        assert_eq!(generate(7, 31), generate(7, 31));
+        assert_ne!(generate(7, 31), generate(8, 31));
+    }
+}
@@ -0,0 +1,79 @@
+//! Racetracks for day 20.
+
+use crate::maze::{carve, odd_size, route, WALL};
+use crate::seeded_rng;
+use gridlib::{Grid, GridCoordinate, GridTraversable};
+
+/// A `size` square racetrack, a single track from S in the bottom left to E in the top right.
+///
+/// The track is the only route through a maze, with the rest of the maze
+/// filled back in, so there are plenty of walls to cheat through. `size` is
+/// rounded up to odd.
+/// ```
+/// let track = aocgen::day20::racetrack(1, 9);
+/// assert_eq!(track.lines().count(), 9);
+/// assert_eq!(track.matches('S').count(), 1);
+/// assert_eq!(track.matches('E').count(), 1);
+/// ```
+pub fn racetrack(seed: u64, size: usize) -> String {
+    let size = odd_size(size);
+    let maze = carve(&mut seeded_rng(seed), size);
+    let start = GridCoordinate::new(1, size - 2);
+    let end = GridCoordinate::new(size - 2, 1);
+    let mut grid = Grid::new(size, size, vec![WALL; size * size]);
+    for pos in route(&maze, start, end) {
+        grid.set_value(pos, '.');
+    }
+    grid.set_value(start, 'S');
+    grid.set_value(end, 'E');
+    return grid.grid_strings().join("\n");
+}
+
+pub fn generate(seed: u64, size: usize) -> String {
+    return racetrack(seed, size);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filelib::remove_blanks;
+
+    #[test]
+    fn test_racetrack_is_valid() {
+        for seed in 0..5 {
+            let lines = remove_blanks(&generate(seed, 21));
+            assert_eq!(day20::check_input(&lines), Ok(()));
+            // A winding track always has a wall worth cutting through
+            assert!(day20::puzzle_a(&lines, 2) > 0);
+        }
+    }
+
+    #[test]
+    fn test_track_never_touches_itself() {
+        let lines = remove_blanks(&racetrack(3, 21));
+        let cell = |x: usize, y: usize| lines[y].as_bytes()[x];
+        let open = |x: usize, y: usize| cell(x, y) != b'#';
+        for y in 1..20 {
+            for x in 1..20 {
+                if !open(x, y) {
+                    continue;
+                }
+                let neighbours = [
+                    open(x - 1, y),
+                    open(x + 1, y),
+                    open(x, y - 1),
+                    open(x, y + 1),
+                ];
+                let count = neighbours.iter().filter(|&&n| n).count();
+                let end = matches!(cell(x, y), b'S' | b'E');
+                assert_eq!(count, if end { 1 } else { 2 }, "at {},{}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_track() {
+        assert_eq!(generate(7, 31), generate(7, 31));
+        assert_ne!(generate(7, 31), generate(8, 31));
+    }
+}
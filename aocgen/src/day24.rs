@@ -0,0 +1,152 @@
+//! Broken adders for day 24.
+
+use crate::seeded_rng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Gate outputs within one full adder that can be swapped without making a loop.
+///
+/// Offsets are x XOR y, the z XOR, x AND y, the carry AND, and the carry OR.
+/// These are the kinds of swap the real inputs have.
+const SWAPS: [(usize, usize); 4] = [(1, 2), (1, 3), (1, 4), (0, 2)];
+
+/// Three lowercase letters, not starting with x, y or z, that haven't been used yet.
+fn wire_name<R: Rng>(rng: &mut R, used: &mut HashSet<String>) -> String {
+    loop {
+        let first = rng.random_range(b'a'..b'x') as char;
+        let rest: String = (0..2)
+            .map(|_| rng.random_range(b'a'..=b'z') as char)
+            .collect();
+        let name = format!("{}{}", first, rest);
+        if used.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// A `bits` wide ripple carry adder with `swaps` pairs of gate outputs swapped.
+///
+/// Gives the input and the answer to part b. Like the real input, each swap
+/// is inside one bit's full adder, never in the first or last bit, and no bit
+/// has two, so `swaps` is capped at `bits - 2`. The x and y wires are random
+/// and the gates are shuffled. `bits` is rounded up to 2, the smallest adder
+/// with both a half adder and a full one carrying into the top z.
+/// ```
+/// let (input, answer) = aocgen::day24::adder(1, 6, 2);
+/// assert_eq!(input.lines().filter(|line| line.contains("->")).count(), 2 + 5 * 5);
+/// assert_eq!(answer.split(',').count(), 4);
+/// ```
+pub fn adder(seed: u64, bits: usize, swaps: usize) -> (String, String) {
+    let bits = bits.max(2);
+    let mut rng = seeded_rng(seed);
+    let mut used = HashSet::new();
+    let mut name = |rng: &mut _| wire_name(rng, &mut used);
+
+    // Bit 0 is a half adder, two gates, then five for each full adder
+    let mut carry = name(&mut rng);
+    let mut gates = vec![
+        (
+            "x00".to_string(),
+            "XOR",
+            "y00".to_string(),
+            "z00".to_string(),
+        ),
+        ("x00".to_string(), "AND", "y00".to_string(), carry.clone()),
+    ];
+    for i in 1..bits {
+        let (x, y, z) = (
+            format!("x{:02}", i),
+            format!("y{:02}", i),
+            format!("z{:02}", i),
+        );
+        let (sum, both, carried) = (name(&mut rng), name(&mut rng), name(&mut rng));
+        let next_carry = if i + 1 == bits {
+            format!("z{:02}", bits)
+        } else {
+            name(&mut rng)
+        };
+        gates.push((x.clone(), "XOR", y.clone(), sum.clone()));
+        gates.push((sum.clone(), "XOR", carry.clone(), z));
+        gates.push((x, "AND", y, both.clone()));
+        gates.push((sum, "AND", carry, carried.clone()));
+        gates.push((both, "OR", carried, next_carry.clone()));
+        carry = next_carry;
+    }
+
+    let mut candidates: Vec<usize> = (1..bits.saturating_sub(1)).collect();
+    candidates.shuffle(&mut rng);
+    let mut swapped = vec![];
+    for bit in candidates.into_iter().take(swaps) {
+        let &(a, b) = SWAPS.choose(&mut rng).unwrap();
+        let (a, b) = (2 + (bit - 1) * 5 + a, 2 + (bit - 1) * 5 + b);
+        let out_a = gates[a].3.clone();
+        gates[a].3 = gates[b].3.clone();
+        gates[b].3 = out_a;
+        swapped.push(gates[a].3.clone());
+        swapped.push(gates[b].3.clone());
+    }
+    swapped.sort();
+
+    let mut lines = vec![];
+    for prefix in ["x", "y"] {
+        for i in 0..bits {
+            lines.push(format!("{}{:02}: {}", prefix, i, rng.random_range(0..=1)));
+        }
+    }
+    lines.push(String::new());
+    gates.shuffle(&mut rng);
+    for (a, op, b, out) in gates {
+        let (a, b) = if rng.random() { (b, a) } else { (a, b) };
+        lines.push(format!("{} {} {} -> {}", a, op, b, out));
+    }
+    return (lines.join("\n"), swapped.join(","));
+}
+
+/// A `size` bit adder with four swaps, like the real 45 bit one.
+pub fn generate(seed: u64, size: usize) -> String {
+    let (input, _) = adder(seed, size, 4);
+    return input;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filelib::split_lines_by_blanks;
+
+    #[test]
+    fn test_repair_finds_the_swaps() {
+        for seed in 0..5 {
+            let (input, answer) = adder(seed, 45, 4);
+            let groups = split_lines_by_blanks(&input);
+            assert_eq!(day24::check_input(&groups), Ok(()));
+            assert_eq!(day24::puzzle_b(&groups, 4), answer);
+            // Part a still has to run on it
+            day24::puzzle_a(&groups);
+        }
+    }
+
+    #[test]
+    fn test_swaps_capped() {
+        let (_, answer) = adder(1, 4, 4);
+        assert_eq!(answer.split(',').count(), 4);
+        let (_, answer) = adder(1, 4, 0);
+        assert_eq!(answer, "");
+    }
+
+    #[test]
+    fn test_smallest_adder() {
+        for bits in [0, 1, 2] {
+            let (input, answer) = adder(1, bits, 4);
+            assert!(input.contains("x01: "));
+            assert!(input.contains("-> z02"));
+            assert_eq!(answer, "");
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_adder() {
+        assert_eq!(generate(7, 10), generate(7, 10));
+        assert_ne!(generate(7, 10), generate(8, 10));
+    }
+}
@@ -0,0 +1,26 @@
+//! Seeded random puzzle inputs, for stress testing and benchmarking days
+//! beyond the one personal input.
+//!
+//! The same seed always gives the same input.
+
+mod maze;
+
+pub mod day14;
+pub mod day16;
+pub mod day18;
+pub mod day20;
+pub mod day24;
+
+mod registry;
+
+pub use crate::registry::find_generator;
+pub use crate::registry::generators;
+pub use crate::registry::Generator;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// ChaCha8 rather than `StdRng`, so seeds give the same input across rand versions.
+fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    return ChaCha8Rng::seed_from_u64(seed);
+}
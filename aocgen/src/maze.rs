@@ -0,0 +1,143 @@
+use gridlib::{Direction, Grid, GridCoordinate, GridTraversable};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::{HashMap, VecDeque};
+
+pub const WALL: char = '#';
+pub const OPEN: char = '.';
+
+/// Round a maze size up to odd, and big enough to have a corridor.
+pub fn odd_size(size: usize) -> usize {
+    return size.max(5) | 1;
+}
+
+/// Two steps in a direction, the next cell over in a maze.
+fn jump(grid: &Grid<char>, pos: GridCoordinate, dir: Direction) -> Option<GridCoordinate> {
+    let wall = grid.get_coordinate_by_direction(pos, dir)?;
+    return grid.get_coordinate_by_direction(wall, dir);
+}
+
+/// A maze with exactly one route between any two open cells, on a `size` square of walls.
+///
+/// Cells sit at odd coordinates with walls between, carved with a randomised
+/// depth first search. `size` must be odd so the border is all wall.
+pub fn carve<R: Rng>(rng: &mut R, size: usize) -> Grid<char> {
+    let mut grid = Grid::new(size, size, vec![WALL; size * size]);
+    let start = GridCoordinate::new(1, 1);
+    grid.set_value(start, OPEN);
+    let mut stack = vec![start];
+    while let Some(&pos) = stack.last() {
+        let mut options: Vec<Direction> = Direction::cardinal_iterator()
+            .copied()
+            .filter(|&dir| match jump(&grid, pos, dir) {
+                Some(next) => {
+                    next.x < size - 1 && next.y < size - 1 && grid.get_value(next) == Some(WALL)
+                }
+                None => false,
+            })
+            .collect();
+        if options.is_empty() {
+            stack.pop();
+            continue;
+        }
+        options.shuffle(rng);
+        let dir = options[0];
+        let wall = grid.get_coordinate_by_direction(pos, dir).unwrap();
+        let next = jump(&grid, pos, dir).unwrap();
+        grid.set_value(wall, OPEN);
+        grid.set_value(next, OPEN);
+        stack.push(next);
+    }
+    return grid;
+}
+
+/// Knock out up to `count` walls that sit between two corridors, so there is more than one route.
+pub fn add_loops<R: Rng>(rng: &mut R, grid: &mut Grid<char>, count: usize) {
+    let size = grid.get_width();
+    let mut knocked = 0;
+    // Most picks land on a wall that can't go, so give up eventually on small mazes
+    for _ in 0..count * 20 {
+        if knocked == count {
+            break;
+        }
+        let pos = GridCoordinate::new(rng.random_range(1..size - 1), rng.random_range(1..size - 1));
+        // Only walls between two cells, not the pillars at their corners
+        if (pos.x + pos.y).is_multiple_of(2) || grid.get_value(pos) != Some(WALL) {
+            continue;
+        }
+        let open = |dir: Direction| {
+            return grid
+                .get_coordinate_by_direction(pos, dir)
+                .and_then(|c| grid.get_value(c))
+                == Some(OPEN);
+        };
+        let horizontal = open(Direction::EAST) && open(Direction::WEST);
+        let vertical = open(Direction::NORTH) && open(Direction::SOUTH);
+        if horizontal != vertical {
+            grid.set_value(pos, OPEN);
+            knocked += 1;
+        }
+    }
+}
+
+/// The shortest route through open cells, both ends included.
+pub fn route(grid: &Grid<char>, start: GridCoordinate, end: GridCoordinate) -> Vec<GridCoordinate> {
+    let mut came_from: HashMap<GridCoordinate, GridCoordinate> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    came_from.insert(start, start);
+    while let Some(pos) = queue.pop_front() {
+        if pos == end {
+            break;
+        }
+        for next in grid.get_adjacent_coordinates(pos) {
+            if grid.get_value(next) == Some(OPEN) && !came_from.contains_key(&next) {
+                came_from.insert(next, pos);
+                queue.push_back(next);
+            }
+        }
+    }
+    let mut result = vec![end];
+    let mut pos = end;
+    while pos != start {
+        pos = came_from[&pos];
+        result.push(pos);
+    }
+    result.reverse();
+    return result;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seeded_rng;
+
+    #[test]
+    fn test_carve_reaches_every_cell() {
+        let grid = carve(&mut seeded_rng(3), 9);
+        for y in (1..9).step_by(2) {
+            for x in (1..9).step_by(2) {
+                let path = route(&grid, GridCoordinate::new(1, 1), GridCoordinate::new(x, y));
+                assert_eq!(path.last(), Some(&GridCoordinate::new(x, y)));
+            }
+        }
+        // A tree on 16 cells has 15 corridors between them
+        let open = grid.data_copy().into_iter().filter(|&c| c == OPEN).count();
+        assert_eq!(open, 16 + 15);
+    }
+
+    #[test]
+    fn test_add_loops() {
+        let mut grid = carve(&mut seeded_rng(3), 21);
+        let before = grid.data_copy().into_iter().filter(|&c| c == OPEN).count();
+        add_loops(&mut seeded_rng(4), &mut grid, 5);
+        let after = grid.data_copy().into_iter().filter(|&c| c == OPEN).count();
+        assert_eq!(after, before + 5);
+    }
+
+    #[test]
+    fn test_odd_size() {
+        assert_eq!(odd_size(1), 5);
+        assert_eq!(odd_size(8), 9);
+        assert_eq!(odd_size(141), 141);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{day14, day16, day18, day20, day24};
+
+/// How to make inputs for a day.
+///
+/// What `size` means depends on the day, it's whatever makes the input bigger.
+#[derive(Debug, Copy, Clone)]
+pub struct Generator {
+    pub day: u32,
+    /// The size of the real input
+    pub default_size: usize,
+    pub generate: fn(u64, usize) -> String,
+}
+
+/// Every day with a generator, in order.
+pub fn generators() -> Vec<Generator> {
+    return vec![
+        Generator {
+            day: 14,
+            default_size: 101,
+            generate: day14::generate,
+        },
+        Generator {
+            day: 16,
+            default_size: 141,
+            generate: day16::generate,
+        },
+        Generator {
+            day: 18,
+            default_size: 71,
+            generate: day18::generate,
+        },
+        Generator {
+            day: 20,
+            default_size: 141,
+            generate: day20::generate,
+        },
+        Generator {
+            day: 24,
+            default_size: 45,
+            generate: day24::generate,
+        },
+    ];
+}
+
+pub fn find_generator(day: u32) -> Option<Generator> {
+    return generators().into_iter().find(|g| g.day == day);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filelib::{remove_blanks, split_lines_by_blanks};
+
+    #[test]
+    fn test_days_in_order() {
+        let days: Vec<u32> = generators().iter().map(|g| g.day).collect();
+        let mut sorted = days.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(days, sorted);
+        assert!(find_generator(16).is_some());
+        assert!(find_generator(1).is_none());
+    }
+
+    #[test]
+    fn test_default_sizes() {
+        for generator in generators() {
+            let input = (generator.generate)(1, generator.default_size);
+            assert!(!input.is_empty(), "day {}", generator.day);
+        }
+    }
+
+    #[test]
+    fn test_smallest_sizes_are_valid() {
+        for generator in generators() {
+            for size in [0, 1] {
+                let input = (generator.generate)(1, size);
+                let lines = remove_blanks(&input);
+                let checked = match generator.day {
+                    14 => ::day14::check_input(&lines),
+                    16 => ::day16::check_input(&lines),
+                    18 => ::day18::check_input(&lines, 71, 71),
+                    20 => ::day20::check_input(&lines),
+                    24 => ::day24::check_input(&split_lines_by_blanks(&input)),
+                    day => panic!("No check for day {}", day),
+                };
+                assert_eq!(checked, Ok(()), "day {} size {}", generator.day, size);
+                assert!(!lines.is_empty(), "day {} size {}", generator.day, size);
+            }
+        }
+    }
+}
@@ -0,0 +1,363 @@
+use crate::error::CircuitError;
+use crate::gate::Gate;
+
+use std::collections::{HashMap, VecDeque};
+
+/// Wires are numbered in the order they were first mentioned.
+pub type WireId = usize;
+
+/// One gate, reading `inputs` in order and driving `output`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GateNode {
+    pub gate: Gate,
+    pub inputs: Vec<WireId>,
+    pub output: WireId,
+}
+
+/// Collects wires and gates, then checks them over in `build`.
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBuilder {
+    names: Vec<String>,
+    ids: HashMap<String, WireId>,
+    gates: Vec<GateNode>,
+    driver: HashMap<WireId, usize>,
+}
+
+impl CircuitBuilder {
+    pub fn new() -> CircuitBuilder {
+        return CircuitBuilder::default();
+    }
+
+    fn wire(&mut self, name: &str) -> WireId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), self.names.len() - 1);
+        return self.names.len() - 1;
+    }
+
+    /// Add a wire without a gate, for inputs that nothing reads yet.
+    pub fn input(&mut self, name: &str) -> WireId {
+        return self.wire(name);
+    }
+
+    pub fn gate(&mut self, gate: Gate, inputs: &[&str], output: &str) -> Result<(), CircuitError> {
+        if !gate.arity().contains(&inputs.len()) {
+            return Err(CircuitError::Arity(output.to_string(), inputs.len()));
+        }
+        let inputs = inputs.iter().map(|name| self.wire(name)).collect();
+        let output = self.wire(output);
+        if self.driver.insert(output, self.gates.len()).is_some() {
+            return Err(CircuitError::DrivenTwice(self.names[output].clone()));
+        }
+        self.gates.push(GateNode {
+            gate: gate,
+            inputs: inputs,
+            output: output,
+        });
+        return Ok(());
+    }
+
+    /// Sort the gates so each comes after everything it reads, failing if
+    /// any wire ends up depending on itself.
+    pub fn build(self) -> Result<Circuit, CircuitError> {
+        let mut driver = vec![None; self.names.len()];
+        for (&wire, &gate) in &self.driver {
+            driver[wire] = Some(gate);
+        }
+        let mut circuit = Circuit {
+            names: self.names,
+            ids: self.ids,
+            gates: self.gates,
+            driver: driver,
+            readers: vec![],
+            order: vec![],
+            rank: vec![],
+        };
+        circuit.readers = vec![vec![]; circuit.names.len()];
+        for (index, gate) in circuit.gates.iter().enumerate() {
+            for &input in &gate.inputs {
+                if !circuit.readers[input].contains(&index) {
+                    circuit.readers[input].push(index);
+                }
+            }
+        }
+        circuit.sort()?;
+        return Ok(circuit);
+    }
+}
+
+/// Gates and the wires between them, with no loops. Wires nothing drives
+/// are the circuit's inputs.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Circuit {
+    names: Vec<String>,
+    ids: HashMap<String, WireId>,
+    gates: Vec<GateNode>,
+    /// The gate driving each wire
+    driver: Vec<Option<usize>>,
+    /// The gates reading each wire
+    readers: Vec<Vec<usize>>,
+    /// Gates, each after the ones it depends on
+    order: Vec<usize>,
+    /// Each gate's position in `order`
+    rank: Vec<usize>,
+}
+
+impl Circuit {
+    pub fn builder() -> CircuitBuilder {
+        return CircuitBuilder::new();
+    }
+
+    /// Kahn's algorithm over the gates.
+    fn sort(&mut self) -> Result<(), CircuitError> {
+        let mut waiting: Vec<usize> = self
+            .gates
+            .iter()
+            .map(|g| {
+                g.inputs
+                    .iter()
+                    .filter(|&&w| self.driver[w].is_some())
+                    .count()
+            })
+            .collect();
+        let mut ready: VecDeque<usize> =
+            (0..self.gates.len()).filter(|&g| waiting[g] == 0).collect();
+        let mut order = vec![];
+        while let Some(gate) = ready.pop_front() {
+            order.push(gate);
+            let output = self.gates[gate].output;
+            for &reader in &self.readers[output] {
+                // A gate reading the same wire twice waits on it twice
+                let uses = self.gates[reader]
+                    .inputs
+                    .iter()
+                    .filter(|&&w| w == output)
+                    .count();
+                waiting[reader] -= uses;
+                if waiting[reader] == 0 {
+                    ready.push_back(reader);
+                }
+            }
+        }
+        if order.len() < self.gates.len() {
+            return Err(CircuitError::Cycle(self.find_cycle(&waiting)));
+        }
+        self.rank = vec![0; self.gates.len()];
+        for (position, &gate) in order.iter().enumerate() {
+            self.rank[gate] = position;
+        }
+        self.order = order;
+        return Ok(());
+    }
+
+    /// Every gate left waiting reads a wire from another one left waiting,
+    /// so walking back through those has to come round to a gate again.
+    fn find_cycle(&self, waiting: &[usize]) -> Vec<String> {
+        let mut gate = (0..self.gates.len()).find(|&g| waiting[g] > 0).unwrap();
+        let mut seen: Vec<usize> = vec![];
+        while !seen.contains(&gate) {
+            seen.push(gate);
+            gate = self.gates[gate]
+                .inputs
+                .iter()
+                .filter_map(|&w| self.driver[w])
+                .find(|&g| waiting[g] > 0)
+                .unwrap();
+        }
+        let start = seen.iter().position(|&g| g == gate).unwrap();
+        // Walked from readers back to drivers, so reverse to follow the wires
+        return seen[start..]
+            .iter()
+            .rev()
+            .map(|&g| self.names[self.gates[g].output].clone())
+            .collect();
+    }
+
+    pub fn wire(&self, name: &str) -> Result<WireId, CircuitError> {
+        return self
+            .ids
+            .get(name)
+            .copied()
+            .ok_or_else(|| CircuitError::UnknownWire(name.to_string()));
+    }
+
+    pub fn name(&self, wire: WireId) -> &str {
+        return &self.names[wire];
+    }
+
+    pub fn wire_count(&self) -> usize {
+        return self.names.len();
+    }
+
+    /// Every wire whose name starts with `prefix`, sorted by name, so
+    /// `z00` first for `z`.
+    pub fn wires_with_prefix(&self, prefix: &str) -> Vec<WireId> {
+        let mut wires: Vec<WireId> = (0..self.names.len())
+            .filter(|&w| self.names[w].starts_with(prefix))
+            .collect();
+        wires.sort_by_key(|&w| &self.names[w]);
+        return wires;
+    }
+
+    pub fn gates(&self) -> &[GateNode] {
+        return &self.gates;
+    }
+
+    /// Gates in an order where each comes after the ones driving its inputs.
+    pub fn topological(&self) -> impl Iterator<Item = &GateNode> {
+        return self.order.iter().map(|&g| &self.gates[g]);
+    }
+
+    pub(crate) fn rank(&self, gate: usize) -> usize {
+        return self.rank[gate];
+    }
+
+    pub fn driver(&self, wire: WireId) -> Option<&GateNode> {
+        return self.driver[wire].map(|g| &self.gates[g]);
+    }
+
+    pub(crate) fn reader_indices(&self, wire: WireId) -> &[usize] {
+        return &self.readers[wire];
+    }
+
+    /// The gates reading a wire.
+    pub fn readers(&self, wire: WireId) -> impl Iterator<Item = &GateNode> {
+        return self.readers[wire].iter().map(|&g| &self.gates[g]);
+    }
+
+    /// Wires no gate drives, by name.
+    pub fn inputs(&self) -> Vec<WireId> {
+        let mut inputs: Vec<WireId> = (0..self.names.len())
+            .filter(|&w| self.driver[w].is_none())
+            .collect();
+        inputs.sort_by_key(|&w| &self.names[w]);
+        return inputs;
+    }
+
+    /// Swap which gates drive two wires. If that makes a loop the circuit
+    /// is left as it was.
+    /// ```
+    /// use circuitlib::{Circuit, Gate};
+    /// let mut builder = Circuit::builder();
+    /// builder.gate(Gate::Not, &["a"], "b").unwrap();
+    /// builder.gate(Gate::Not, &["b"], "c").unwrap();
+    /// let mut circuit = builder.build().unwrap();
+    /// let (b, c) = (circuit.wire("b").unwrap(), circuit.wire("c").unwrap());
+    /// assert!(circuit.swap_outputs(b, c).is_err());
+    /// assert_eq!(circuit.driver(c).unwrap().inputs, vec![b]);
+    /// ```
+    pub fn swap_outputs(&mut self, a: WireId, b: WireId) -> Result<(), CircuitError> {
+        for wire in [a, b] {
+            if self.driver[wire].is_none() {
+                return Err(CircuitError::Undriven(self.names[wire].clone()));
+            }
+        }
+        self.exchange(a, b);
+        if let Err(e) = self.sort() {
+            self.exchange(a, b);
+            self.sort().unwrap();
+            return Err(e);
+        }
+        return Ok(());
+    }
+
+    fn exchange(&mut self, a: WireId, b: WireId) {
+        let (gate_a, gate_b) = (self.driver[a].unwrap(), self.driver[b].unwrap());
+        self.gates[gate_a].output = b;
+        self.gates[gate_b].output = a;
+        self.driver.swap(a, b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circuit(gates: &[(Gate, &[&str], &str)]) -> Result<Circuit, CircuitError> {
+        let mut builder = Circuit::builder();
+        for &(gate, inputs, output) in gates {
+            builder.gate(gate, inputs, output)?;
+        }
+        return builder.build();
+    }
+
+    #[test]
+    fn test_build() {
+        // Out of order on purpose
+        let circuit =
+            circuit(&[(Gate::Or, &["t", "c"], "z"), (Gate::And, &["a", "b"], "t")]).unwrap();
+        let order: Vec<&str> = circuit
+            .topological()
+            .map(|g| circuit.name(g.output))
+            .collect();
+        assert_eq!(order, vec!["t", "z"]);
+        let inputs: Vec<&str> = circuit.inputs().iter().map(|&w| circuit.name(w)).collect();
+        assert_eq!(inputs, vec!["a", "b", "c"]);
+        let t = circuit.wire("t").unwrap();
+        assert_eq!(circuit.driver(t).unwrap().gate, Gate::And);
+        assert_eq!(circuit.readers(t).count(), 1);
+        assert_eq!(
+            circuit.wire("q"),
+            Err(CircuitError::UnknownWire("q".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            circuit(&[(Gate::And, &["a", "b"], "c"), (Gate::Or, &["a", "b"], "c")]),
+            Err(CircuitError::DrivenTwice("c".to_string()))
+        );
+        assert_eq!(
+            circuit(&[(Gate::Not, &["a", "b"], "c")]),
+            Err(CircuitError::Arity("c".to_string(), 2))
+        );
+        let cycle = circuit(&[
+            (Gate::And, &["x", "c"], "a"),
+            (Gate::Xor, &["a", "y"], "b"),
+            (Gate::Or, &["b", "b"], "c"),
+            (Gate::Not, &["c"], "d"),
+        ]);
+        let Err(CircuitError::Cycle(wires)) = cycle else {
+            panic!("expected a cycle, got {:?}", cycle);
+        };
+        assert_eq!(wires.len(), 3);
+        // Each wire feeds the next
+        let feeds = [("a", "b"), ("b", "c"), ("c", "a")];
+        for i in 0..3 {
+            let pair = (wires[i].as_str(), wires[(i + 1) % 3].as_str());
+            assert!(feeds.contains(&pair), "{:?}", wires);
+        }
+    }
+
+    #[test]
+    fn test_swap_outputs() {
+        let mut circuit = circuit(&[
+            (Gate::And, &["a", "b"], "x"),
+            (Gate::Or, &["a", "b"], "y"),
+            (Gate::Not, &["x"], "z"),
+        ])
+        .unwrap();
+        let (x, y, z) = (
+            circuit.wire("x").unwrap(),
+            circuit.wire("y").unwrap(),
+            circuit.wire("z").unwrap(),
+        );
+        circuit.swap_outputs(x, y).unwrap();
+        assert_eq!(circuit.driver(x).unwrap().gate, Gate::Or);
+        assert_eq!(circuit.driver(y).unwrap().gate, Gate::And);
+        let before = circuit.clone();
+        assert!(matches!(
+            circuit.swap_outputs(x, z),
+            Err(CircuitError::Cycle(_))
+        ));
+        assert_eq!(circuit, before);
+        let a = circuit.wire("a").unwrap();
+        assert_eq!(
+            circuit.swap_outputs(a, x),
+            Err(CircuitError::Undriven("a".to_string()))
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! Circuits as graphviz DOT, and back.
+//!
+//! Each gate is a node named after the wire it drives, with a `gate`
+//! attribute, coloured by its kind, and an edge from each of its inputs in
+//! order. Wires nothing drives are boxes. `parse` reads that much of DOT
+//! back: one node or edge per line, ignoring settings for the whole graph.
+//! ```
+//! use circuitlib::{dot, Circuit, Gate};
+//! let mut builder = Circuit::builder();
+//! builder.gate(Gate::Xor, &["x00", "y00"], "z00").unwrap();
+//! let circuit = builder.build().unwrap();
+//! let text = dot::to_dot(&circuit, &["z00"]);
+//! assert!(text.contains("\"z00\" [label=\"z00\\nXOR\", gate=\"XOR\", fillcolor=gold, color=red, penwidth=3];"));
+//! assert_eq!(dot::parse(&text), Ok(circuit));
+//! ```
+use crate::circuit::Circuit;
+use crate::error::CircuitError;
+use crate::gate::Gate;
+
+use std::collections::HashMap;
+
+fn colour(gate: Gate) -> &'static str {
+    return match gate {
+        Gate::And => "lightblue",
+        Gate::Or => "palegreen",
+        Gate::Xor => "gold",
+        Gate::Nand => "steelblue",
+        Gate::Nor => "seagreen",
+        Gate::Xnor => "orange",
+        Gate::Not | Gate::Buf => "lightgrey",
+        Gate::Lut { .. } => "plum",
+    };
+}
+
+/// The whole circuit, with the wires in `highlight` outlined in red.
+pub fn to_dot(circuit: &Circuit, highlight: &[&str]) -> String {
+    let outline = |wire: &str| {
+        if highlight.contains(&wire) {
+            return ", color=red, penwidth=3";
+        }
+        return "";
+    };
+    let mut result = String::from("digraph circuit {\n");
+    result += "    rankdir=LR;\n";
+    result += "    node [style=filled, fontname=\"monospace\"];\n";
+    for wire in circuit.inputs() {
+        let name = circuit.name(wire);
+        result += &format!(
+            "    \"{}\" [shape=box, fillcolor=white{}];\n",
+            name,
+            outline(name)
+        );
+    }
+    for gate in circuit.topological() {
+        let name = circuit.name(gate.output);
+        result += &format!(
+            "    \"{}\" [label=\"{}\\n{}\", gate=\"{}\", fillcolor={}{}];\n",
+            name,
+            name,
+            gate.gate,
+            gate.gate,
+            colour(gate.gate),
+            outline(name)
+        );
+    }
+    for gate in circuit.topological() {
+        for &input in &gate.inputs {
+            result += &format!(
+                "    \"{}\" -> \"{}\";\n",
+                circuit.name(input),
+                circuit.name(gate.output)
+            );
+        }
+    }
+    result += "}\n";
+    return result;
+}
+
+fn unquote(id: &str) -> &str {
+    let id = id.trim();
+    return id
+        .strip_prefix('"')
+        .and_then(|id| id.strip_suffix('"'))
+        .unwrap_or(id);
+}
+
+/// `key=value` pairs from inside `[...]`, values unquoted.
+fn attributes(text: &str) -> Result<HashMap<String, String>, String> {
+    let mut result = HashMap::new();
+    let mut pairs = vec![];
+    let (mut current, mut quoted) = (String::new(), false);
+    for c in text.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                pairs.push(current);
+                current = String::new();
+                continue;
+            }
+            _ => (),
+        }
+        current.push(c);
+    }
+    if quoted {
+        return Err("unclosed quote".to_string());
+    }
+    pairs.push(current);
+    for pair in pairs.iter().filter(|p| !p.trim().is_empty()) {
+        let Some((key, value)) = pair.split_once('=') else {
+            return Err(format!("{} should be key=value", pair.trim()));
+        };
+        result.insert(key.trim().to_string(), unquote(value).to_string());
+    }
+    return Ok(result);
+}
+
+/// A statement as its id or edge, and the attributes after it.
+fn split_statement(line: &str) -> Result<(&str, HashMap<String, String>), String> {
+    let Some((head, rest)) = line.split_once('[') else {
+        return Ok((line, HashMap::new()));
+    };
+    let Some(inside) = rest.strip_suffix(']') else {
+        return Err("attributes should end with ]".to_string());
+    };
+    return Ok((head.trim(), attributes(inside)?));
+}
+
+/// Read back a circuit written by `to_dot`, or edited by hand in the same
+/// style. Nodes with a `gate` are gates, and their edges in are their
+/// inputs, in the order the edges appear.
+pub fn parse(text: &str) -> Result<Circuit, CircuitError> {
+    let mut builder = Circuit::builder();
+    // Gates by output, in the order they're declared
+    let mut gates: Vec<(String, Gate, usize)> = vec![];
+    let mut inputs: HashMap<String, Vec<String>> = HashMap::new();
+    // Where each wire's first edge in is, to point at edges with no gate
+    let mut first_edge: HashMap<String, usize> = HashMap::new();
+    for (index, raw) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let error = |reason: String| CircuitError::Parse(line_number, reason);
+        let line = raw.trim().trim_end_matches(';').trim();
+        let settings = ["node ", "node[", "edge ", "edge[", "graph ", "graph["];
+        if line.is_empty()
+            || line.starts_with("//")
+            || line.starts_with("digraph")
+            || line == "}"
+            || settings.iter().any(|s| line.starts_with(s))
+        {
+            continue;
+        }
+        let (head, attributes) = split_statement(line).map_err(error)?;
+        if let Some((from, to)) = head.split_once("->") {
+            let (from, to) = (unquote(from), unquote(to));
+            first_edge.entry(to.to_string()).or_insert(line_number);
+            inputs
+                .entry(to.to_string())
+                .or_default()
+                .push(from.to_string());
+            continue;
+        }
+        // Something like rankdir=LR
+        if head.contains('=') {
+            continue;
+        }
+        let name = unquote(head);
+        match attributes.get("gate") {
+            Some(gate) => gates.push((name.to_string(), gate.parse().map_err(error)?, line_number)),
+            None => {
+                builder.input(name);
+            }
+        }
+    }
+    for (output, gate, line_number) in &gates {
+        let reads = inputs.remove(output).unwrap_or_default();
+        let reads: Vec<&str> = reads.iter().map(|s| s.as_str()).collect();
+        builder.gate(*gate, &reads, output).map_err(|e| match e {
+            CircuitError::Arity(..) => CircuitError::Parse(*line_number, e.to_string()),
+            e => e,
+        })?;
+    }
+    if let Some(line_number) = inputs.keys().map(|wire| first_edge[wire]).min() {
+        let wire = inputs
+            .keys()
+            .find(|wire| first_edge[*wire] == line_number)
+            .unwrap();
+        return Err(CircuitError::Parse(
+            line_number,
+            format!("{} has edges in but no gate", wire),
+        ));
+    }
+    return builder.build();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut builder = Circuit::builder();
+        builder.gate(Gate::Xor, &["x00", "y00"], "s").unwrap();
+        builder.gate(Gate::Nand, &["s", "c"], "z00").unwrap();
+        let lut = Gate::Lut {
+            inputs: 3,
+            table: 0xe8,
+        };
+        builder.gate(lut, &["s", "x00", "c"], "z01").unwrap();
+        builder.input("unused");
+        let circuit = builder.build().unwrap();
+        let text = to_dot(&circuit, &[]);
+        assert!(!text.contains("color=red"));
+        assert!(text.contains("    \"unused\" [shape=box, fillcolor=white];\n"));
+        assert!(text.contains("    \"c\" -> \"z01\";\n"));
+        let parsed = parse(&text).unwrap();
+        // Wire numbers can differ, so compare what the gates read by name
+        for gate in circuit.gates() {
+            let output = parsed.wire(circuit.name(gate.output)).unwrap();
+            let twin = parsed.driver(output).unwrap();
+            assert_eq!(twin.gate, gate.gate);
+            let names = |c: &Circuit, wires: &[usize]| -> Vec<String> {
+                return wires.iter().map(|&w| c.name(w).to_string()).collect();
+            };
+            assert_eq!(names(&parsed, &twin.inputs), names(&circuit, &gate.inputs));
+        }
+        assert!(parsed.wire("unused").is_ok());
+    }
+
+    #[test]
+    fn test_hand_written() {
+        // Bare ids, attributes in any order, and edges before their gate
+        let text = "digraph {\n  a -> out\n  b -> out [color=blue]\n  out [fillcolor=\"x, y\", gate=OR]\n}";
+        let circuit = parse(text).unwrap();
+        let out = circuit.wire("out").unwrap();
+        assert_eq!(circuit.driver(out).unwrap().gate, Gate::Or);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("a -> b;\nb [gate=\"MAYBE\"];"),
+            Err(CircuitError::Parse(2, "Unknown gate MAYBE".to_string()))
+        );
+        assert_eq!(
+            parse("a -> b;\nb [gate=NOT, label=\"b];"),
+            Err(CircuitError::Parse(2, "unclosed quote".to_string()))
+        );
+        assert_eq!(
+            parse("a -> b;\nb [gate=AND];\nc -> d;"),
+            Err(CircuitError::Parse(
+                3,
+                "d has edges in but no gate".to_string()
+            ))
+        );
+        assert_eq!(
+            parse("a -> b;\nb [gate=NOT];\nb -> a;\na [gate=BUF];"),
+            Err(CircuitError::Cycle(vec!["a".to_string(), "b".to_string()]))
+        );
+    }
+}
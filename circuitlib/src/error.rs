@@ -0,0 +1,34 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CircuitError {
+    /// Two gates write the same wire
+    DrivenTwice(String),
+    /// A gate, named by its output, given a number of inputs it can't take
+    Arity(String, usize),
+    /// Wires that each depend on the next, and the last on the first
+    Cycle(Vec<String>),
+    /// Nothing drives this wire and it wasn't given a value
+    Undriven(String),
+    /// No wire by this name
+    UnknownWire(String),
+    /// A line of a DOT or Verilog netlist that couldn't be read, and why
+    Parse(usize, String),
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            CircuitError::DrivenTwice(wire) => write!(f, "{} is driven twice", wire),
+            CircuitError::Arity(wire, count) => {
+                write!(f, "the gate driving {} can't take {} inputs", wire, count)
+            }
+            CircuitError::Cycle(wires) => write!(f, "{} form a loop", wires.join(" -> ")),
+            CircuitError::Undriven(wire) => write!(f, "{} has no driver or value", wire),
+            CircuitError::UnknownWire(wire) => write!(f, "no wire called {}", wire),
+            CircuitError::Parse(line, reason) => write!(f, "line {}: {}", line, reason),
+        };
+    }
+}
+
+impl std::error::Error for CircuitError {}
@@ -0,0 +1,153 @@
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// What a gate computes from its inputs.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Gate {
+    And,
+    Or,
+    Xor,
+    Nand,
+    Nor,
+    Xnor,
+    Not,
+    Buf,
+    /// Any function of up to 6 inputs: bit `i` of `table` is the output
+    /// when the inputs, first input lowest, spell `i` in binary.
+    Lut {
+        inputs: u8,
+        table: u64,
+    },
+}
+
+const NAMES: [(&str, Gate); 8] = [
+    ("AND", Gate::And),
+    ("OR", Gate::Or),
+    ("XOR", Gate::Xor),
+    ("NAND", Gate::Nand),
+    ("NOR", Gate::Nor),
+    ("XNOR", Gate::Xnor),
+    ("NOT", Gate::Not),
+    ("BUF", Gate::Buf),
+];
+
+impl Gate {
+    /// How many inputs the gate can take.
+    pub fn arity(&self) -> RangeInclusive<usize> {
+        return match self {
+            Gate::Not | Gate::Buf => 1..=1,
+            Gate::Lut { inputs, .. } => *inputs as usize..=*inputs as usize,
+            _ => 1..=usize::MAX,
+        };
+    }
+
+    /// ```
+    /// use circuitlib::Gate;
+    /// assert!(Gate::Xor.evaluate(&[true, false, false]));
+    /// assert!(!Gate::Nand.evaluate(&[true, true]));
+    /// let implies = Gate::Lut { inputs: 2, table: 0b1101 };
+    /// assert!(!implies.evaluate(&[true, false]));
+    /// ```
+    pub fn evaluate(&self, inputs: &[bool]) -> bool {
+        return match self {
+            Gate::And => inputs.iter().all(|&b| b),
+            Gate::Or => inputs.iter().any(|&b| b),
+            Gate::Xor => inputs.iter().filter(|&&b| b).count() % 2 == 1,
+            Gate::Nand => !Gate::And.evaluate(inputs),
+            Gate::Nor => !Gate::Or.evaluate(inputs),
+            Gate::Xnor => !Gate::Xor.evaluate(inputs),
+            Gate::Not => !inputs[0],
+            Gate::Buf => inputs[0],
+            Gate::Lut { table, .. } => {
+                let index = inputs
+                    .iter()
+                    .rev()
+                    .fold(0, |index, &b| index * 2 + b as usize);
+                (table >> index) & 1 == 1
+            }
+        };
+    }
+}
+
+impl Display for Gate {
+    /// The name used in puzzle inputs, eg `AND`, or `LUT2:6` for a table.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Gate::Lut { inputs, table } = self {
+            return write!(f, "LUT{}:{:x}", inputs, table);
+        }
+        let (name, _) = NAMES.iter().find(|(_, gate)| gate == self).unwrap();
+        return write!(f, "{}", name);
+    }
+}
+
+impl FromStr for Gate {
+    type Err = String;
+
+    /// ```
+    /// use circuitlib::Gate;
+    /// assert_eq!("XNOR".parse(), Ok(Gate::Xnor));
+    /// assert_eq!("LUT2:6".parse(), Ok(Gate::Lut { inputs: 2, table: 6 }));
+    /// assert!("LUT2:1f".parse::<Gate>().is_err());
+    /// assert!("MAYBE".parse::<Gate>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Gate, String> {
+        if let Some((_, gate)) = NAMES.iter().find(|(name, _)| *name == s) {
+            return Ok(*gate);
+        }
+        let bad = || format!("Unknown gate {}", s);
+        let (inputs, table) = s
+            .strip_prefix("LUT")
+            .and_then(|rest| rest.split_once(':'))
+            .ok_or_else(bad)?;
+        let inputs: u8 = inputs.parse().map_err(|_| bad())?;
+        let table = u64::from_str_radix(table, 16).map_err(|_| bad())?;
+        if inputs > 6 || (inputs < 6 && table >> (1 << inputs) != 0) {
+            return Err(format!("{} does not fit its inputs", s));
+        }
+        return Ok(Gate::Lut {
+            inputs: inputs,
+            table: table,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truth_tables() {
+        let pairs = [[false, false], [false, true], [true, false], [true, true]];
+        let table = |gate: Gate| -> Vec<bool> { pairs.iter().map(|p| gate.evaluate(p)).collect() };
+        assert_eq!(table(Gate::And), vec![false, false, false, true]);
+        assert_eq!(table(Gate::Or), vec![false, true, true, true]);
+        assert_eq!(table(Gate::Xor), vec![false, true, true, false]);
+        assert_eq!(table(Gate::Nor), vec![true, false, false, false]);
+        assert_eq!(table(Gate::Xnor), vec![true, false, false, true]);
+        // The first input is the low bit of the index, so [false, true] is bit 2
+        let lut = Gate::Lut {
+            inputs: 2,
+            table: 0b0100,
+        };
+        assert_eq!(table(lut), vec![false, true, false, false]);
+        assert!(Gate::Not.evaluate(&[false]));
+        assert!(Gate::Buf.evaluate(&[true]));
+    }
+
+    #[test]
+    fn test_names() {
+        for (_, gate) in NAMES {
+            assert_eq!(gate.to_string().parse(), Ok(gate));
+        }
+        let lut = Gate::Lut {
+            inputs: 3,
+            table: 0xe8,
+        };
+        assert_eq!(lut.to_string(), "LUT3:e8");
+        assert_eq!("LUT3:e8".parse(), Ok(lut));
+        assert!("LUT7:0".parse::<Gate>().is_err());
+        assert_eq!(Gate::Not.arity(), 1..=1);
+        assert!(Gate::And.arity().contains(&3));
+    }
+}
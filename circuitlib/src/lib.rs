@@ -0,0 +1,27 @@
+//! Logic circuits: wires driven by gates, checked for loops and missing
+//! drivers up front, evaluated in topological order.
+//!
+//! A `Simulation` holds every wire's value and can change one input and
+//! only redo the gates downstream of it.
+
+mod circuit;
+
+pub use crate::circuit::Circuit;
+pub use crate::circuit::CircuitBuilder;
+pub use crate::circuit::GateNode;
+pub use crate::circuit::WireId;
+
+mod error;
+
+pub use crate::error::CircuitError;
+
+mod gate;
+
+pub use crate::gate::Gate;
+
+mod simulation;
+
+pub use crate::simulation::Simulation;
+
+pub mod dot;
+pub mod verilog;
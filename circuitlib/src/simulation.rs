@@ -0,0 +1,241 @@
+use crate::circuit::{Circuit, WireId};
+use crate::error::CircuitError;
+use crate::gate::Gate;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Gates waiting to be redone by `Simulation::set`, by rank.
+struct Queue {
+    heap: BinaryHeap<Reverse<(usize, usize)>>,
+    queued: Vec<bool>,
+}
+
+impl Queue {
+    fn readers_of(&mut self, circuit: &Circuit, wire: WireId) {
+        for &reader in circuit.reader_indices(wire) {
+            if !self.queued[reader] {
+                self.queued[reader] = true;
+                self.heap.push(Reverse((circuit.rank(reader), reader)));
+            }
+        }
+    }
+}
+
+/// The value on every wire of a circuit.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Simulation<'a> {
+    circuit: &'a Circuit,
+    values: Vec<bool>,
+}
+
+impl<'a> Simulation<'a> {
+    /// Evaluate every gate once, in topological order. `inputs` has to give
+    /// a value for each wire nothing drives, and only for those.
+    /// ```
+    /// use circuitlib::{Circuit, Gate, Simulation};
+    /// use std::collections::HashMap;
+    /// let mut builder = Circuit::builder();
+    /// builder.gate(Gate::Xor, &["x00", "y00"], "z00").unwrap();
+    /// builder.gate(Gate::And, &["x00", "y00"], "z01").unwrap();
+    /// let circuit = builder.build().unwrap();
+    /// let inputs = HashMap::from([("x00".to_string(), true), ("y00".to_string(), true)]);
+    /// let simulation = Simulation::new(&circuit, &inputs).unwrap();
+    /// assert_eq!(simulation.number("z"), 2);
+    /// ```
+    pub fn new(
+        circuit: &'a Circuit,
+        inputs: &HashMap<String, bool>,
+    ) -> Result<Simulation<'a>, CircuitError> {
+        let mut values = vec![false; circuit.wire_count()];
+        for (name, &value) in inputs {
+            let wire = circuit.wire(name)?;
+            if circuit.driver(wire).is_some() {
+                return Err(CircuitError::DrivenTwice(name.clone()));
+            }
+            values[wire] = value;
+        }
+        for wire in circuit.inputs() {
+            if !inputs.contains_key(circuit.name(wire)) {
+                return Err(CircuitError::Undriven(circuit.name(wire).to_string()));
+            }
+        }
+        let mut simulation = Simulation {
+            circuit: circuit,
+            values: values,
+        };
+        for gate in circuit.topological() {
+            simulation.values[gate.output] = simulation.output(gate.inputs.as_slice(), gate.gate);
+        }
+        return Ok(simulation);
+    }
+
+    fn output(&self, inputs: &[WireId], gate: Gate) -> bool {
+        let values: Vec<bool> = inputs.iter().map(|&w| self.values[w]).collect();
+        return gate.evaluate(&values);
+    }
+
+    pub fn get(&self, wire: WireId) -> bool {
+        return self.values[wire];
+    }
+
+    pub fn value(&self, name: &str) -> Result<bool, CircuitError> {
+        return Ok(self.values[self.circuit.wire(name)?]);
+    }
+
+    /// The wires starting with `prefix` read as a binary number, the first
+    /// by name being the lowest bit.
+    pub fn number(&self, prefix: &str) -> u128 {
+        let mut number = 0;
+        for wire in self.circuit.wires_with_prefix(prefix).into_iter().rev() {
+            number = number * 2 + self.values[wire] as u128;
+        }
+        return number;
+    }
+
+    /// Change one input and update only the gates that depend on it.
+    ///
+    /// Gates are redone in topological order, each at most once, and
+    /// nothing past a gate whose output didn't change. Gives back every wire
+    /// that changed, including the input.
+    pub fn set(&mut self, wire: WireId, value: bool) -> Result<Vec<WireId>, CircuitError> {
+        if self.circuit.driver(wire).is_some() {
+            return Err(CircuitError::DrivenTwice(
+                self.circuit.name(wire).to_string(),
+            ));
+        }
+        if self.values[wire] == value {
+            return Ok(vec![]);
+        }
+        self.values[wire] = value;
+        let mut changed = vec![wire];
+        // Gates to redo, earliest in topological order first
+        let mut queue = Queue {
+            heap: BinaryHeap::new(),
+            queued: vec![false; self.circuit.gates().len()],
+        };
+        queue.readers_of(self.circuit, wire);
+        while let Some(Reverse((_, index))) = queue.heap.pop() {
+            let gate = &self.circuit.gates()[index];
+            let output = self.output(&gate.inputs, gate.gate);
+            if output != self.values[gate.output] {
+                self.values[gate.output] = output;
+                changed.push(gate.output);
+                queue.readers_of(self.circuit, gate.output);
+            }
+        }
+        return Ok(changed);
+    }
+
+    pub fn flip(&mut self, wire: WireId) -> Result<Vec<WireId>, CircuitError> {
+        return self.set(wire, !self.values[wire]);
+    }
+
+    /// Set the inputs starting with `prefix` to a number, like `number`
+    /// reads one, and give back every wire that changed.
+    pub fn set_number(&mut self, prefix: &str, number: u128) -> Result<Vec<WireId>, CircuitError> {
+        let mut changed = vec![];
+        for (bit, wire) in self
+            .circuit
+            .wires_with_prefix(prefix)
+            .into_iter()
+            .enumerate()
+        {
+            let value = bit < 128 && (number >> bit) & 1 == 1;
+            changed.extend(self.set(wire, value)?);
+        }
+        return Ok(changed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Adds two 2 bit numbers
+    fn adder() -> Circuit {
+        let mut builder = Circuit::builder();
+        let gates: [(Gate, [&str; 2], &str); 7] = [
+            (Gate::Xor, ["x00", "y00"], "z00"),
+            (Gate::And, ["x00", "y00"], "c00"),
+            (Gate::Xor, ["x01", "y01"], "s01"),
+            (Gate::Xor, ["s01", "c00"], "z01"),
+            (Gate::And, ["x01", "y01"], "a01"),
+            (Gate::And, ["s01", "c00"], "b01"),
+            (Gate::Or, ["a01", "b01"], "z02"),
+        ];
+        for (gate, inputs, output) in gates {
+            builder.gate(gate, &inputs, output).unwrap();
+        }
+        return builder.build().unwrap();
+    }
+
+    fn inputs(x: u128, y: u128) -> HashMap<String, bool> {
+        let mut inputs = HashMap::new();
+        for bit in 0..2 {
+            inputs.insert(format!("x{:02}", bit), (x >> bit) & 1 == 1);
+            inputs.insert(format!("y{:02}", bit), (y >> bit) & 1 == 1);
+        }
+        return inputs;
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let circuit = adder();
+        for x in 0..4 {
+            for y in 0..4 {
+                let simulation = Simulation::new(&circuit, &inputs(x, y)).unwrap();
+                assert_eq!(simulation.number("z"), x + y);
+                assert_eq!(simulation.value("z00"), Ok((x + y) % 2 == 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_inputs() {
+        let circuit = adder();
+        let mut missing = inputs(1, 1);
+        missing.remove("y01");
+        assert_eq!(
+            Simulation::new(&circuit, &missing),
+            Err(CircuitError::Undriven("y01".to_string()))
+        );
+        let mut driven = inputs(1, 1);
+        driven.insert("c00".to_string(), true);
+        assert_eq!(
+            Simulation::new(&circuit, &driven),
+            Err(CircuitError::DrivenTwice("c00".to_string()))
+        );
+        let mut unknown = inputs(1, 1);
+        unknown.insert("q".to_string(), true);
+        assert!(Simulation::new(&circuit, &unknown).is_err());
+    }
+
+    #[test]
+    fn test_incremental() {
+        let circuit = adder();
+        let mut simulation = Simulation::new(&circuit, &inputs(1, 2)).unwrap();
+        let x00 = circuit.wire("x00").unwrap();
+        let names = |wires: Vec<WireId>| -> Vec<String> {
+            return wires.iter().map(|&w| circuit.name(w).to_string()).collect();
+        };
+        // 1 + 2 to 0 + 2 changes only the low bit
+        assert_eq!(names(simulation.flip(x00).unwrap()), vec!["x00", "z00"]);
+        assert_eq!(simulation.number("z"), 2);
+        assert_eq!(simulation.set(x00, false), Ok(vec![]));
+        // Ripples all the way up
+        simulation.set_number("x", 3).unwrap();
+        simulation.set_number("y", 1).unwrap();
+        assert_eq!(simulation.number("z"), 4);
+        for x in 0..4 {
+            for y in 0..4 {
+                simulation.set_number("x", x).unwrap();
+                simulation.set_number("y", y).unwrap();
+                let fresh = Simulation::new(&circuit, &inputs(x, y)).unwrap();
+                assert_eq!(simulation, fresh);
+            }
+        }
+        let z00 = circuit.wire("z00").unwrap();
+        assert!(simulation.flip(z00).is_err());
+    }
+}
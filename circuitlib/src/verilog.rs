@@ -0,0 +1,259 @@
+//! Circuits as structural Verilog, and back.
+//!
+//! Gates become the built in primitives, `xor g0(out, a, b);`, and lookup
+//! tables an `assign` that shifts the table by the inputs. Wires nothing
+//! drives are the module's inputs and wires nothing reads its outputs.
+//! `parse` reads back that much of Verilog: one module of declarations,
+//! primitives and table `assign`s.
+//! ```
+//! use circuitlib::{verilog, Circuit, Gate};
+//! let mut builder = Circuit::builder();
+//! builder.gate(Gate::Xor, &["x00", "y00"], "z00").unwrap();
+//! let circuit = builder.build().unwrap();
+//! assert_eq!(
+//!     verilog::to_verilog(&circuit, "half"),
+//!     "module half(x00, y00, z00);
+//!     input x00, y00;
+//!     output z00;
+//!     xor g0(z00, x00, y00);
+//! endmodule
+//! "
+//! );
+//! ```
+use crate::circuit::{Circuit, WireId};
+use crate::error::CircuitError;
+use crate::gate::Gate;
+
+fn names(circuit: &Circuit, wires: &[WireId]) -> String {
+    let names: Vec<&str> = wires.iter().map(|&w| circuit.name(w)).collect();
+    return names.join(", ");
+}
+
+/// The module `module`, with ports in the order inputs then outputs.
+pub fn to_verilog(circuit: &Circuit, module: &str) -> String {
+    let inputs = circuit.inputs();
+    let mut outputs = vec![];
+    let mut wires = vec![];
+    for gate in circuit.topological() {
+        if circuit.readers(gate.output).next().is_none() {
+            outputs.push(gate.output);
+        } else {
+            wires.push(gate.output);
+        }
+    }
+    outputs.sort_by_key(|&w| circuit.name(w));
+    let ports = [inputs.clone(), outputs.clone()].concat();
+
+    let mut result = format!("module {}({});\n", module, names(circuit, &ports));
+    for (kind, wires) in [("input", &inputs), ("output", &outputs), ("wire", &wires)] {
+        if !wires.is_empty() {
+            result += &format!("    {} {};\n", kind, names(circuit, wires));
+        }
+    }
+    for (index, gate) in circuit.topological().enumerate() {
+        let output = circuit.name(gate.output);
+        result += &match gate.gate {
+            Gate::Lut { inputs, table } => {
+                // Concatenation puts the first input lowest, like the table
+                let mut reads = gate.inputs.clone();
+                reads.reverse();
+                format!(
+                    "    assign {} = {}'h{:x} >> {{{}}};\n",
+                    output,
+                    1 << inputs,
+                    table,
+                    names(circuit, &reads)
+                )
+            }
+            _ => format!(
+                "    {} g{}({}, {});\n",
+                gate.gate.to_string().to_lowercase(),
+                index,
+                output,
+                names(circuit, &gate.inputs)
+            ),
+        };
+    }
+    result += "endmodule\n";
+    return result;
+}
+
+fn identifiers(list: &str) -> Vec<&str> {
+    return list
+        .split(',')
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .collect();
+}
+
+/// `out = N'hTABLE >> {inputs}`, with the inputs highest first.
+fn lookup_table(assignment: &str) -> Result<(&str, Gate, Vec<&str>), String> {
+    let bad = || {
+        format!(
+            "assign {} should be a table shifted by its inputs",
+            assignment
+        )
+    };
+    let (output, value) = assignment.split_once('=').ok_or_else(bad)?;
+    let (table, shift) = value.split_once(">>").ok_or_else(bad)?;
+    let (_, table) = table.trim().split_once("'h").ok_or_else(bad)?;
+    let reads = shift
+        .trim()
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .ok_or_else(bad)?;
+    let mut reads = identifiers(reads);
+    reads.reverse();
+    let gate = format!("LUT{}:{}", reads.len(), table.trim()).parse()?;
+    return Ok((output.trim(), gate, reads));
+}
+
+/// `kind name(out, inputs...)` for one of the built in primitives.
+fn primitive(statement: &str) -> Result<(&str, Gate, Vec<&str>), String> {
+    let bad = || format!("{} isn't a gate this can read", statement);
+    let (kind, rest) = statement.split_once(char::is_whitespace).ok_or_else(bad)?;
+    let gate: Gate = kind.to_uppercase().parse().map_err(|_| bad())?;
+    if matches!(gate, Gate::Lut { .. }) {
+        return Err(bad());
+    }
+    let (_, ports) = rest.split_once('(').ok_or_else(bad)?;
+    let ports = ports.trim().strip_suffix(')').ok_or_else(bad)?;
+    let ports = identifiers(ports);
+    let Some((output, reads)) = ports.split_first() else {
+        return Err(bad());
+    };
+    return Ok((output, gate, reads.to_vec()));
+}
+
+/// Read back a module written by `to_verilog`, or edited by hand in the same
+/// style. Declared inputs are kept even if nothing reads them.
+pub fn parse(text: &str) -> Result<Circuit, CircuitError> {
+    let mut builder = Circuit::builder();
+    let mut line_number = 1;
+    for chunk in text.split(';') {
+        // Count lines up to where the statement starts, not the ; before it
+        let leading = chunk.len() - chunk.trim_start().len();
+        line_number += chunk[..leading].matches('\n').count();
+        let start = line_number;
+        line_number += chunk[leading..].matches('\n').count();
+        let error = |reason: String| CircuitError::Parse(start, reason);
+
+        let statement: Vec<&str> = chunk
+            .lines()
+            .map(|line| line.split("//").next().unwrap().trim())
+            .filter(|line| !line.is_empty())
+            .collect();
+        let statement = statement.join(" ");
+        let statement = statement
+            .strip_prefix("endmodule")
+            .unwrap_or(&statement)
+            .trim();
+        if statement.is_empty()
+            || statement.starts_with("module ")
+            || statement.starts_with("output ")
+            || statement.starts_with("wire ")
+        {
+            continue;
+        }
+        if let Some(names) = statement.strip_prefix("input ") {
+            for name in identifiers(names) {
+                builder.input(name);
+            }
+            continue;
+        }
+        let (output, gate, reads) = match statement.strip_prefix("assign ") {
+            Some(assignment) => lookup_table(assignment),
+            None => primitive(statement),
+        }
+        .map_err(error)?;
+        builder.gate(gate, &reads, output).map_err(|e| match e {
+            CircuitError::Arity(..) => error(e.to_string()),
+            e => e,
+        })?;
+    }
+    return builder.build();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circuit() -> Circuit {
+        let mut builder = Circuit::builder();
+        builder.gate(Gate::Nor, &["a", "b"], "n").unwrap();
+        builder.gate(Gate::Not, &["n"], "out").unwrap();
+        let lut = Gate::Lut {
+            inputs: 3,
+            table: 0x96,
+        };
+        builder.gate(lut, &["a", "b", "n"], "t").unwrap();
+        return builder.build().unwrap();
+    }
+
+    #[test]
+    fn test_to_verilog() {
+        let text = to_verilog(&circuit(), "top");
+        assert!(text.starts_with(
+            "module top(a, b, out, t);\n    input a, b;\n    output out, t;\n    wire n;\n"
+        ));
+        assert!(text.contains("    nor g0(n, a, b);\n"));
+        assert!(text.contains("    assign t = 8'h96 >> {n, b, a};\n"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let circuit = circuit();
+        let parsed = parse(&to_verilog(&circuit, "top")).unwrap();
+        for name in ["n", "out", "t"] {
+            let (wire, twin) = (circuit.wire(name).unwrap(), parsed.wire(name).unwrap());
+            let (gate, twin) = (circuit.driver(wire).unwrap(), parsed.driver(twin).unwrap());
+            assert_eq!(gate.gate, twin.gate);
+            let names = |c: &Circuit, wires: &[WireId]| -> Vec<String> {
+                return wires.iter().map(|&w| c.name(w).to_string()).collect();
+            };
+            assert_eq!(names(&circuit, &gate.inputs), names(&parsed, &twin.inputs));
+        }
+    }
+
+    #[test]
+    fn test_hand_written() {
+        let text = "// a comment
+module m(a, b,
+         c);
+    input a, b; // and another
+    output c;
+    and g(c,
+          a, b);
+endmodule";
+        let circuit = parse(text).unwrap();
+        assert_eq!(
+            circuit.driver(circuit.wire("c").unwrap()).unwrap().gate,
+            Gate::And
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse("module m(a, b);\n  input a;\n\n  maybe g0(b, a);\nendmodule"),
+            Err(CircuitError::Parse(
+                4,
+                "maybe g0(b, a) isn't a gate this can read".to_string()
+            ))
+        );
+        assert_eq!(
+            parse("assign t = 4'h6 >> a;"),
+            Err(CircuitError::Parse(
+                1,
+                "assign t = 4'h6 >> a should be a table shifted by its inputs".to_string()
+            ))
+        );
+        assert_eq!(
+            parse("input a;\nnot g0(b, a, a);"),
+            Err(CircuitError::Parse(
+                2,
+                "the gate driving b can't take 2 inputs".to_string()
+            ))
+        );
+    }
+}
@@ -1,27 +1,36 @@
 extern crate filelib;
 
 pub use filelib::load_no_blanks;
+use filelib::{parse_number, split_pair, ParseError};
 use std::collections::HashMap;
 
-/// Get the sum of all first and last numbers in each line. If a single number appears in a line, count it for both.
-/// ```
-/// let vec1: Vec<String> = vec!["3 4", "4 3", "2 5", "1 3", "3 9", "3 3"].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day01::puzzle_a(&vec1), 11);
-/// ```
-pub fn puzzle_a(string_list: &Vec<String>) -> u32 {
-    // split string_list into two lists of numbers
+/// Split string_list into two sorted lists of numbers
+fn parse_lists(string_list: &Vec<String>) -> Result<(Vec<u32>, Vec<u32>), ParseError> {
     let mut num1: Vec<u32> = vec![];
     let mut num2: Vec<u32> = vec![];
     for s in string_list {
-        let (a_s, b_s) = s.split_once(" ").unwrap();
-        let a = a_s.trim().parse::<u32>().unwrap();
-        let b = b_s.trim().parse::<u32>().unwrap();
-        num1.push(a);
-        num2.push(b);
+        let (a_s, b_s) = split_pair(s.trim(), " ")?;
+        num1.push(parse_number(a_s)?);
+        num2.push(parse_number(b_s)?);
     }
-    // Sort both lists
     num1.sort();
     num2.sort();
+    return Ok((num1, num2));
+}
+
+/// Check both location lists parse, without comparing them.
+/// ```
+/// assert!(day01::check_input(&vec!["3   4".to_string()]).is_ok());
+/// assert!(day01::check_input(&vec!["3".to_string()]).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return parse_lists(string_list).map(|_| ());
+}
+
+/// Get the sum of all first and last numbers in each line. If a single number appears in a line, count it for both.
+#[doc = filelib::fixture_doc!("day01-1", a, |fixture| day01::puzzle_a(&fixture.lines()))]
+pub fn puzzle_a(string_list: &Vec<String>) -> u32 {
+    let (num1, num2) = parse_lists(string_list).unwrap();
 
     let mut distance = 0;
     for (a, b) in num1.into_iter().zip(num2.into_iter()) {
@@ -35,24 +44,9 @@ pub fn puzzle_a(string_list: &Vec<String>) -> u32 {
 }
 
 /// Get the sum of all first and last numbers in each line, including number words. If a single number appears in a line, count it for both.
-/// ```
-/// let vec1: Vec<String> = vec!["3 4", "4 3", "2 5", "1 3", "3 9", "3 3"].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day01::puzzle_b(&vec1), 31);
-/// ```
+#[doc = filelib::fixture_doc!("day01-1", b, |fixture| day01::puzzle_b(&fixture.lines()))]
 pub fn puzzle_b(string_list: &Vec<String>) -> u32 {
-    // split string_list into two lists of numbers
-    let mut num1: Vec<u32> = vec![];
-    let mut num2: Vec<u32> = vec![];
-    for s in string_list {
-        let (a_s, b_s) = s.split_once(" ").unwrap();
-        let a = a_s.trim().parse::<u32>().unwrap();
-        let b = b_s.trim().parse::<u32>().unwrap();
-        num1.push(a);
-        num2.push(b);
-    }
-    // Sort both lists
-    num1.sort();
-    num2.sort();
+    let (num1, num2) = parse_lists(string_list).unwrap();
 
     let mut scores: HashMap<u32, u32> = HashMap::new();
     let mut total_score: u32 = 0;
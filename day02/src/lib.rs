@@ -1,9 +1,10 @@
 extern crate filelib;
 
 pub use filelib::load_no_blanks;
-use log::info;
+use filelib::{parse_number, ParseError};
+use tracelib::{debug, trace};
 
-pub fn parse(string_list: &Vec<String>) -> Vec<Vec<u32>> {
+pub fn parse(string_list: &Vec<String>) -> Result<Vec<Vec<u32>>, ParseError> {
     let mut result = vec![];
     for l in string_list {
         let mut cur = vec![];
@@ -11,12 +12,21 @@ pub fn parse(string_list: &Vec<String>) -> Vec<Vec<u32>> {
             if s.is_empty() {
                 continue;
             }
-            let v: u32 = s.parse().unwrap();
+            let v: u32 = parse_number(s)?;
             cur.push(v);
         }
         result.push(cur);
     }
-    return result;
+    return Ok(result);
+}
+
+/// Check every report is a list of levels.
+/// ```
+/// assert!(day02::check_input(&vec!["7 6 4 2 1".to_string()]).is_ok());
+/// assert!(day02::check_input(&vec!["7 6 x".to_string()]).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return parse(string_list).map(|_| ());
 }
 
 /// is_safe if:
@@ -31,57 +41,47 @@ fn is_safe(report: &Vec<u32>) -> bool {
     let mut is_increasing = false;
     let mut is_decreasing = false;
 
-    info!("Current report {:?}", report);
+    debug!("Current report {:?}", report);
     for level in report {
         if !handle_first {
             handle_first = true;
             continue;
         }
-        info!("handling last {:?} and now {:?}", last_level, level);
+        trace!("handling last {:?} and now {:?}", last_level, level);
 
         if level > last_level && is_decreasing {
             // if we are decreasing and the level is up, unsafe
-            info!("unsafe, cond1");
+            trace!("unsafe, cond1");
             return false;
         } else if level < last_level && is_increasing {
             // If we are increasing and the level is down, unsafe
-            info!("unsafe, cond2");
+            trace!("unsafe, cond2");
             return false;
         } else if level > last_level {
             let difference = level - last_level;
             is_increasing = true;
             if difference > 3 || difference < 1 {
-                info!("unsafe, cond3");
+                trace!("unsafe, cond3");
                 return false;
             }
         } else {
             let difference = last_level - level;
             is_decreasing = true;
             if difference > 3 || difference < 1 {
-                info!("unsafe, cond4");
+                trace!("unsafe, cond4");
                 return false;
             }
         }
         last_level = level
     }
-    info!("is safe!");
+    trace!("is safe!");
     return true;
 }
 
 /// Find unsafe levels
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "7 6 4 2 1",
-///     "1 2 7 8 9",
-///     "9 7 6 2 1",
-///     "1 3 2 4 5",
-///     "8 6 4 4 1",
-///     "1 3 6 7 9"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day02::puzzle_a(&vec1), 2);
-/// ```
+#[doc = filelib::fixture_doc!("day02-1", a, |fixture| day02::puzzle_a(&fixture.lines()))]
 pub fn puzzle_a(string_list: &Vec<String>) -> u32 {
-    let levels = parse(string_list);
+    let levels = parse(string_list).unwrap();
     let filtered_levels: Vec<Vec<u32>> = levels
         .into_iter()
         .filter(|report| is_safe(report))
@@ -123,19 +123,9 @@ fn is_safe_dampener(report: &Vec<u32>, dampener: usize) -> bool {
 }
 
 /// Find at most 1 unsafe level
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "7 6 4 2 1",
-///     "1 2 7 8 9",
-///     "9 7 6 2 1",
-///     "1 3 2 4 5",
-///     "8 6 4 4 1",
-///     "1 3 6 7 9"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day02::puzzle_b(&vec1), 4);
-/// ```
+#[doc = filelib::fixture_doc!("day02-1", b, |fixture| day02::puzzle_b(&fixture.lines()))]
 pub fn puzzle_b(string_list: &Vec<String>) -> u32 {
-    let levels = parse(string_list);
+    let levels = parse(string_list).unwrap();
     let filtered_levels: Vec<Vec<u32>> = levels
         .into_iter()
         .filter(|report| is_safe_dampener(report, 1))
@@ -3,14 +3,15 @@ use day02::puzzle_a;
 use day02::puzzle_b;
 
 fn main() {
-    colog::init();
+    tracelib::init();
+    let _day = tracelib::day_span(env!("CARGO_PKG_NAME")).entered();
 
     let filename = "input";
-    let lines = load_no_blanks(filename);
+    let lines = tracelib::parse_span().in_scope(|| load_no_blanks(filename));
 
-    let value = puzzle_a(&lines);
+    let value = tracelib::part_span("a").in_scope(|| puzzle_a(&lines));
     println!("Answer to 1st question: {}", value);
 
-    let value_b = puzzle_b(&lines);
+    let value_b = tracelib::part_span("b").in_scope(|| puzzle_b(&lines));
     println!("Answer to 2nd question: {}", value_b);
 }
@@ -1,16 +1,18 @@
 extern crate filelib;
 
 pub use filelib::load_no_blanks;
-use log::info;
+use filelib::ParseError;
 use regex::Regex;
+use tracelib::trace;
 
 fn extract_whole_muls(corrupt_input: &Vec<String>) -> Vec<(i32, i32)> {
     let mut result = vec![];
-    let extract_mul_regex = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
+    // The puzzle only allows 1-3 digit numbers, longer ones are corrupt
+    let extract_mul_regex = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
     for line in corrupt_input {
         for capture in extract_mul_regex.captures_iter(line) {
             let (_, [a_s, b_s]) = capture.extract();
-            info!("captured: {:?}, {:?}", a_s, b_s);
+            trace!("captured: {:?}, {:?}", a_s, b_s);
             let a: i32 = a_s.parse().unwrap();
             let b: i32 = b_s.parse().unwrap();
             result.push((a, b));
@@ -19,13 +21,19 @@ fn extract_whole_muls(corrupt_input: &Vec<String>) -> Vec<(i32, i32)> {
     return result;
 }
 
-/// Parse only things that match mul(x,y) exactly. Do not handle negative numbers. Add the results.
+/// Any text is valid corrupted memory, this only checks nothing in it
+/// trips up the extraction.
 /// ```
-/// let vec1: Vec<String> = vec![
-///     "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day03::puzzle_a(&vec1), 161);
+/// assert!(day03::check_input(&vec!["mul(1234,5)mul(2,4)".to_string()]).is_ok());
 /// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    extract_whole_muls(string_list);
+    split_by_dos_and_donts(string_list);
+    return Ok(());
+}
+
+/// Parse only things that match mul(x,y) exactly. Do not handle negative numbers. Add the results.
+#[doc = filelib::fixture_doc!("day03-1", a, |fixture| day03::puzzle_a(&fixture.lines()))]
 pub fn puzzle_a(string_list: &Vec<String>) -> i32 {
     return extract_whole_muls(string_list)
         .into_iter()
@@ -70,12 +78,7 @@ fn split_by_dos_and_donts(string_list: &Vec<String>) -> Vec<String> {
 }
 
 /// As 1, Work with dos and don't. We start in do()
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day03::puzzle_b(&vec1), 48);
-/// ```
+#[doc = filelib::fixture_doc!("day03-2", b, |fixture| day03::puzzle_b(&fixture.lines()))]
 pub fn puzzle_b(string_list: &Vec<String>) -> i32 {
     let do_parts = split_by_dos_and_donts(string_list);
     return extract_whole_muls(&do_parts)
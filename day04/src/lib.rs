@@ -1,9 +1,10 @@
 extern crate filelib;
 
 pub use filelib::load_no_blanks;
+use filelib::{grid_size, ParseError};
 use gridlib::GridTraversable;
 use gridlib::{Direction, Grid, GridCoordinate};
-use log::info;
+use tracelib::trace;
 
 #[derive(PartialEq, Debug, Copy, Clone)]
 enum XMASChar {
@@ -36,20 +37,29 @@ impl XMASChar {
     }
 }
 
-fn parse(lines: &Vec<String>) -> Grid<XMASChar> {
-    let height = lines.len();
-    let width = lines.first().unwrap().len();
+fn parse(lines: &Vec<String>) -> Result<Grid<XMASChar>, ParseError> {
+    let (width, height) = grid_size(lines)?;
     let mut values: Vec<XMASChar> = vec![];
     for line in lines {
         for v in line.chars() {
             let new_value = XMASChar::from_char(v);
             match new_value {
                 Some(x) => values.push(x),
-                None => continue,
+                None => return Err(ParseError::new(&format!("{:?} is not in XMAS", v))),
             }
         }
     }
-    return Grid::new(width, height, values);
+    return Ok(Grid::new(width, height, values));
+}
+
+/// Check the wordsearch is a rectangle of XMAS letters.
+/// ```
+/// assert!(day04::check_input(&vec!["XMAS".to_string(), "SAMX".to_string()]).is_ok());
+/// assert!(day04::check_input(&vec!["XMAS".to_string(), "SAM".to_string()]).is_err());
+/// assert!(day04::check_input(&vec!["XMAZ".to_string()]).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return parse(string_list).map(|_| ());
 }
 
 // Find all of a specific coordinates.
@@ -95,7 +105,7 @@ fn search(grid: &Grid<XMASChar>, x_coords: &Vec<GridCoordinate>) -> u32 {
         };
         if next_char == XMASChar::End {
             // we have hit a match!
-            info!("Found path {:?}", path_so_far);
+            trace!("Found path {:?}", path_so_far);
             found += 1;
             continue;
         }
@@ -121,23 +131,9 @@ fn search(grid: &Grid<XMASChar>, x_coords: &Vec<GridCoordinate>) -> u32 {
 }
 
 /// Find all XMAS in a wordsearch, including overlaps. Can be backwards.
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "MMMSXXMASM",
-///     "MSAMXMSMSA",
-///     "AMXSXMAAMM",
-///     "MSAMASMSMX",
-///     "XMASAMXAMM",
-///     "XXAMMXXAMA",
-///     "SMSMSASXSS",
-///     "SAXAMASAAA",
-///     "MAMMMXMMMM",
-///     "MXMXAXMASX"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day04::puzzle_a(&vec1), 18);
-/// ```
+#[doc = filelib::fixture_doc!("day04-1", a, |fixture| day04::puzzle_a(&fixture.lines()))]
 pub fn puzzle_a(string_list: &Vec<String>) -> u32 {
-    let grid = parse(string_list);
+    let grid = parse(string_list).unwrap();
     let x_coords = find_all_char(&grid, XMASChar::X);
     return search(&grid, &x_coords);
 }
@@ -186,30 +182,16 @@ fn xsearch(grid: &Grid<XMASChar>, a_coords: &Vec<GridCoordinate>) -> u32 {
         if ne == sw || se == nw {
             continue;
         }
-        info!("Found X at A centered at {:?}", a_coord);
+        trace!("Found X at A centered at {:?}", a_coord);
         result += 1;
     }
     return result;
 }
 
 /// Find all MAS in the shape of an X
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "MMMSXXMASM",
-///     "MSAMXMSMSA",
-///     "AMXSXMAAMM",
-///     "MSAMASMSMX",
-///     "XMASAMXAMM",
-///     "XXAMMXXAMA",
-///     "SMSMSASXSS",
-///     "SAXAMASAAA",
-///     "MAMMMXMMMM",
-///     "MXMXAXMASX"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day04::puzzle_b(&vec1), 9);
-/// ```
+#[doc = filelib::fixture_doc!("day04-1", b, |fixture| day04::puzzle_b(&fixture.lines()))]
 pub fn puzzle_b(string_list: &Vec<String>) -> u32 {
-    let grid = parse(string_list);
+    let grid = parse(string_list).unwrap();
     let a_coords = find_all_char(&grid, XMASChar::A);
     return xsearch(&grid, &a_coords);
 }
@@ -1,10 +1,13 @@
 extern crate filelib;
 
 pub use filelib::load_no_blanks;
+use filelib::{grid_size, ParseError};
 use gridlib::GridTraversable;
 use gridlib::{Direction, Grid, GridCoordinate};
-use log::info;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::collections::HashSet;
+use tracelib::debug;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 struct Guard {
@@ -57,12 +60,12 @@ impl Guard {
     }
 }
 
-fn parse_map(string_list: &Vec<String>) -> (Map, Guard) {
+fn parse_map(string_list: &Vec<String>) -> Result<(Map, Guard), ParseError> {
     let mut values = vec![];
     let mut guard_pos = GridCoordinate::new(0, 0);
     let mut guard_dir = Direction::NORTH;
-    let width = string_list.first().unwrap().len();
-    let height = string_list.len();
+    let mut guards = 0;
+    let (width, height) = grid_size(string_list)?;
     for (y, line) in string_list.into_iter().enumerate() {
         for (x, c) in line.chars().enumerate() {
             let space: SpaceType;
@@ -70,38 +73,58 @@ fn parse_map(string_list: &Vec<String>) -> (Map, Guard) {
                 '.' => space = SpaceType::Empty,
                 '#' => space = SpaceType::Wall,
                 '^' => {
-                    info!("Found guard at {},{}", x, y);
+                    debug!("Found guard at {},{}", x, y);
                     guard_dir = Direction::NORTH;
                     guard_pos = GridCoordinate::new(x, y);
+                    guards += 1;
                     space = SpaceType::Empty
                 }
                 '>' => {
-                    info!("Found guard at {},{}", x, y);
+                    debug!("Found guard at {},{}", x, y);
                     guard_dir = Direction::EAST;
                     guard_pos = GridCoordinate::new(x, y);
+                    guards += 1;
                     space = SpaceType::Empty
                 }
                 'V' => {
-                    info!("Found guard at {},{}", x, y);
+                    debug!("Found guard at {},{}", x, y);
                     guard_dir = Direction::SOUTH;
                     guard_pos = GridCoordinate::new(x, y);
+                    guards += 1;
                     space = SpaceType::Empty
                 }
                 '<' => {
-                    info!("Found guard at {},{}", x, y);
+                    debug!("Found guard at {},{}", x, y);
                     guard_dir = Direction::WEST;
                     guard_pos = GridCoordinate::new(x, y);
+                    guards += 1;
                     space = SpaceType::Empty
                 }
-                _ => panic!("Unknown character"),
+                _ => return Err(ParseError::new(&format!("Unknown character {:?}", c))),
             };
             values.push(space);
         }
     }
-    return (
+    if guards != 1 {
+        return Err(ParseError::new(&format!(
+            "expected one guard, found {}",
+            guards
+        )));
+    }
+    return Ok((
         Map::new(width, height, values),
         Guard::new(guard_dir, guard_pos),
-    );
+    ));
+}
+
+/// Check the lab map is a rectangle with exactly one guard.
+/// ```
+/// assert!(day06::check_input(&vec![".#.".to_string(), ".^.".to_string()]).is_ok());
+/// assert!(day06::check_input(&vec![".#.".to_string(), "...".to_string()]).is_err());
+/// assert!(day06::check_input(&vec!["^#^".to_string()]).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return parse_map(string_list).map(|_| ());
 }
 
 fn cycle_guard(m: &Map, g: &mut Guard) -> HashSet<GridCoordinate> {
@@ -120,23 +143,9 @@ fn cycle_guard(m: &Map, g: &mut Guard) -> HashSet<GridCoordinate> {
 }
 
 /// Figure out all the squares the guard will be in by raytracing and reflecting
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "....#.....",
-///     ".........#",
-///     "..........",
-///     "..#.......",
-///     ".......#..",
-///     "..........",
-///     ".#..^.....",
-///     "........#.",
-///     "#.........",
-///     "......#..."
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day06::puzzle_a(&vec1), 41);
-/// ```
+#[doc = filelib::fixture_doc!("day06-1", a, |fixture| day06::puzzle_a(&fixture.lines()))]
 pub fn puzzle_a(string_list: &Vec<String>) -> usize {
-    let (map, mut guard) = parse_map(string_list);
+    let (map, mut guard) = parse_map(string_list).unwrap();
     let positions = cycle_guard(&map, &mut guard);
     return positions.len();
 }
@@ -161,43 +170,29 @@ fn cycle_guard_detect_loop(m: &Map, g: &mut Guard) -> bool {
 }
 
 /// Flip a bit to trap the guard in a loop.
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "....#.....",
-///     ".........#",
-///     "..........",
-///     "..#.......",
-///     ".......#..",
-///     "..........",
-///     ".#..^.....",
-///     "........#.",
-///     "#.........",
-///     "......#..."
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day06::puzzle_b(&vec1), 6);
-/// ```
+#[doc = filelib::fixture_doc!("day06-1", b, |fixture| day06::puzzle_b(&fixture.lines()))]
 pub fn puzzle_b(string_list: &Vec<String>) -> usize {
-    let (map, guard) = parse_map(string_list);
+    let (map, guard) = parse_map(string_list).unwrap();
     let mut clone_iniital_path_guard = guard.clone();
     let positions = cycle_guard(&map, &mut clone_iniital_path_guard);
 
-    return map
+    // Can't put in starting position, and if we never reach it, no point in checking
+    let candidates: Vec<GridCoordinate> = map
         .coord_iter()
+        .filter(|coord| *coord != guard.position && positions.contains(coord))
+        .collect();
+    // Each placement is independent, so these can be checked in any order
+    #[cfg(feature = "parallel")]
+    let candidates = candidates.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let candidates = candidates.into_iter();
+
+    return candidates
         .filter(|&coord| {
-            if coord == guard.position {
-                // Can't put in starting position
-                return false;
-            }
-            if !positions.contains(&coord) {
-                // If we never reach it, no point in checking
-                return false;
-            }
-            // Valid coord to switch
             let mut cur_map = map.clone();
             cur_map.set_value(coord, SpaceType::Wall);
             let mut cur_guard = guard.clone();
             return cycle_guard_detect_loop(&cur_map, &mut cur_guard);
         })
-        .collect::<Vec<_>>()
-        .len();
+        .count();
 }
@@ -0,0 +1,317 @@
+//! Equations `target: a b c ...` solved with any set of operators.
+//!
+//! Operators are applied left to right with no precedence, so the last one
+//! applied is the rightmost. That makes it easiest to search from the right:
+//! undo the last operator on the target to find what everything before it
+//! has to come to, and only go on if that's possible. `*` can only be undone
+//! when the target divides, and `||` when the target ends in the number, so
+//! most branches die straight away.
+//!
+//! An operator with no inverse, or one that can't say what came before (like
+//! `*` by 0), falls back to trying every way of evaluating the numbers to its
+//! left, which is fine for a few numbers and slow for many.
+//! ```
+//! use day07::operators::{Inverse, Operators};
+//! let operators = Operators::new()
+//!     .operator("-", |left, right| left.checked_sub(right))
+//!     .invertible("+", |left, right| left.checked_add(right), |target, right| {
+//!         return target.checked_sub(right).map_or(Inverse::Impossible, Inverse::Left);
+//!     });
+//! assert_eq!(operators.solutions(2, &[5, 2, 1]), vec![vec!["-", "-"]]);
+//! assert_eq!(operators.solutions(4, &[5, 2, 1]), vec![vec!["-", "+"]]);
+//! ```
+use crate::Number;
+
+/// What undoing an operator on a target gives.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Inverse {
+    /// The one left operand that gives the target
+    Left(Number),
+    /// No left operand gives the target
+    Impossible,
+    /// Any number of left operands could, so they have to be tried
+    Ambiguous,
+}
+
+type Apply = Box<dyn Fn(Number, Number) -> Option<Number> + Send + Sync>;
+type Undo = Box<dyn Fn(Number, Number) -> Inverse + Send + Sync>;
+
+pub struct Operator {
+    pub symbol: String,
+    /// `None` when the result doesn't exist or doesn't fit
+    apply: Apply,
+    /// From the target and the right operand
+    inverse: Option<Undo>,
+}
+
+impl Operator {
+    pub fn apply(&self, left: Number, right: Number) -> Option<Number> {
+        return (self.apply)(left, right);
+    }
+
+    pub fn inverse(&self, target: Number, right: Number) -> Inverse {
+        return match &self.inverse {
+            Some(inverse) => inverse(target, right),
+            None => Inverse::Ambiguous,
+        };
+    }
+}
+
+pub struct Operators {
+    operators: Vec<Operator>,
+}
+
+impl Default for Operators {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+fn concat(left: Number, right: Number) -> Option<Number> {
+    if left < 0 || right < 0 {
+        return None;
+    }
+    let digit_count = right.checked_ilog10().unwrap_or(0) + 1;
+    let base: Number = 10;
+    return left.checked_mul(base.pow(digit_count))?.checked_add(right);
+}
+
+impl Operators {
+    pub fn new() -> Operators {
+        return Operators { operators: vec![] };
+    }
+
+    /// Add an operator that can only be searched forwards.
+    pub fn operator(
+        mut self,
+        symbol: &str,
+        apply: impl Fn(Number, Number) -> Option<Number> + Send + Sync + 'static,
+    ) -> Operators {
+        self.operators.push(Operator {
+            symbol: symbol.to_string(),
+            apply: Box::new(apply),
+            inverse: None,
+        });
+        return self;
+    }
+
+    /// Add an operator with an inverse, which takes the target and the right
+    /// operand.
+    pub fn invertible(
+        mut self,
+        symbol: &str,
+        apply: impl Fn(Number, Number) -> Option<Number> + Send + Sync + 'static,
+        inverse: impl Fn(Number, Number) -> Inverse + Send + Sync + 'static,
+    ) -> Operators {
+        self.operators.push(Operator {
+            symbol: symbol.to_string(),
+            apply: Box::new(apply),
+            inverse: Some(Box::new(inverse)),
+        });
+        return self;
+    }
+
+    /// `+` and `*`, part a.
+    ///
+    /// Puzzle numbers are never negative, so neither is anything they come
+    /// to, and `+` gives up as soon as undoing it would go below 0.
+    pub fn add_multiply() -> Operators {
+        return Operators::new()
+            .invertible(
+                "+",
+                |left, right| left.checked_add(right),
+                |target, right| match target.checked_sub(right) {
+                    Some(left) if left >= 0 => Inverse::Left(left),
+                    _ => Inverse::Impossible,
+                },
+            )
+            .invertible(
+                "*",
+                |left, right| left.checked_mul(right),
+                |target, right| {
+                    if right == 0 {
+                        // Anything times 0 is 0
+                        return match target {
+                            0 => Inverse::Ambiguous,
+                            _ => Inverse::Impossible,
+                        };
+                    }
+                    if target % right != 0 {
+                        return Inverse::Impossible;
+                    }
+                    return Inverse::Left(target / right);
+                },
+            );
+    }
+
+    /// `+`, `*` and `||`, which sticks the digits together, part b.
+    pub fn with_concat() -> Operators {
+        return Operators::add_multiply().invertible("||", concat, |target, right| {
+            if target < 0 || right < 0 {
+                return Inverse::Impossible;
+            }
+            let digit_count = right.checked_ilog10().unwrap_or(0) + 1;
+            let base: Number = 10;
+            let Some(shift) = base.checked_pow(digit_count) else {
+                return Inverse::Impossible;
+            };
+            if target < right || (target - right) % shift != 0 {
+                return Inverse::Impossible;
+            }
+            return Inverse::Left((target - right) / shift);
+        });
+    }
+
+    pub fn operators(&self) -> &[Operator] {
+        return &self.operators;
+    }
+
+    /// Every way of evaluating `numbers`, as the value and the operators used.
+    fn forward(&self, numbers: &[Number]) -> Vec<(Number, Vec<usize>)> {
+        let mut result = vec![(numbers[0], vec![])];
+        for &number in &numbers[1..] {
+            let mut next = vec![];
+            for (value, used) in &result {
+                for (index, operator) in self.operators.iter().enumerate() {
+                    if let Some(value) = operator.apply(*value, number) {
+                        let mut used = used.clone();
+                        used.push(index);
+                        next.push((value, used));
+                    }
+                }
+            }
+            result = next;
+        }
+        return result;
+    }
+
+    /// Undo operators from the right. `after` is what's been chosen so far,
+    /// rightmost first. `found` gets each full assignment and says whether to
+    /// stop, which this passes back up.
+    fn backward(
+        &self,
+        target: Number,
+        numbers: &[Number],
+        after: &mut Vec<usize>,
+        found: &mut dyn FnMut(Vec<usize>) -> bool,
+    ) -> bool {
+        let Some((&last, before)) = numbers.split_last() else {
+            return false;
+        };
+        if before.is_empty() {
+            if last != target {
+                return false;
+            }
+            return found(after.iter().rev().copied().collect());
+        }
+        for (index, operator) in self.operators.iter().enumerate() {
+            match operator.inverse(target, last) {
+                Inverse::Impossible => continue,
+                Inverse::Left(left) => {
+                    after.push(index);
+                    let stop = self.backward(left, before, after, found);
+                    after.pop();
+                    if stop {
+                        return true;
+                    }
+                }
+                Inverse::Ambiguous => {
+                    for (value, mut used) in self.forward(before) {
+                        if operator.apply(value, last) != Some(target) {
+                            continue;
+                        }
+                        used.push(index);
+                        used.extend(after.iter().rev());
+                        if found(used) {
+                            return true;
+                        }
+                    }
+                }
+            }
+        }
+        return false;
+    }
+
+    /// Every choice of operators, left to right, that makes `numbers` come
+    /// to `target`, ordered by the order the operators were added in.
+    /// ```
+    /// let operators = day07::operators::Operators::with_concat();
+    /// assert_eq!(operators.solutions(3267, &[81, 40, 27]), vec![vec!["+", "*"], vec!["*", "+"]]);
+    /// assert_eq!(operators.solutions(7290, &[6, 8, 6, 15]), vec![vec!["*", "||", "*"]]);
+    /// ```
+    pub fn solutions(&self, target: Number, numbers: &[Number]) -> Vec<Vec<&str>> {
+        let mut found = vec![];
+        self.backward(target, numbers, &mut vec![], &mut |used| {
+            found.push(used);
+            return false;
+        });
+        // In the order the operators were added, not the order they're found
+        found.sort();
+        return found
+            .iter()
+            .map(|used| {
+                used.iter()
+                    .map(|&i| self.operators[i].symbol.as_str())
+                    .collect()
+            })
+            .collect();
+    }
+
+    /// Whether any choice of operators works, stopping at the first.
+    pub fn is_solvable(&self, target: Number, numbers: &[Number]) -> bool {
+        return self.backward(target, numbers, &mut vec![], &mut |_| true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(concat(1, 2), Some(12));
+        assert_eq!(concat(99, 3232), Some(993232));
+        assert_eq!(concat(5, 0), Some(50));
+        assert_eq!(concat(Number::MAX, 1), None);
+    }
+
+    #[test]
+    fn test_concat_inverse() {
+        let operators = Operators::with_concat();
+        let concat = &operators.operators()[2];
+        assert_eq!(concat.inverse(156, 6), Inverse::Left(15));
+        assert_eq!(concat.inverse(6, 6), Inverse::Left(0));
+        assert_eq!(concat.inverse(50, 0), Inverse::Left(5));
+        assert_eq!(concat.inverse(156, 5), Inverse::Impossible);
+        assert_eq!(concat.inverse(6, 16), Inverse::Impossible);
+    }
+
+    #[test]
+    fn test_multiply_by_zero() {
+        // 0 can't be undone, so what's left of it is evaluated forwards
+        let operators = Operators::add_multiply();
+        assert_eq!(
+            operators.solutions(3, &[2, 5, 0, 3]),
+            vec![vec!["+", "*", "+"], vec!["*", "*", "+"]]
+        );
+        assert!(!operators.is_solvable(4, &[2, 5, 0, 3]));
+    }
+
+    #[test]
+    fn test_long_equation() {
+        // 3^59 assignments forwards, almost all cut off backwards
+        let mut state: u64 = 7;
+        let mut numbers = vec![];
+        for _ in 0..60 {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            numbers.push((state >> 33) as Number % 900 + 100);
+        }
+        let target: Number = numbers.iter().sum();
+        let operators = Operators::with_concat();
+        assert!(operators.is_solvable(target, &numbers));
+        assert!(!operators.is_solvable(target + 1, &numbers));
+        assert!(operators
+            .solutions(target, &numbers)
+            .contains(&vec!["+"; 59]));
+    }
+}
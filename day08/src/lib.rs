@@ -1,10 +1,11 @@
 extern crate filelib;
 
 pub use filelib::load_no_blanks;
+use filelib::{grid_size, ParseError};
 use gridlib::GridTraversable;
 use gridlib::{Grid, GridCoordinate};
-use log::info;
 use std::collections::HashSet;
+use tracelib::trace;
 
 type Map = Grid<Option<Antenna>>;
 
@@ -23,7 +24,7 @@ impl Antenna {
     }
 
     fn get_antinodes(&self, other: &Antenna, grid: &Map) -> Vec<GridCoordinate> {
-        info!("Trying to find Antinode for {:?}, {:?}", self, other);
+        trace!("Trying to find Antinode for {:?}, {:?}", self, other);
         let mut result: Vec<GridCoordinate> = vec![];
         if other.frequency != self.frequency {
             return result;
@@ -69,12 +70,12 @@ impl Antenna {
                 result.push(GridCoordinate::new(x, y));
             }
         }
-        info!("Antinodes found {:?}", result);
+        trace!("Antinodes found {:?}", result);
         return result;
     }
 
     fn get_continual_antinodes(&self, other: &Antenna, grid: &Map) -> Vec<GridCoordinate> {
-        info!("Trying to find Antinode for {:?}, {:?}", self, other);
+        trace!("Trying to find Antinode for {:?}, {:?}", self, other);
         let mut result: Vec<GridCoordinate> = vec![];
         if other.frequency != self.frequency {
             return result;
@@ -152,14 +153,13 @@ impl Antenna {
                 antinode_2_y = y.checked_sub(y_diff);
             }
         }
-        info!("Antinodes found {:?}", result);
+        trace!("Antinodes found {:?}", result);
         return result;
     }
 }
 
-fn parse_map(string_list: &Vec<String>) -> Map {
-    let width = string_list.first().unwrap().len();
-    let height = string_list.len();
+fn parse_map(string_list: &Vec<String>) -> Result<Map, ParseError> {
+    let (width, height) = grid_size(string_list)?;
     let mut values = vec![];
     for (y, line) in string_list.into_iter().enumerate() {
         for (x, c) in line.chars().enumerate() {
@@ -169,34 +169,27 @@ fn parse_map(string_list: &Vec<String>) -> Map {
                     v = Some(Antenna::new(GridCoordinate::new(x, y), c));
                 }
                 '.' => v = None,
-                _ => panic!("Unknown char {}", c),
+                _ => return Err(ParseError::new(&format!("Unknown char {:?}", c))),
             };
             values.push(v);
         }
     }
-    return Map::new(width, height, values);
+    return Ok(Map::new(width, height, values));
 }
 
-/// Count antinodes
+/// Check the map is a rectangle of antennas and empty space.
 /// ```
-/// let vec1: Vec<String> = vec![
-///     "............",
-///     "........0...",
-///     ".....0......",
-///     ".......0....",
-///     "....0.......",
-///     "......A.....",
-///     "............",
-///     "............",
-///     "........A...",
-///     ".........A..",
-///     "............",
-///     "............"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day08::puzzle_a(&vec1), 14);
+/// assert!(day08::check_input(&vec!["..a".to_string(), "0..".to_string()]).is_ok());
+/// assert!(day08::check_input(&vec!["..#".to_string()]).is_err());
 /// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return parse_map(string_list).map(|_| ());
+}
+
+/// Count antinodes
+#[doc = filelib::fixture_doc!("day08-1", a, |fixture| day08::puzzle_a(&fixture.lines()))]
 pub fn puzzle_a(string_list: &Vec<String>) -> usize {
-    let map = parse_map(string_list);
+    let map = parse_map(string_list).unwrap();
     let mut antinodes = HashSet::new();
     let mut antennas = vec![];
     for coord in map.coord_iter() {
@@ -216,25 +209,9 @@ pub fn puzzle_a(string_list: &Vec<String>) -> usize {
 }
 
 /// Even more antinodes
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "............",
-///     "........0...",
-///     ".....0......",
-///     ".......0....",
-///     "....0.......",
-///     "......A.....",
-///     "............",
-///     "............",
-///     "........A...",
-///     ".........A..",
-///     "............",
-///     "............"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day08::puzzle_b(&vec1), 34);
-/// ```
+#[doc = filelib::fixture_doc!("day08-1", b, |fixture| day08::puzzle_b(&fixture.lines()))]
 pub fn puzzle_b(string_list: &Vec<String>) -> usize {
-    let map = parse_map(string_list);
+    let map = parse_map(string_list).unwrap();
     let mut antinodes = HashSet::new();
     let mut antennas = vec![];
     for coord in map.coord_iter() {
@@ -0,0 +1,391 @@
+//! A disk as extents, runs of blocks belonging to one file, and ways of
+//! compacting it.
+//!
+//! Free space isn't stored, it's whatever lies between the extents. Moving
+//! whole files keeps the gaps in one min-heap of start positions per gap
+//! size, so finding where a file goes is a look at the top of at most ten
+//! heaps rather than a scan of the disk.
+use crate::Number;
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Extent {
+    pub id: Number,
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Extent {
+    /// One past the last block.
+    pub fn end(&self) -> usize {
+        return self.start + self.len;
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Strategy {
+    /// Move single blocks from the end of the disk into the first free block,
+    /// splitting files up, until there are no gaps. Part a.
+    Blocks,
+    /// Move each whole file, highest id first, to the leftmost gap before it
+    /// that it fits in. Part b.
+    FirstFit,
+    /// Like `FirstFit`, but to the smallest gap before it that it fits in,
+    /// the leftmost of those if there are several.
+    BestFit,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Disk {
+    /// By start position
+    extents: Vec<Extent>,
+    /// Blocks in all, free space at the end included
+    size: usize,
+}
+
+/// Min-heaps of gap starts, one per gap size.
+struct FreeList {
+    by_size: Vec<BinaryHeap<Reverse<usize>>>,
+}
+
+impl FreeList {
+    fn new(gaps: &[(usize, usize)]) -> FreeList {
+        let largest = gaps.iter().map(|&(_, len)| len).max().unwrap_or(0);
+        let mut free = FreeList {
+            by_size: vec![BinaryHeap::new(); largest + 1],
+        };
+        for &(start, len) in gaps {
+            free.add(start, len);
+        }
+        return free;
+    }
+
+    fn add(&mut self, start: usize, len: usize) {
+        if len > 0 {
+            self.by_size[len].push(Reverse(start));
+        }
+    }
+
+    /// Where a file of `len` blocks starting at `before` should go, and the
+    /// size of the gap it goes in.
+    fn find(&self, len: usize, before: usize, strategy: Strategy) -> Option<(usize, usize)> {
+        let mut best: Option<(usize, usize)> = None;
+        for size in len..self.by_size.len() {
+            let Some(&Reverse(start)) = self.by_size[size].peek() else {
+                continue;
+            };
+            if start >= before {
+                continue;
+            }
+            if strategy == Strategy::BestFit {
+                return Some((start, size));
+            }
+            if best.is_none_or(|(best_start, _)| start < best_start) {
+                best = Some((start, size));
+            }
+        }
+        return best;
+    }
+
+    /// Put a file of `len` blocks in the gap of `size` at `start`.
+    fn take(&mut self, start: usize, size: usize, len: usize) {
+        self.by_size[size].pop();
+        self.add(start + len, size - len);
+    }
+}
+
+impl Disk {
+    /// A disk from the dense format's sizes, alternating file then free
+    /// space, with file ids counting up from 0.
+    pub fn from_sizes(sizes: &[usize]) -> Disk {
+        let mut extents = vec![];
+        let mut position = 0;
+        for (i, &len) in sizes.iter().enumerate() {
+            if i % 2 == 0 && len > 0 {
+                extents.push(Extent {
+                    id: (i / 2) as Number,
+                    start: position,
+                    len: len,
+                });
+            }
+            position += len;
+        }
+        return Disk {
+            extents: extents,
+            size: position,
+        };
+    }
+
+    pub fn extents(&self) -> &[Extent] {
+        return &self.extents;
+    }
+
+    /// Free runs as `(start, len)`, left to right, including any at the end.
+    pub fn gaps(&self) -> Vec<(usize, usize)> {
+        let mut result = vec![];
+        let mut position = 0;
+        for extent in &self.extents {
+            if extent.start > position {
+                result.push((position, extent.start - position));
+            }
+            position = extent.end();
+        }
+        if self.size > position {
+            result.push((position, self.size - position));
+        }
+        return result;
+    }
+
+    /// A copy of the disk compacted with `strategy`.
+    /// ```
+    /// use day09::disk::{Disk, Strategy};
+    /// let disk = Disk::from_sizes(&[1, 2, 3, 4, 5]);
+    /// assert_eq!(disk.compact(Strategy::Blocks).to_string(), "022111222......");
+    /// let disk = Disk::from_sizes(&[2, 3, 3, 3, 1, 3, 3, 1, 2, 1, 4, 1, 4, 1, 3, 1, 4, 0, 2]);
+    /// assert_eq!(disk.compact(Strategy::FirstFit).to_string(), "00992111777.44.333....5555.6666.....8888..");
+    /// ```
+    pub fn compact(&self, strategy: Strategy) -> Disk {
+        let mut extents = match strategy {
+            Strategy::Blocks => self.compact_blocks(),
+            _ => self.compact_files(strategy),
+        };
+        extents.sort_by_key(|e| e.start);
+        return Disk {
+            extents: extents,
+            size: self.size,
+        };
+    }
+
+    /// Every used block ends up in the first `total` blocks. The ones there
+    /// already stay put and the gaps are filled from the end, last block first.
+    fn compact_blocks(&self) -> Vec<Extent> {
+        let total: usize = self.extents.iter().map(|e| e.len).sum();
+        // What's past `total`, last block first, as (id, blocks)
+        let mut tail: Vec<(Number, usize)> = self
+            .extents
+            .iter()
+            .rev()
+            .filter(|e| e.end() > total)
+            .map(|e| (e.id, e.end() - e.start.max(total)))
+            .collect();
+        tail.reverse();
+
+        let mut result: Vec<Extent> = vec![];
+        let mut place = |id: Number, start: usize, len: usize| {
+            // Keep a file that's split across a gap boundary in one extent
+            if let Some(last) = result.last_mut() {
+                if last.id == id && last.end() == start {
+                    last.len += len;
+                    return;
+                }
+            }
+            result.push(Extent {
+                id: id,
+                start: start,
+                len: len,
+            });
+        };
+        let gaps = self.gaps();
+        let mut gaps = gaps.iter().peekable();
+        for extent in &self.extents {
+            while let Some(&&(start, len)) = gaps.peek() {
+                if start > extent.start || start >= total {
+                    break;
+                }
+                gaps.next();
+                let (mut position, end) = (start, (start + len).min(total));
+                while position < end {
+                    let (id, left) = tail.last_mut().unwrap();
+                    let moved = (*left).min(end - position);
+                    place(*id, position, moved);
+                    *left -= moved;
+                    position += moved;
+                    if *left == 0 {
+                        tail.pop();
+                    }
+                }
+            }
+            if extent.start < total {
+                place(
+                    extent.id,
+                    extent.start,
+                    extent.end().min(total) - extent.start,
+                );
+            }
+        }
+        return result;
+    }
+
+    fn compact_files(&self, strategy: Strategy) -> Vec<Extent> {
+        let mut free = FreeList::new(&self.gaps());
+        let mut extents = self.extents.clone();
+        let mut order: Vec<usize> = (0..extents.len()).collect();
+        order.sort_by_key(|&i| Reverse(extents[i].id));
+        for i in order {
+            let file = extents[i];
+            if let Some((start, size)) = free.find(file.len, file.start, strategy) {
+                free.take(start, size, file.len);
+                // Where it was is never used again: everything left to move is
+                // further left
+                extents[i].start = start;
+            }
+        }
+        return extents;
+    }
+
+    /// The sum of each block's position times its file id.
+    ///
+    /// Worked out per extent, and in `u128` so multi-million digit maps
+    /// don't overflow.
+    pub fn checksum(&self) -> u128 {
+        let mut sum: u128 = 0;
+        for extent in &self.extents {
+            let (start, len) = (extent.start as u128, extent.len as u128);
+            // start + (start + 1) + ... + (start + len - 1)
+            let positions = len * start + len * (len - 1) / 2;
+            sum += positions * extent.id as u128;
+        }
+        return sum;
+    }
+
+    /// Every block's file id, `None` for free space.
+    pub fn blocks(&self) -> Vec<Option<Number>> {
+        let mut result = vec![None; self.size];
+        for extent in &self.extents {
+            for block in &mut result[extent.start..extent.end()] {
+                *block = Some(extent.id);
+            }
+        }
+        return result;
+    }
+
+    /// Back to the dense format, if it can be written that way: files in id
+    /// order from 0, each in one piece, and no run longer than 9. Compacting
+    /// usually breaks the first two. An empty gap at the very end isn't kept.
+    /// ```
+    /// let disk = day09::disk::Disk::from_sizes(&[2, 3, 3, 0, 1]);
+    /// assert_eq!(disk.dense(), Some("23301".to_string()));
+    /// assert_eq!(disk.compact(day09::disk::Strategy::Blocks).dense(), None);
+    /// ```
+    pub fn dense(&self) -> Option<String> {
+        let mut result = String::new();
+        let mut position = 0;
+        for (index, extent) in self.extents.iter().enumerate() {
+            if extent.id != index as Number || (index == 0 && extent.start != 0) {
+                return None;
+            }
+            if index > 0 {
+                result.push(char::from_digit((extent.start - position) as u32, 10)?);
+            }
+            result.push(char::from_digit(extent.len as u32, 10)?);
+            position = extent.end();
+        }
+        if self.size > position {
+            result.push(char::from_digit((self.size - position) as u32, 10)?);
+        }
+        return Some(result);
+    }
+}
+
+impl std::fmt::Display for Disk {
+    /// The puzzle's picture, `0..111....22222`, with ids past 9 shown by
+    /// their last digit.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for block in self.blocks() {
+            match block {
+                Some(id) => write!(f, "{}", id % 10)?,
+                None => write!(f, ".")?,
+            }
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Disk {
+        let sizes: Vec<usize> = "2333133121414131402"
+            .chars()
+            .map(|c| c.to_digit(10).unwrap() as usize)
+            .collect();
+        return Disk::from_sizes(&sizes);
+    }
+
+    /// A long random map, from a fixed seed so it's the same every run.
+    fn big_map(digits: usize) -> Vec<usize> {
+        let mut state: u64 = 2024;
+        let mut result = vec![];
+        for i in 0..digits {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let digit = (state >> 33) as usize % 10;
+            // Files can't be empty
+            result.push(if i % 2 == 0 { digit.max(1) } else { digit });
+        }
+        return result;
+    }
+
+    #[test]
+    fn test_example() {
+        let disk = example();
+        assert_eq!(
+            disk.to_string(),
+            "00...111...2...333.44.5555.6666.777.888899"
+        );
+        let blocks = disk.compact(Strategy::Blocks);
+        assert_eq!(
+            blocks.to_string(),
+            "0099811188827773336446555566.............."
+        );
+        assert_eq!(blocks.checksum(), 1928);
+        assert_eq!(disk.compact(Strategy::FirstFit).checksum(), 2858);
+        assert_eq!(disk.dense(), Some("2333133121414131402".to_string()));
+    }
+
+    #[test]
+    fn test_best_fit() {
+        // File 2 fits the gap of 3 and the gap of 1, best fit takes the 1 and
+        // leaves room for file 1
+        let disk = Disk::from_sizes(&[1, 3, 2, 1, 1]);
+        assert_eq!(disk.compact(Strategy::FirstFit).to_string(), "0211....");
+        assert_eq!(disk.compact(Strategy::BestFit).to_string(), "011...2.");
+    }
+
+    #[test]
+    fn test_gaps() {
+        let disk = Disk::from_sizes(&[1, 2, 3, 0, 1, 4]);
+        assert_eq!(disk.gaps(), vec![(1, 2), (7, 4)]);
+        assert_eq!(disk.dense(), Some("123014".to_string()));
+        assert_eq!(disk.compact(Strategy::Blocks).gaps(), vec![(5, 6)]);
+    }
+
+    #[test]
+    fn test_big_map() {
+        let sizes = big_map(1_000_000);
+        let disk = Disk::from_sizes(&sizes);
+        let dense: String = sizes
+            .iter()
+            .map(|&s| char::from_digit(s as u32, 10).unwrap())
+            .collect();
+        assert_eq!(disk.dense(), Some(dense));
+        let total: usize = disk.extents().iter().map(|e| e.len).sum();
+        for strategy in [Strategy::Blocks, Strategy::FirstFit, Strategy::BestFit] {
+            let compacted = disk.compact(strategy);
+            let used: usize = compacted.extents().iter().map(|e| e.len).sum();
+            assert_eq!(used, total);
+            // Nothing overlaps
+            for pair in compacted.extents().windows(2) {
+                assert!(pair[0].end() <= pair[1].start);
+            }
+        }
+        assert_eq!(
+            disk.compact(Strategy::Blocks).gaps(),
+            vec![(total, disk.size - total)]
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Slow versions of the puzzles, to check the fast ones against.
+
+use crate::{parse_digits, Number};
+use filelib::ParseError;
+
+/// The disk one entry per block, `None` for free ones.
+fn parse_map(string_list: &Vec<String>) -> Result<Vec<Option<Number>>, ParseError> {
+    let digits = parse_digits(string_list)?;
+    let mut result = vec![];
+    for (i, &count) in digits.iter().enumerate() {
+        let block = if i % 2 == 0 {
+            Some(i as Number / 2)
+        } else {
+            None
+        };
+        for _ in 0..count {
+            result.push(block);
+        }
+    }
+    return Ok(result);
+}
+
+fn checksum(blocks: Vec<Option<Number>>) -> Number {
+    let mut num = 0;
+    for (i, block) in blocks.iter().enumerate() {
+        num += block.unwrap_or(0) * i as Number;
+    }
+    return num;
+}
+
+/// Move blocks one at a time on the expanded disk, then checksum it.
+///
+/// The rightmost used block goes to the leftmost free one until every free
+/// block is after every used one.
+/// ```
+/// assert_eq!(day09::reference::puzzle_a(&vec!["2333133121414131402".to_string()]), 1928);
+/// ```
+pub fn puzzle_a(string_list: &Vec<String>) -> Number {
+    let mut blocks = parse_map(string_list).unwrap();
+    let (mut left, mut right) = (0, blocks.len());
+    loop {
+        while left < blocks.len() && blocks[left].is_some() {
+            left += 1;
+        }
+        while right > 0 && blocks[right - 1].is_none() {
+            right -= 1;
+        }
+        if right == 0 || left >= right - 1 {
+            break;
+        }
+        blocks.swap(left, right - 1);
+    }
+    return checksum(blocks);
+}
+
+/// Move whole files one block at a time on the expanded disk, then checksum it.
+///
+/// Each file, highest id first, goes to the leftmost run of free blocks it fits
+/// in that starts before it.
+/// ```
+/// assert_eq!(day09::reference::puzzle_b(&vec!["2333133121414131402".to_string()]), 2858);
+/// ```
+pub fn puzzle_b(string_list: &Vec<String>) -> Number {
+    let mut blocks = parse_map(string_list).unwrap();
+    let highest_id = blocks.iter().flatten().max().copied().unwrap_or(0);
+    for id in (0..=highest_id).rev() {
+        let file_start = blocks.iter().position(|&b| b == Some(id)).unwrap();
+        let file_size = blocks.iter().filter(|&&b| b == Some(id)).count();
+
+        let mut run_start = 0;
+        let mut run_size = 0;
+        for i in 0..file_start {
+            if blocks[i].is_some() {
+                run_size = 0;
+                continue;
+            }
+            if run_size == 0 {
+                run_start = i;
+            }
+            run_size += 1;
+            if run_size == file_size {
+                for j in 0..file_size {
+                    blocks[run_start + j] = Some(id);
+                    blocks[file_start + j] = None;
+                }
+                break;
+            }
+        }
+    }
+    return checksum(blocks);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// A disk map in the puzzle format, files are never empty but gaps can be.
+    /// Mostly small, so files often fill a gap exactly.
+    fn disk_map_strategy() -> impl Strategy<Value = String> {
+        let size = prop_oneof![3 => 1..4u32, 1 => 1..10u32];
+        let gap = prop_oneof![3 => 0..4u32, 1 => 0..10u32];
+        return prop::collection::vec((size, gap), 1..8).prop_map(|pairs| {
+            let mut map = String::new();
+            for (file, gap) in pairs {
+                map.push(char::from_digit(file, 10).unwrap());
+                map.push(char::from_digit(gap, 10).unwrap());
+            }
+            return map;
+        });
+    }
+
+    proptest! {
+        #[test]
+        fn prop_block_compaction_matches_blocks(map in disk_map_strategy()) {
+            let input = vec![map];
+            prop_assert_eq!(crate::puzzle_a(&input), puzzle_a(&input));
+        }
+
+        #[test]
+        fn prop_interval_compaction_matches_blocks(map in disk_map_strategy()) {
+            let input = vec![map];
+            prop_assert_eq!(crate::puzzle_b(&input), puzzle_b(&input));
+        }
+
+        #[test]
+        fn prop_dense_round_trips(map in disk_map_strategy()) {
+            let disk = crate::parse_disk(&vec![map.clone()]).unwrap();
+            prop_assert_eq!(disk.dense(), Some(map.trim_end_matches('0').to_string()));
+        }
+    }
+}
@@ -5,23 +5,34 @@ use std::collections::HashSet;
 use std::collections::VecDeque;
 
 pub use filelib::load_no_blanks;
+use filelib::{grid_size, ParseError};
 use gridlib::Grid;
 use gridlib::GridCoordinate;
 use gridlib::GridTraversable;
-use log::info;
+use tracelib::trace;
 
-fn parse_topgraphic_map(string_list: &Vec<String>) -> Grid<u32> {
+fn parse_topgraphic_map(string_list: &Vec<String>) -> Result<Grid<u32>, ParseError> {
     let mut values = vec![];
-    let width = string_list.first().unwrap().len();
-    let height = string_list.len();
+    let (width, height) = grid_size(string_list)?;
     for s in string_list.iter() {
         for c in s.chars() {
-            let v = c.to_digit(10).unwrap();
+            let Some(v) = c.to_digit(10) else {
+                return Err(ParseError::new(&format!("{:?} is not a height", c)));
+            };
             values.push(v);
         }
     }
 
-    return Grid::new(width, height, values);
+    return Ok(Grid::new(width, height, values));
+}
+
+/// Check the map is a rectangle of heights.
+/// ```
+/// assert!(day10::check_input(&vec!["0123".to_string(), "9876".to_string()]).is_ok());
+/// assert!(day10::check_input(&vec!["01.3".to_string()]).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return parse_topgraphic_map(string_list).map(|_| ());
 }
 
 // Trails start at height 0, end at 9 and increase by 1 step at a time.
@@ -53,7 +64,7 @@ fn find_trails(grid: &Grid<u32>) -> Vec<Vec<GridCoordinate>> {
                 next_path.push(current);
                 if current == target {
                     results.push(next_path);
-                    info!("Path between {} and {}", zero, nine);
+                    trace!("Path between {} and {}", zero, nine);
                     // There may be more than one path...
                     continue;
                 }
@@ -65,7 +76,7 @@ fn find_trails(grid: &Grid<u32>) -> Vec<Vec<GridCoordinate>> {
                     }
                 }
             }
-            info!("No path between {} and {}", zero, nine);
+            trace!("No path between {} and {}", zero, nine);
         }
     }
 
@@ -94,21 +105,9 @@ fn score_trails(trails: Vec<Vec<GridCoordinate>>) -> u32 {
 }
 
 /// score the trailheads
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "89010123",
-///     "78121874",
-///     "87430965",
-///     "96549874",
-///     "45678903",
-///     "32019012",
-///     "01329801",
-///     "10456732"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day10::puzzle_a(&vec1), 36);
-/// ```
+#[doc = filelib::fixture_doc!("day10-1", a, |fixture| day10::puzzle_a(&fixture.lines()))]
 pub fn puzzle_a(string_list: &Vec<String>) -> u32 {
-    let map = parse_topgraphic_map(string_list);
+    let map = parse_topgraphic_map(string_list).unwrap();
     let trails = find_trails(&map);
     return score_trails(trails);
 }
@@ -134,21 +133,9 @@ fn rate_trails(trails: Vec<Vec<GridCoordinate>>) -> u32 {
 }
 
 /// Determine rating instead.
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "89010123",
-///     "78121874",
-///     "87430965",
-///     "96549874",
-///     "45678903",
-///     "32019012",
-///     "01329801",
-///     "10456732"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day10::puzzle_b(&vec1), 81);
-/// ```
+#[doc = filelib::fixture_doc!("day10-1", b, |fixture| day10::puzzle_b(&fixture.lines()))]
 pub fn puzzle_b(string_list: &Vec<String>) -> u32 {
-    let map = parse_topgraphic_map(string_list);
+    let map = parse_topgraphic_map(string_list).unwrap();
     let trails = find_trails(&map);
     return rate_trails(trails);
 }
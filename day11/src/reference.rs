@@ -0,0 +1,41 @@
+//! Slow versions of the puzzles, to check the fast ones against.
+
+use crate::parse_stones;
+use crate::rules::Rules;
+
+/// Count the stones after `blinks` by blinking the whole row in order, the way part a does.
+/// ```
+/// assert_eq!(day11::reference::count_stones(&vec!["125 17".to_string()], 6), 22);
+/// ```
+pub fn count_stones(string_list: &Vec<String>, blinks: usize) -> usize {
+    let rules = Rules::puzzle();
+    let mut stones = parse_stones(string_list).unwrap();
+    for _ in 0..blinks {
+        stones = rules.blink(&stones);
+    }
+    return stones.len();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Number;
+    use proptest::prelude::*;
+
+    proptest! {
+        /// Counting by value forgets the order, so check it never loses or invents a stone.
+        #[test]
+        fn prop_counts_match_blink(
+            stones in prop::collection::vec(0..100_000 as Number, 1..4),
+            blinks in 1..16usize,
+        ) {
+            let rules = Rules::puzzle();
+            let mut row = stones.clone();
+            for blink in 1..=blinks {
+                row = rules.blink(&row);
+                let fast = crate::count_stones(stones.clone(), blink);
+                prop_assert_eq!(fast, row.len(), "first disagreement after {} blinks", blink);
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Blink rules declared as data, and ways of running them.
+//!
+//! A rule is a test on a stone and what that stone turns into. Each blink
+//! every stone takes the first rule that matches it, and a stone no rule
+//! matches stays as it is. Stones never affect each other, so after the
+//! first blink only how many of each value there are matters: `evolve` and
+//! friends keep a count per value instead of the row.
+//! ```
+//! use day11::rules::Rules;
+//! let rules = Rules::new()
+//!     .rule("odd", |stone| stone % 2 == 1, |stone| vec![stone + 1, stone + 1])
+//!     .rule("even", |_| true, |stone| vec![stone / 2]);
+//! assert_eq!(rules.blink(&[3, 4]), vec![4, 4, 2]);
+//! assert_eq!(rules.totals(&[3, 4], 3), vec![2, 3, 3, 4]);
+//! ```
+use crate::{count_digits, Number};
+
+use std::collections::HashMap;
+
+/// How many stones have each value.
+pub type Counts = HashMap<Number, usize>;
+
+pub struct Rule {
+    pub name: String,
+    applies: Box<dyn Fn(Number) -> bool>,
+    outputs: Box<dyn Fn(Number) -> Vec<Number>>,
+}
+
+pub struct Rules {
+    rules: Vec<Rule>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl Rules {
+    /// No rules, so nothing changes.
+    pub fn new() -> Rules {
+        return Rules { rules: vec![] };
+    }
+
+    /// Add a rule, tried after the ones already added.
+    pub fn rule(
+        mut self,
+        name: &str,
+        applies: impl Fn(Number) -> bool + 'static,
+        outputs: impl Fn(Number) -> Vec<Number> + 'static,
+    ) -> Rules {
+        self.rules.push(Rule {
+            name: name.to_string(),
+            applies: Box::new(applies),
+            outputs: Box::new(outputs),
+        });
+        return self;
+    }
+
+    /// The puzzle's rules: 0 becomes 1, an even number of digits splits in
+    /// half, and anything else is multiplied by 2024.
+    pub fn puzzle() -> Rules {
+        return Rules::new()
+            .rule("zero", |stone| stone == 0, |_| vec![1])
+            .rule(
+                "split",
+                |stone| count_digits(stone).is_multiple_of(2),
+                |stone| {
+                    // 1000 has 4 digits, so split by 10^2 into 10 and 0
+                    let splitter = (10 as Number).pow(count_digits(stone) / 2);
+                    vec![stone / splitter, stone % splitter]
+                },
+            )
+            .rule("multiply", |_| true, |stone| vec![stone * 2024]);
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        return &self.rules;
+    }
+
+    /// The rule `stone` follows, if any.
+    pub fn matching(&self, stone: Number) -> Option<&Rule> {
+        return self.rules.iter().find(|rule| (rule.applies)(stone));
+    }
+
+    /// What `stone` turns into after one blink.
+    pub fn apply(&self, stone: Number) -> Vec<Number> {
+        return match self.matching(stone) {
+            Some(rule) => (rule.outputs)(stone),
+            None => vec![stone],
+        };
+    }
+
+    /// One blink of the whole row, keeping the order.
+    pub fn blink(&self, stones: &[Number]) -> Vec<Number> {
+        return stones.iter().flat_map(|&stone| self.apply(stone)).collect();
+    }
+
+    /// One blink of stones counted by value.
+    pub fn evolve(&self, counts: &Counts) -> Counts {
+        let mut result = Counts::new();
+        for (&stone, &count) in counts {
+            for next in self.apply(stone) {
+                *result.entry(next).or_insert(0) += count;
+            }
+        }
+        return result;
+    }
+
+    /// The counts before any blinks, then after each one, forever.
+    pub fn generations(&self, stones: &[Number]) -> impl Iterator<Item = Counts> + '_ {
+        let mut start = Counts::new();
+        for &stone in stones {
+            *start.entry(stone).or_insert(0) += 1;
+        }
+        return std::iter::successors(Some(start), move |counts| Some(self.evolve(counts)));
+    }
+
+    /// How many stones of each value there are after `blinks`.
+    /// ```
+    /// let counts = day11::rules::Rules::puzzle().counts(&[125, 17], 6);
+    /// assert_eq!(counts[&2], 4);
+    /// assert_eq!(counts.values().sum::<usize>(), 22);
+    /// ```
+    pub fn counts(&self, stones: &[Number], blinks: usize) -> Counts {
+        return self.generations(stones).nth(blinks).unwrap();
+    }
+
+    /// How many stones there are before any blinks and after each of `blinks`.
+    pub fn totals(&self, stones: &[Number], blinks: usize) -> Vec<usize> {
+        return self
+            .generations(stones)
+            .take(blinks + 1)
+            .map(|counts| counts.values().sum())
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_blink() {
+        let rules = Rules::puzzle();
+        assert_eq!(
+            rules.blink(&[0, 1, 10, 99, 999]),
+            vec![1, 2024, 1, 0, 9, 9, 2021976]
+        );
+        assert_eq!(rules.matching(1000).unwrap().name, "split");
+    }
+
+    #[test]
+    fn test_totals() {
+        let rules = Rules::puzzle();
+        assert_eq!(rules.totals(&[125, 17], 6), vec![2, 3, 4, 5, 9, 13, 22]);
+        assert_eq!(*rules.totals(&[125, 17], 25).last().unwrap(), 55312);
+    }
+
+    #[test]
+    fn test_unmatched_stones_stay() {
+        let rules = Rules::new().rule("double", |stone| stone < 10, |stone| vec![stone * 2]);
+        assert_eq!(rules.blink(&[3, 12]), vec![6, 12]);
+        assert_eq!(rules.counts(&[3, 12], 3), Counts::from([(12, 2)]));
+        assert_eq!(Rules::new().blink(&[5]), vec![5]);
+    }
+}
@@ -3,19 +3,28 @@ extern crate filelib;
 use std::collections::{HashSet, VecDeque};
 
 pub use filelib::load_no_blanks;
+use filelib::{grid_size, ParseError};
 use gridlib::{Direction, Grid, GridCoordinate, GridTraversable};
-use log::info;
+use tracelib::{debug, trace};
 
-fn parse(string_list: &Vec<String>) -> Grid<char> {
+fn parse(string_list: &Vec<String>) -> Result<Grid<char>, ParseError> {
     let mut values = vec![];
-    let height = string_list.len();
-    let width = string_list.first().unwrap().len();
+    let (width, height) = grid_size(string_list)?;
     for line in string_list {
         for c in line.chars() {
             values.push(c);
         }
     }
-    return Grid::new(width, height, values);
+    return Ok(Grid::new(width, height, values));
+}
+
+/// Any letter can be a plant, so this only checks the garden is a rectangle.
+/// ```
+/// assert!(day12::check_input(&vec!["AAB".to_string(), "ACC".to_string()]).is_ok());
+/// assert!(day12::check_input(&vec!["AAB".to_string(), "AC".to_string()]).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return parse(string_list).map(|_| ());
 }
 
 type Cache = HashSet<GridCoordinate>;
@@ -23,7 +32,7 @@ type Cache = HashSet<GridCoordinate>;
 // We can flood fill to calculate the area and permieter at the same time
 // Flood fill is basically a BFS.
 fn flood_fill(grid: &Grid<char>, visited: &mut Cache, coord: GridCoordinate) -> (usize, usize) {
-    info!("Flood filling from {:?}", coord);
+    trace!("Flood filling from {:?}", coord);
     let mut perimeter = 0;
     let mut area = 0;
     let mut queue = VecDeque::new();
@@ -48,22 +57,14 @@ fn flood_fill(grid: &Grid<char>, visited: &mut Cache, coord: GridCoordinate) ->
             }
         }
     }
-    info!("Solution: area {}, perimeter {}", area, perimeter);
+    debug!("Solution: area {}, perimeter {}", area, perimeter);
     return (area, perimeter);
 }
 
 /// Find price of fencing area, based on area * perimeter sum
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "AAAA",
-///     "BBCD",
-///     "BBCC",
-///     "EEEC"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day12::puzzle_a(&vec1), 140);
-/// ```
+#[doc = filelib::fixture_doc!("day12-1", a, |fixture| day12::puzzle_a(&fixture.lines()))]
 pub fn puzzle_a(string_list: &Vec<String>) -> usize {
-    let grid = parse(string_list);
+    let grid = parse(string_list).unwrap();
     let mut visited = Cache::new();
     let mut total_price = 0;
     for coord in grid.coord_iter() {
@@ -83,7 +84,7 @@ fn flood_fill_sides(
     visited: &mut Cache,
     coord: GridCoordinate,
 ) -> (usize, usize) {
-    info!("Flood filling from {:?}", coord);
+    trace!("Flood filling from {:?}", coord);
     let mut sides: HashSet<(Direction, i64, i64)> = HashSet::new();
     let mut area = 0;
     let mut queue = VecDeque::new();
@@ -208,22 +209,14 @@ fn flood_fill_sides(
         };
     }
 
-    info!("Solution: area {}, sides {:?}", area, sides);
+    debug!("Solution: area {}, sides {:?}", area, sides);
     return (area, sides.len());
 }
 
 /// Find price of fencing area, based on area * number of sides sum
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "AAAA",
-///     "BBCD",
-///     "BBCC",
-///     "EEEC"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day12::puzzle_b(&vec1), 80);
-/// ```
+#[doc = filelib::fixture_doc!("day12-1", b, |fixture| day12::puzzle_b(&fixture.lines()))]
 pub fn puzzle_b(string_list: &Vec<String>) -> usize {
-    let grid = parse(string_list);
+    let grid = parse(string_list).unwrap();
     let mut visited = Cache::new();
     let mut total_price = 0;
     for coord in grid.coord_iter() {
@@ -246,7 +239,7 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, perimeter) = flood_fill(&grid, &mut visited, GridCoordinate::new(0, 0));
         assert_eq!(area, 4);
@@ -259,7 +252,7 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, perimeter) = flood_fill(&grid, &mut visited, GridCoordinate::new(0, 1));
         assert_eq!(area, 4);
@@ -272,7 +265,7 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, perimeter) = flood_fill(&grid, &mut visited, GridCoordinate::new(2, 1));
         assert_eq!(area, 4);
@@ -285,7 +278,7 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, perimeter) = flood_fill(&grid, &mut visited, GridCoordinate::new(3, 1));
         assert_eq!(area, 1);
@@ -298,94 +291,31 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, perimeter) = flood_fill(&grid, &mut visited, GridCoordinate::new(0, 3));
         assert_eq!(area, 3);
         assert_eq!(perimeter, 8);
     }
 
-    #[test]
-    fn test_example_2() {
-        let input = vec!["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        let result = puzzle_a(&input);
-        assert_eq!(result, 772);
-    }
-
-    #[test]
-    fn test_example_3() {
-        let input = vec![
-            "RRRRIICCFF",
-            "RRRRIICCCF",
-            "VVRRRCCFFF",
-            "VVRCCCJFFF",
-            "VVVVCJJCFE",
-            "VVIVCCJJEE",
-            "VVIIICJJEE",
-            "MIIIIIJJEE",
-            "MIIISIJEEE",
-            "MMMISSJEEE",
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-        let result = puzzle_a(&input);
-        assert_eq!(result, 1930);
-    }
-
-    #[test]
-    fn test_example_2_b() {
-        let input = vec!["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        let result = puzzle_b(&input);
-        assert_eq!(result, 436);
-    }
-
-    #[test]
-    fn test_example_3_b() {
-        let input = vec!["EEEEE", "EXXXX", "EEEEE", "EXXXX", "EEEEE"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        let result = puzzle_b(&input);
-        assert_eq!(result, 236);
-    }
-
-    #[test]
-    fn test_example_4_b() {
-        let input = vec!["AAAAAA", "AAABBA", "AAABBA", "ABBAAA", "ABBAAA", "AAAAAA"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        let result = puzzle_b(&input);
-        assert_eq!(result, 368);
-    }
-
-    #[test]
-    fn test_example_5b() {
-        let input = vec![
-            "RRRRIICCFF",
-            "RRRRIICCCF",
-            "VVRRRCCFFF",
-            "VVRCCCJFFF",
-            "VVVVCJJCFE",
-            "VVIVCCJJEE",
-            "VVIIICJJEE",
-            "MIIIIIJJEE",
-            "MIIISIJEEE",
-            "MMMISSJEEE",
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-        let result = puzzle_b(&input);
-        assert_eq!(result, 1206);
-    }
+    filelib::fixture_test!(test_example_2, "day12-2", a, |fixture| puzzle_a(
+        &fixture.lines()
+    ));
+    filelib::fixture_test!(test_example_3, "day12-3", a, |fixture| puzzle_a(
+        &fixture.lines()
+    ));
+    filelib::fixture_test!(test_example_2_b, "day12-2", b, |fixture| puzzle_b(
+        &fixture.lines()
+    ));
+    filelib::fixture_test!(test_example_3_b, "day12-3", b, |fixture| puzzle_b(
+        &fixture.lines()
+    ));
+    filelib::fixture_test!(test_example_4_b, "day12-4", b, |fixture| puzzle_b(
+        &fixture.lines()
+    ));
+    filelib::fixture_test!(test_example_5_b, "day12-5", b, |fixture| puzzle_b(
+        &fixture.lines()
+    ));
 
     #[test]
     fn test_example_1_region_a_partb() {
@@ -393,7 +323,7 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, sides) = flood_fill_sides(&grid, &mut visited, GridCoordinate::new(0, 0));
         assert_eq!(area, 4);
@@ -406,7 +336,7 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, sides) = flood_fill_sides(&grid, &mut visited, GridCoordinate::new(0, 1));
         assert_eq!(area, 4);
@@ -419,7 +349,7 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, sides) = flood_fill_sides(&grid, &mut visited, GridCoordinate::new(2, 1));
         assert_eq!(area, 4);
@@ -432,7 +362,7 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, sides) = flood_fill_sides(&grid, &mut visited, GridCoordinate::new(3, 1));
         assert_eq!(area, 1);
@@ -445,7 +375,7 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, sides) = flood_fill_sides(&grid, &mut visited, GridCoordinate::new(0, 3));
         assert_eq!(area, 3);
@@ -458,7 +388,7 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, sides) = flood_fill_sides(&grid, &mut visited, GridCoordinate::new(0, 0));
         assert_eq!(area, 17);
@@ -487,7 +417,7 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, sides) = flood_fill_sides(&grid, &mut visited, GridCoordinate::new(1, 1));
         assert_eq!(area, 4);
@@ -500,7 +430,7 @@ mod tests {
             .iter()
             .map(|s| s.to_string())
             .collect();
-        let grid = parse(&input);
+        let grid = parse(&input).unwrap();
         let mut visited = Cache::new();
         let (area, sides) = flood_fill_sides(&grid, &mut visited, GridCoordinate::new(1, 1));
         assert_eq!(area, 4);
@@ -1,29 +1,41 @@
 extern crate filelib;
 
 pub use filelib::load_no_blanks;
+use filelib::{parse_number, split_pair, ParseError};
 use gridlib::GridCoordinateInf;
-use log::info;
 use mathlib::modulusi64;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use tracelib::trace;
 
 type Number = i64;
 type Coord = GridCoordinateInf<Number>;
 
-fn parse_robots(string_list: &Vec<String>) -> Vec<(Coord, Coord)> {
+fn parse_robots(string_list: &Vec<String>) -> Result<Vec<(Coord, Coord)>, ParseError> {
     let mut robots = vec![];
     for line in string_list {
-        let (p_rest, v_commas) = line.split_once(" v=").unwrap();
-        let (_, p_commas) = p_rest.split_once("p=").unwrap();
-        let (p_x_s, p_y_s) = p_commas.split_once(",").unwrap();
-        let (v_x_s, v_y_s) = v_commas.split_once(",").unwrap();
-        let p_x = p_x_s.parse().unwrap();
-        let p_y = p_y_s.parse().unwrap();
-        let v_x = v_x_s.parse().unwrap();
-        let v_y = v_y_s.parse().unwrap();
+        let (p_rest, v_commas) = split_pair(line, " v=")?;
+        let (_, p_commas) = split_pair(p_rest, "p=")?;
+        let (p_x_s, p_y_s) = split_pair(p_commas, ",")?;
+        let (v_x_s, v_y_s) = split_pair(v_commas, ",")?;
+        let p_x = parse_number(p_x_s)?;
+        let p_y = parse_number(p_y_s)?;
+        let v_x = parse_number(v_x_s)?;
+        let v_y = parse_number(v_y_s)?;
         let pos = Coord::new(p_x, p_y);
         let vel = Coord::new(v_x, v_y);
         robots.push((pos, vel));
     }
-    return robots;
+    return Ok(robots);
+}
+
+/// Check every robot has a position and velocity.
+/// ```
+/// assert!(day14::check_input(&vec!["p=0,4 v=3,-3".to_string()]).is_ok());
+/// assert!(day14::check_input(&vec!["p=0,4".to_string()]).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return parse_robots(string_list).map(|_| ());
 }
 
 fn find_pos(pos: Coord, vec: Coord, width: usize, height: usize, seconds_elapsed: Number) -> Coord {
@@ -33,7 +45,7 @@ fn find_pos(pos: Coord, vec: Coord, width: usize, height: usize, seconds_elapsed
     let y = modulusi64(pos.y + seconds_elapsed * vec.y, height as Number);
 
     let new_pos = Coord::new(x, y);
-    info!("pos {} -> {}", pos, new_pos);
+    trace!("pos {} -> {}", pos, new_pos);
     return new_pos;
 }
 
@@ -76,26 +88,10 @@ fn sort_to_quadrants(robots: Vec<Coord>, width: usize, height: usize) -> Vec<usi
 }
 
 /// Calculate the safety factor
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "p=0,4 v=3,-3",
-///     "p=6,3 v=-1,-3",
-///     "p=10,3 v=-1,2",
-///     "p=2,0 v=2,-1",
-///     "p=0,0 v=1,3",
-///     "p=3,0 v=-2,-2",
-///     "p=7,6 v=-1,-3",
-///     "p=3,0 v=-1,-2",
-///     "p=9,3 v=2,3",
-///     "p=7,3 v=-1,2",
-///     "p=2,4 v=2,-3",
-///     "p=9,5 v=-3,-3"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day14::puzzle_a(&vec1, 7, 11), 12);
-/// ```
+#[doc = filelib::fixture_doc!("day14-1", a, |fixture| day14::puzzle_a(&fixture.lines(), 7, 11))]
 pub fn puzzle_a(string_list: &Vec<String>, height: usize, width: usize) -> usize {
     let seconds_elapsed = 100;
-    let values = parse_robots(string_list);
+    let values = parse_robots(string_list).unwrap();
     let final_locations = values
         .into_iter()
         .map(|(pos, vec)| find_pos(pos, vec, width, height, seconds_elapsed))
@@ -106,45 +102,31 @@ pub fn puzzle_a(string_list: &Vec<String>, height: usize, width: usize) -> usize
 }
 
 /// Find an iteration that looks like a Christmas tree??? How do I define that???
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "p=0,4 v=3,-3",
-///     "p=6,3 v=-1,-3",
-///     "p=10,3 v=-1,2",
-///     "p=2,0 v=2,-1",
-///     "p=0,0 v=1,3",
-///     "p=3,0 v=-2,-2",
-///     "p=7,6 v=-1,-3",
-///     "p=3,0 v=-1,-2",
-///     "p=9,3 v=2,3",
-///     "p=7,3 v=-1,2",
-///     "p=2,4 v=2,-3",
-///     "p=9,5 v=-3,-3"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day14::puzzle_b(&vec1, 7, 11), 5);
-/// ```
+#[doc = filelib::fixture_doc!("day14-1", b, |fixture| day14::puzzle_b(&fixture.lines(), 7, 11))]
 pub fn puzzle_b(string_list: &Vec<String>, height: usize, width: usize) -> usize {
-    let values = parse_robots(string_list);
-
-    let mut min_value = usize::MAX;
-    let mut min_i: usize = 0;
+    let values = parse_robots(string_list).unwrap();
 
     // Okay lets think about this
     // 103 * 101 = 10403 is the max number of possible spaces, things will definitely loop by then, so thats an upper bound.
-    for i in 1..=(width * height) {
-        let final_locations = values
-            .clone()
-            .into_iter()
-            .map(|(pos, vec)| find_pos(pos, vec, width, height, i as Number))
-            .collect();
-        let value = sort_to_quadrants(final_locations, width, height)
-            .into_iter()
-            .product();
-
-        if value < min_value {
-            min_value = value;
-            min_i = i;
-        }
-    }
+    #[cfg(feature = "parallel")]
+    let seconds = (1..=(width * height)).into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let seconds = 1..=(width * height);
+
+    // Ties go to the earliest second, so compare (value, second) pairs
+    let (_, min_i) = seconds
+        .map(|i| {
+            let final_locations = values
+                .clone()
+                .into_iter()
+                .map(|(pos, vec)| find_pos(pos, vec, width, height, i as Number))
+                .collect();
+            let value: usize = sort_to_quadrants(final_locations, width, height)
+                .into_iter()
+                .product();
+            return (value, i);
+        })
+        .min()
+        .unwrap();
     return min_i;
 }
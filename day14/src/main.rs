@@ -3,15 +3,16 @@ use day14::puzzle_a;
 use day14::puzzle_b;
 
 fn main() {
-    colog::init();
+    tracelib::init();
+    let _day = tracelib::day_span(env!("CARGO_PKG_NAME")).entered();
     let filename = "input";
-    let lines = load_no_blanks(filename);
+    let lines = tracelib::parse_span().in_scope(|| load_no_blanks(filename));
 
     let height = 103;
     let width = 101;
-    let value = puzzle_a(&lines, height, width);
+    let value = tracelib::part_span("a").in_scope(|| puzzle_a(&lines, height, width));
     println!("Answer to 1st question: {}", value);
 
-    let value_b = puzzle_b(&lines, height, width);
+    let value_b = tracelib::part_span("b").in_scope(|| puzzle_b(&lines, height, width));
     println!("Answer to 2nd question: {}", value_b);
 }
@@ -2,9 +2,10 @@ extern crate filelib;
 
 use std::collections::{HashSet, VecDeque};
 
+use filelib::{grid_size, ParseError};
 pub use filelib::{load, split_lines_by_blanks};
 use gridlib::{Direction, Grid, GridCoordinate, GridTraversable};
-use log::info;
+use tracelib::{enabled, trace, Level};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum Terrain {
@@ -15,12 +16,33 @@ enum Terrain {
 type Map = Grid<Terrain>;
 type Coord = GridCoordinate;
 
+/// The robot steps without bounds checks, so the warehouse must be walled
+/// in, and hold exactly one robot.
+fn check_warehouse(strings: &Vec<String>) -> Result<(usize, usize), ParseError> {
+    let (width, height) = grid_size(strings)?;
+    let robots: usize = strings.iter().map(|line| line.matches('@').count()).sum();
+    if robots != 1 {
+        return Err(ParseError::new(&format!(
+            "expected one robot, found {}",
+            robots
+        )));
+    }
+    for (y, line) in strings.iter().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            let edge = x == 0 || y == 0 || x == width - 1 || y == height - 1;
+            if edge && c != '#' {
+                return Err(ParseError::new("warehouse is not surrounded by walls"));
+            }
+        }
+    }
+    return Ok((width, height));
+}
+
 // We need to get: Map, Robot location (@), Box (O)
-fn parse_warehouse(strings: &Vec<String>) -> (Map, Coord, Vec<Coord>) {
+fn parse_warehouse(strings: &Vec<String>) -> Result<(Map, Coord, Vec<Coord>), ParseError> {
     let mut robot_coord: Coord = Coord::new(0, 0);
     let mut values = vec![];
-    let height = strings.len();
-    let width = strings.first().unwrap().len();
+    let (width, height) = check_warehouse(strings)?;
     let mut boxes = vec![];
 
     for (y, line) in strings.into_iter().enumerate() {
@@ -40,16 +62,21 @@ fn parse_warehouse(strings: &Vec<String>) -> (Map, Coord, Vec<Coord>) {
                     values.push(Terrain::Empty);
                     boxes.push(Coord::new(x, y));
                 }
-                _ => panic!("Unknown character - Terrain {}", c),
+                _ => {
+                    return Err(ParseError::new(&format!(
+                        "Unknown character - Terrain {:?}",
+                        c
+                    )))
+                }
             }
         }
     }
 
     let grid = Map::new(width, height, values);
-    return (grid, robot_coord, boxes);
+    return Ok((grid, robot_coord, boxes));
 }
 
-fn parse_moves(strings: &Vec<String>) -> Vec<Direction> {
+fn parse_moves(strings: &Vec<String>) -> Result<Vec<Direction>, ParseError> {
     let mut results = vec![];
     for line in strings {
         for c in line.chars() {
@@ -59,11 +86,38 @@ fn parse_moves(strings: &Vec<String>) -> Vec<Direction> {
                 'V' => Direction::SOUTH,
                 'v' => Direction::SOUTH,
                 '>' => Direction::EAST,
-                _ => panic!("Unknown character - Direction {}", c),
+                _ => {
+                    return Err(ParseError::new(&format!(
+                        "Unknown character - Direction {:?}",
+                        c
+                    )))
+                }
             });
         }
     }
-    return results;
+    return Ok(results);
+}
+
+/// Check the warehouse and the robot's moves, for both widths of warehouse.
+/// ```
+/// let warehouse = vec!["####".to_string(), "#@O#".to_string(), "####".to_string()];
+/// let moves = vec!["<^>v".to_string()];
+/// assert!(day15::check_input(&vec![warehouse.clone(), moves.clone()]).is_ok());
+/// assert!(day15::check_input(&vec![warehouse.clone()]).is_err());
+/// let open = vec!["@O".to_string()];
+/// assert!(day15::check_input(&vec![open, moves]).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<Vec<String>>) -> Result<(), ParseError> {
+    if string_list.len() != 2 {
+        return Err(ParseError::new(&format!(
+            "expected a warehouse and moves, got {} sections",
+            string_list.len()
+        )));
+    }
+    parse_warehouse(&string_list[0])?;
+    parse_double_warehouse(&string_list[0])?;
+    parse_moves(&string_list[1])?;
+    return Ok(());
 }
 
 fn step(map: &Map, robot: Coord, boxes: &Vec<Coord>, direction: Direction) -> (Coord, Vec<Coord>) {
@@ -71,7 +125,7 @@ fn step(map: &Map, robot: Coord, boxes: &Vec<Coord>, direction: Direction) -> (C
     let mut new_boxes = vec![];
 
     let potential_new_coord = map.get_coordinate_by_direction(robot, direction).unwrap();
-    info!("Checking if can go to {}", potential_new_coord);
+    trace!("Checking if can go to {}", potential_new_coord);
     let potential_value = map.get_value(potential_new_coord).unwrap();
     match potential_value {
         Terrain::Wall => {
@@ -120,13 +174,13 @@ fn step(map: &Map, robot: Coord, boxes: &Vec<Coord>, direction: Direction) -> (C
                     }
                     new_robot = potential_new_coord;
                 } else {
-                    info!("Invalid move case");
+                    trace!("Invalid move case");
                     // Invalid move
                     new_boxes = boxes.clone();
                     new_robot = robot.clone();
                 }
             } else {
-                info!("Just move");
+                trace!("Just move");
                 // No box, just move
                 new_boxes = boxes.clone();
                 new_robot = potential_new_coord;
@@ -134,41 +188,15 @@ fn step(map: &Map, robot: Coord, boxes: &Vec<Coord>, direction: Direction) -> (C
         }
     }
 
-    info!("Robot is now at: {}", new_robot);
+    trace!("Robot is now at: {}", new_robot);
     return (new_robot, new_boxes);
 }
 
 /// Find all boxes GPS after move
-/// ```
-/// let vec1: Vec<Vec<String>> = vec![vec![
-///     "##########",
-///     "#..O..O.O#",
-///     "#......O.#",
-///     "#.OO..O.O#",
-///     "#..O@..O.#",
-///     "#O#..O...#",
-///     "#O..O..O.#",
-///     "#.OO.O.OO#",
-///     "#....O...#",
-///     "##########"
-/// ].iter().map(|s| s.to_string()).collect(),
-/// vec![
-///     "<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^",
-///     "vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v",
-///     "><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<",
-///     "<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^",
-///     "^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><",
-///     "^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^",
-///     ">^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^",
-///     "<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>",
-///     "^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>",
-///     "v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^",
-/// ].iter().map(|s| s.to_string()).collect()];
-/// assert_eq!(day15::puzzle_a(&vec1), 10092);
-/// ```
+#[doc = filelib::fixture_doc!("day15-1", a, |fixture| day15::puzzle_a(&fixture.groups()))]
 pub fn puzzle_a(string_list: &Vec<Vec<String>>) -> usize {
-    let (warehouse, mut robot, mut boxes) = parse_warehouse(string_list.first().unwrap());
-    let directions = parse_moves(string_list.last().unwrap());
+    let (warehouse, mut robot, mut boxes) = parse_warehouse(string_list.first().unwrap()).unwrap();
+    let directions = parse_moves(string_list.last().unwrap()).unwrap();
     for dir in directions {
         (robot, boxes) = step(&warehouse, robot, &boxes, dir);
     }
@@ -176,11 +204,13 @@ pub fn puzzle_a(string_list: &Vec<Vec<String>>) -> usize {
 }
 
 // We need to get: Map, Robot location (@), Box (O)
-fn parse_double_warehouse(strings: &Vec<String>) -> (Map, Coord, Vec<(Coord, Coord)>) {
+fn parse_double_warehouse(
+    strings: &Vec<String>,
+) -> Result<(Map, Coord, Vec<(Coord, Coord)>), ParseError> {
     let mut robot_coord: Coord = Coord::new(0, 0);
     let mut values = vec![];
-    let height = strings.len();
-    let width = strings.first().unwrap().len() * 2;
+    let (width, height) = check_warehouse(strings)?;
+    let width = width * 2;
     let mut boxes = vec![];
 
     for (y, line) in strings.into_iter().enumerate() {
@@ -204,13 +234,18 @@ fn parse_double_warehouse(strings: &Vec<String>) -> (Map, Coord, Vec<(Coord, Coo
                     values.push(Terrain::Empty);
                     boxes.push((Coord::new(2 * x, y), Coord::new(2 * x + 1, y)));
                 }
-                _ => panic!("Unknown character - Terrain {}", c),
+                _ => {
+                    return Err(ParseError::new(&format!(
+                        "Unknown character - Terrain {:?}",
+                        c
+                    )))
+                }
             }
         }
     }
 
     let grid = Map::new(width, height, values);
-    return (grid, robot_coord, boxes);
+    return Ok((grid, robot_coord, boxes));
 }
 
 fn step_double(
@@ -223,7 +258,7 @@ fn step_double(
     let mut new_boxes = vec![];
 
     let potential_new_coord = map.get_coordinate_by_direction(robot, direction).unwrap();
-    info!("Checking if can go to {}", potential_new_coord);
+    trace!("Checking if can go to {}", potential_new_coord);
     let potential_value = map.get_value(potential_new_coord).unwrap();
     match potential_value {
         Terrain::Wall => {
@@ -307,13 +342,13 @@ fn step_double(
                     }
                     new_robot = potential_new_coord;
                 } else {
-                    info!("Invalid move case");
+                    trace!("Invalid move case");
                     // Invalid move
                     new_boxes = boxes.clone();
                     new_robot = robot.clone();
                 }
             } else {
-                info!("Just move");
+                trace!("Just move");
                 // No box, just move
                 new_boxes = boxes.clone();
                 new_robot = potential_new_coord;
@@ -321,41 +356,16 @@ fn step_double(
         }
     }
 
-    info!("Robot is now at: {}", new_robot);
+    trace!("Robot is now at: {}", new_robot);
     return (new_robot, new_boxes);
 }
 
 /// Find all GPS in double space move
-/// ```
-/// let vec1: Vec<Vec<String>> = vec![vec![
-///     "##########",
-///     "#..O..O.O#",
-///     "#......O.#",
-///     "#.OO..O.O#",
-///     "#..O@..O.#",
-///     "#O#..O...#",
-///     "#O..O..O.#",
-///     "#.OO.O.OO#",
-///     "#....O...#",
-///     "##########"
-/// ].iter().map(|s| s.to_string()).collect(),
-/// vec![
-///     "<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^",
-///     "vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v",
-///     "><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<",
-///     "<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^",
-///     "^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><",
-///     "^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^",
-///     ">^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^",
-///     "<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>",
-///     "^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>",
-///     "v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^",
-/// ].iter().map(|s| s.to_string()).collect()];
-/// assert_eq!(day15::puzzle_b(&vec1), 9021);
-/// ```
+#[doc = filelib::fixture_doc!("day15-1", b, |fixture| day15::puzzle_b(&fixture.groups()))]
 pub fn puzzle_b(string_list: &Vec<Vec<String>>) -> usize {
-    let (warehouse, mut robot, mut boxes) = parse_double_warehouse(string_list.first().unwrap());
-    let directions = parse_moves(string_list.last().unwrap());
+    let (warehouse, mut robot, mut boxes) =
+        parse_double_warehouse(string_list.first().unwrap()).unwrap();
+    let directions = parse_moves(string_list.last().unwrap()).unwrap();
     print_map(&warehouse, &robot, &boxes);
     for dir in directions {
         (robot, boxes) = step_double(&warehouse, robot, &boxes, dir);
@@ -364,7 +374,11 @@ pub fn puzzle_b(string_list: &Vec<Vec<String>>) -> usize {
     return boxes.iter().map(|(left, _)| left.x + left.y * 100).sum();
 }
 
+/// Only draws the warehouse when tracing is on, it's called after every move.
 fn print_map(warehouse: &Map, robot: &Coord, boxes: &Vec<(Coord, Coord)>) {
+    if !enabled!(Level::TRACE) {
+        return;
+    }
     let mut values = vec!['.'; warehouse.get_height() * warehouse.get_width()];
     let width = warehouse.get_width();
     values[robot.x + robot.y * width] = '@';
@@ -385,11 +399,8 @@ fn print_map(warehouse: &Map, robot: &Coord, boxes: &Vec<(Coord, Coord)>) {
         }
         lines.last_mut().unwrap().push(c);
     }
-    for line in lines {
-        let str: String = line.into_iter().collect();
-        info!("{}", str);
-    }
-    info!("----");
+    let lines: Vec<String> = lines.into_iter().map(|l| l.into_iter().collect()).collect();
+    trace!("\n{}", lines.join("\n"));
 }
 
 #[cfg(test)]
@@ -405,12 +416,12 @@ mod tests {
         .into_iter()
         .map(|s| s.to_string())
         .collect();
-        let (warehouse, mut robot, mut boxes) = parse_warehouse(&input);
+        let (warehouse, mut robot, mut boxes) = parse_warehouse(&input).unwrap();
         let input = vec!["<^^>>>vv<v>>v<<"]
             .into_iter()
             .map(|s| s.to_string())
             .collect();
-        let directions = parse_moves(&input);
+        let directions = parse_moves(&input).unwrap();
         println!("Robot {}", robot);
         for dir in directions {
             (robot, boxes) = step(&warehouse, robot, &boxes, dir);
@@ -428,12 +439,12 @@ mod tests {
         .into_iter()
         .map(|s| s.to_string())
         .collect();
-        let (warehouse, mut robot, mut boxes) = parse_double_warehouse(&input);
+        let (warehouse, mut robot, mut boxes) = parse_double_warehouse(&input).unwrap();
         let input = vec!["<vv<<^^<<^^"]
             .into_iter()
             .map(|s| s.to_string())
             .collect();
-        let directions = parse_moves(&input);
+        let directions = parse_moves(&input).unwrap();
         println!("{:?}, {:?}", robot, boxes);
         for dir in directions {
             println!("{}", dir);
@@ -467,9 +478,9 @@ mod tests {
             .into_iter()
             .map(|s| s.to_string())
             .collect();
-        let (warehouse, mut robot, mut boxes) = parse_double_warehouse(&input);
+        let (warehouse, mut robot, mut boxes) = parse_double_warehouse(&input).unwrap();
         let input = vec!["<"].into_iter().map(|s| s.to_string()).collect();
-        let directions = parse_moves(&input);
+        let directions = parse_moves(&input).unwrap();
         for dir in directions {
             (robot, boxes) = step_double(&warehouse, robot, &boxes, dir);
         }
@@ -7,10 +7,11 @@ use std::collections::HashSet;
 use std::u32;
 
 pub use filelib::load_no_blanks;
+use filelib::{grid_size, ParseError};
 use gridlib::GridPrintable;
 use gridlib::SimpleGridOverlay;
 use gridlib::{Direction, Grid, GridCoordinate, GridTraversable};
-use log::info;
+use tracelib::{debug, enabled, trace, Level};
 
 type Map = Grid<Terrain>;
 type Coord = GridCoordinate;
@@ -59,12 +60,11 @@ impl PartialOrd for QueueState {
 }
 
 // We need to get: Map, start location, end Location
-fn parse_maze(strings: &Vec<String>) -> (Map, Coord, Coord) {
-    let mut start_coord: Coord = Coord::new(0, 0);
-    let mut end_coord: Coord = Coord::new(0, 0);
+fn parse_maze(strings: &Vec<String>) -> Result<(Map, Coord, Coord), ParseError> {
+    let mut start_coord: Option<Coord> = None;
+    let mut end_coord: Option<Coord> = None;
     let mut values = vec![];
-    let height = strings.len();
-    let width = strings.first().unwrap().len();
+    let (width, height) = grid_size(strings)?;
 
     for (y, line) in strings.into_iter().enumerate() {
         for (x, c) in line.chars().enumerate() {
@@ -75,21 +75,39 @@ fn parse_maze(strings: &Vec<String>) -> (Map, Coord, Coord) {
                 '.' => {
                     values.push(Terrain::Empty);
                 }
-                'S' => {
-                    start_coord = Coord::new(x, y);
+                'S' if start_coord.is_none() => {
+                    start_coord = Some(Coord::new(x, y));
                     values.push(Terrain::Empty);
                 }
-                'E' => {
+                'E' if end_coord.is_none() => {
                     values.push(Terrain::Empty);
-                    end_coord = Coord::new(x, y);
+                    end_coord = Some(Coord::new(x, y));
+                }
+                _ => {
+                    return Err(ParseError::new(&format!(
+                        "Unexpected character - Terrain {:?}",
+                        c
+                    )))
                 }
-                _ => panic!("Unknown character - Terrain {}", c),
             }
         }
     }
 
+    let (Some(start_coord), Some(end_coord)) = (start_coord, end_coord) else {
+        return Err(ParseError::new("maze needs a start and an end"));
+    };
     let grid = Map::new(width, height, values);
-    return (grid, start_coord, end_coord);
+    return Ok((grid, start_coord, end_coord));
+}
+
+/// Check the maze has one start and one end.
+/// ```
+/// assert!(day16::check_input(&vec!["#####".to_string(), "#S.E#".to_string()]).is_ok());
+/// assert!(day16::check_input(&vec!["#S.S#".to_string()]).is_err());
+/// assert!(day16::check_input(&vec!["#S..#".to_string()]).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return parse_maze(string_list).map(|_| ());
 }
 
 fn dijkstra_min_path(grid: Map, start: Coord, end: Coord) -> (usize, u32) {
@@ -132,14 +150,12 @@ fn dijkstra_min_path(grid: Map, start: Coord, end: Coord) -> (usize, u32) {
                 cur_path.iter().map(|(coord, _)| *coord).collect();
             best_routes.extend(grid_coords);
             final_path = cur_path.clone();
-            info!("Possible end path found!");
-            let print_map = cur_path.into_iter().map(|(c, _)| c).collect();
-            print_best_steps(&grid, &print_map);
+            trace!("Possible end path found!");
+            print_best_steps(&grid, cur_path.iter().map(|(c, _)| c));
             continue;
         } else if cur_location == end {
-            info!("Throwing out route: {:?}, cost: {:?}", cur_path, cur_cost);
-            let print_map = cur_path.into_iter().map(|(c, _)| c).collect();
-            print_best_steps(&grid, &print_map);
+            trace!("Throwing out route: {:?}, cost: {:?}", cur_path, cur_cost);
+            print_best_steps(&grid, cur_path.iter().map(|(c, _)| c));
             continue;
         }
         if visited.contains(&(cur_location, cur_direction)) && location_cost < cur_cost {
@@ -177,71 +193,32 @@ fn dijkstra_min_path(grid: Map, start: Coord, end: Coord) -> (usize, u32) {
             previous_steps: new_path,
         });
     }
-    info!("Final path: {:?}", final_path);
-    print_best_steps(&grid, &best_routes);
+    debug!("Final path: {:?}", final_path);
+    print_best_steps(&grid, best_routes.iter());
     return (best_routes.len(), best_cost);
 }
 
 /// S -> E in a maze
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "###############",
-///     "#.......#....E#",
-///     "#.#.###.#.###.#",
-///     "#.....#.#...#.#",
-///     "#.###.#####.#.#",
-///     "#.#.#.......#.#",
-///     "#.#.#####.###.#",
-///     "#...........#.#",
-///     "###.#.#####.#.#",
-///     "#...#.....#.#.#",
-///     "#.#.#.###.#.#.#",
-///     "#.....#...#.#.#",
-///     "#.###.#.#.#.#.#",
-///     "#S..#.....#...#",
-///     "###############"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day16::puzzle_a(&vec1), 7036);
-/// ```
+#[doc = filelib::fixture_doc!("day16-1", a, |fixture| day16::puzzle_a(&fixture.lines()))]
 pub fn puzzle_a(string_list: &Vec<String>) -> u32 {
-    let (grid, start_coord, end_coord) = parse_maze(string_list);
+    let (grid, start_coord, end_coord) = parse_maze(string_list).unwrap();
     let (_, cost) = dijkstra_min_path(grid, start_coord, end_coord);
     return cost;
 }
 
-fn print_best_steps(map: &Map, best_route: &HashSet<Coord>) {
-    let overlay = best_route.iter().map(|x| SimpleGridOverlay::new('O', *x));
-    let lines = map.grid_strings_with_overlay(overlay);
-    for line in lines {
-        info!("{}", line);
+/// Only draws the map when tracing is on, it's called for every path that reaches the end.
+fn print_best_steps<'a>(map: &Map, best_route: impl Iterator<Item = &'a Coord>) {
+    if !enabled!(Level::TRACE) {
+        return;
     }
-    info!("----");
-    info!("");
+    let overlay = best_route.map(|x| SimpleGridOverlay::new('O', *x));
+    trace!("\n{}", map.grid_strings_with_overlay(overlay).join("\n"));
 }
 
 /// As above, but find the number of tiles that are part of the best path through the maze
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "###############",
-///     "#.......#....E#",
-///     "#.#.###.#.###.#",
-///     "#.....#.#...#.#",
-///     "#.###.#####.#.#",
-///     "#.#.#.......#.#",
-///     "#.#.#####.###.#",
-///     "#...........#.#",
-///     "###.#.#####.#.#",
-///     "#...#.....#.#.#",
-///     "#.#.#.###.#.#.#",
-///     "#.....#...#.#.#",
-///     "#.###.#.#.#.#.#",
-///     "#S..#.....#...#",
-///     "###############"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day16::puzzle_b(&vec1), 45);
-/// ```
+#[doc = filelib::fixture_doc!("day16-1", b, |fixture| day16::puzzle_b(&fixture.lines()))]
 pub fn puzzle_b(string_list: &Vec<String>) -> usize {
-    let (grid, start_coord, end_coord) = parse_maze(string_list);
+    let (grid, start_coord, end_coord) = parse_maze(string_list).unwrap();
     let (num_best_paths, _) = dijkstra_min_path(grid, start_coord, end_coord);
     return num_best_paths;
 }
@@ -0,0 +1,377 @@
+//! Programs as something a person can read: pseudo-code with the loops
+//! picked out, and the control flow graph in DOT.
+//! ```
+//! let program = day17::decompile::decompile(&[0, 3, 5, 4, 3, 0]).unwrap();
+//! assert_eq!(
+//!     program.pseudo_code(),
+//!     "do {
+//!     A = A >> 3      //  0: adv 3
+//!     out(A % 8)      //  2: out a
+//! } while A != 0      //  4: jnz 0
+//! "
+//! );
+//! ```
+use crate::Number;
+
+use std::collections::BTreeSet;
+use vmlib::threebit::{Combo, Instruction};
+use vmlib::{disassemble, InstructionSet, VmError};
+
+/// Where a jump or falling off the end of a block goes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Target {
+    /// The block starting at this address
+    Block(usize),
+    /// Past the end of the program, so the machine stops
+    Halt,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Edge {
+    pub to: Target,
+    /// `A != 0` or `A == 0` after a `jnz`, `None` when there's no choice
+    pub when: Option<&'static str>,
+}
+
+/// Instructions that always run together: only entered at the top, and
+/// only left after the last one.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Block {
+    pub start: usize,
+    pub instructions: Vec<(usize, Instruction)>,
+    pub edges: Vec<Edge>,
+}
+
+/// A `jnz` back to `head` from `jump`, shown as a do-while.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Loop {
+    pub head: usize,
+    pub jump: usize,
+}
+
+impl Loop {
+    fn contains(&self, other: &Loop) -> bool {
+        return self.head <= other.head && other.jump <= self.jump;
+    }
+
+    fn overlaps(&self, other: &Loop) -> bool {
+        return self.head <= other.jump && other.head <= self.jump;
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Decompiled {
+    listing: Vec<(usize, Instruction)>,
+    blocks: Vec<Block>,
+    loops: Vec<Loop>,
+}
+
+fn register(r: usize) -> char {
+    return Instruction::REGISTERS[r];
+}
+
+fn combo(op: Combo) -> String {
+    return match op {
+        Combo::Literal(value) => value.to_string(),
+        Combo::Register(r) => register(r).to_string(),
+    };
+}
+
+/// `op % 8`, leaving out the `% 8` for literals, which are all below 4.
+fn low_bits(op: Combo) -> String {
+    return match op {
+        Combo::Literal(value) => value.to_string(),
+        Combo::Register(r) => format!("{} % 8", register(r)),
+    };
+}
+
+/// One instruction as a statement, eg `C = A >> B` for `cdv b`.
+/// ```
+/// let bst = "bst a".parse().unwrap();
+/// assert_eq!(day17::decompile::statement(bst), "B = A % 8");
+/// ```
+pub fn statement(instruction: Instruction) -> String {
+    return match instruction {
+        Instruction::Adv(op) => format!("A = A >> {}", combo(op)),
+        Instruction::Bxl(lit) => format!("B ^= {}", lit),
+        Instruction::Bst(op) => format!("B = {}", low_bits(op)),
+        Instruction::Jnz(lit) => format!("if A != 0 goto {}", lit),
+        Instruction::Bxc(_) => "B ^= C".to_string(),
+        Instruction::Out(op) => format!("out({})", low_bits(op)),
+        Instruction::Bdv(op) => format!("B = A >> {}", combo(op)),
+        Instruction::Cdv(op) => format!("C = A >> {}", combo(op)),
+    };
+}
+
+/// Split a program into blocks and find its loops.
+///
+/// A `jnz` has to land on an instruction, or past the end to halt.
+pub fn decompile(program: &[Number]) -> Result<Decompiled, VmError> {
+    let listing = disassemble::<Instruction>(program)?;
+    let addresses: BTreeSet<usize> = listing.iter().map(|&(ip, _)| ip).collect();
+
+    let mut jumps = vec![];
+    for &(ip, instruction) in &listing {
+        if let Instruction::Jnz(target) = instruction {
+            let target = target as usize;
+            if target < program.len() && !addresses.contains(&target) {
+                return Err(VmError::Decode(
+                    ip,
+                    format!("jumps to {}, the middle of an instruction", target),
+                ));
+            }
+            jumps.push((ip, target));
+        }
+    }
+
+    let mut leaders = BTreeSet::from([0]);
+    for &(ip, target) in &jumps {
+        leaders.insert(target);
+        leaders.insert(ip + 2);
+    }
+    let target = |address: usize| {
+        if address < program.len() {
+            return Target::Block(address);
+        }
+        return Target::Halt;
+    };
+
+    let mut blocks: Vec<Block> = vec![];
+    for &(ip, instruction) in &listing {
+        if leaders.contains(&ip) {
+            blocks.push(Block {
+                start: ip,
+                instructions: vec![],
+                edges: vec![],
+            });
+        }
+        blocks
+            .last_mut()
+            .unwrap()
+            .instructions
+            .push((ip, instruction));
+    }
+    for block in blocks.iter_mut() {
+        let &(ip, last) = block.instructions.last().unwrap();
+        let next = target(ip + 2);
+        block.edges = match last {
+            Instruction::Jnz(to) => vec![
+                Edge {
+                    to: target(to as usize),
+                    when: Some("A != 0"),
+                },
+                Edge {
+                    to: next,
+                    when: Some("A == 0"),
+                },
+            ],
+            _ => vec![Edge {
+                to: next,
+                when: None,
+            }],
+        };
+    }
+
+    // Smallest loops first, then any bigger one that nests with them
+    // cleanly. Jumps back that cross a loop stay as gotos.
+    let mut back: Vec<Loop> = jumps
+        .iter()
+        .filter(|&&(ip, target)| target <= ip)
+        .map(|&(ip, target)| Loop {
+            head: target,
+            jump: ip,
+        })
+        .collect();
+    back.sort_by_key(|l| l.jump - l.head);
+    let mut loops: Vec<Loop> = vec![];
+    for candidate in back {
+        let nests = loops
+            .iter()
+            .all(|l| !l.overlaps(&candidate) || candidate.contains(l));
+        if nests {
+            loops.push(candidate);
+        }
+    }
+    loops.sort_by_key(|l| (l.head, usize::MAX - l.jump));
+
+    return Ok(Decompiled {
+        listing: listing,
+        blocks: blocks,
+        loops: loops,
+    });
+}
+
+const COMMENT_COLUMN: usize = 20;
+
+impl Decompiled {
+    pub fn blocks(&self) -> &[Block] {
+        return &self.blocks;
+    }
+
+    /// In program order, a loop before the ones inside it.
+    pub fn loops(&self) -> &[Loop] {
+        return &self.loops;
+    }
+
+    /// Addresses that a `goto` lands on, which need a label.
+    fn labels(&self) -> BTreeSet<usize> {
+        return self
+            .listing
+            .iter()
+            .filter_map(|&(ip, instruction)| match instruction {
+                Instruction::Jnz(target) if !self.loops.iter().any(|l| l.jump == ip) => {
+                    Some(target as usize)
+                }
+                _ => None,
+            })
+            .collect();
+    }
+
+    /// One line per instruction, commented with its address and mnemonic,
+    /// with loops shown as `do { ... } while A != 0`.
+    pub fn pseudo_code(&self) -> String {
+        let labels = self.labels();
+        let mut result = String::new();
+        let mut depth = 0;
+        let mut line = |depth: usize, code: &str, comment: Option<String>| {
+            let code = format!("{}{}", "    ".repeat(depth), code);
+            match comment {
+                // Always at least one space before the comment
+                Some(comment) => {
+                    result += &format!("{:<COMMENT_COLUMN$}// {}\n", code + " ", comment)
+                }
+                None => result += &format!("{}\n", code),
+            }
+        };
+        for &(ip, instruction) in &self.listing {
+            if labels.contains(&ip) {
+                line(depth, &format!("{}:", ip), None);
+            }
+            for _ in self.loops.iter().filter(|l| l.head == ip) {
+                line(depth, "do {", None);
+                depth += 1;
+            }
+            let comment = Some(format!("{:>2}: {}", ip, instruction));
+            if self.loops.iter().any(|l| l.jump == ip) {
+                depth -= 1;
+                line(depth, "} while A != 0", comment);
+            } else {
+                line(depth, &statement(instruction), comment);
+            }
+        }
+        return result;
+    }
+
+    /// The control flow graph in graphviz's DOT, one node per block.
+    pub fn dot(&self) -> String {
+        let node = |target: Target| match target {
+            Target::Block(start) => format!("b{}", start),
+            Target::Halt => "halt".to_string(),
+        };
+        let mut result = String::from("digraph day17 {\n");
+        result += "    node [shape=box, fontname=\"monospace\"];\n";
+        for block in &self.blocks {
+            let label: String = block
+                .instructions
+                .iter()
+                .map(|&(ip, instruction)| format!("{}: {}\\l", ip, statement(instruction)))
+                .collect();
+            result += &format!("    b{} [label=\"{}\"];\n", block.start, label);
+        }
+        result += "    halt [shape=doublecircle];\n";
+        for block in &self.blocks {
+            for edge in &block.edges {
+                let attributes = match edge.when {
+                    Some(when) => format!(" [label=\"{}\"]", when),
+                    None => String::new(),
+                };
+                result += &format!("    b{} -> {}{};\n", block.start, node(edge.to), attributes);
+            }
+        }
+        result += "}\n";
+        return result;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vmlib::assemble;
+
+    fn decompiled(text: &str) -> Decompiled {
+        return decompile(&assemble::<Instruction>(text).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_pseudo_code() {
+        let program = decompiled("bst a\nbxl 1\ncdv b\nbxc 6\nadv 3\nbxl 4\nout b\njnz 0");
+        assert_eq!(
+            program.pseudo_code(),
+            "do {
+    B = A % 8       //  0: bst a
+    B ^= 1          //  2: bxl 1
+    C = A >> B      //  4: cdv b
+    B ^= C          //  6: bxc 6
+    A = A >> 3      //  8: adv 3
+    B ^= 4          // 10: bxl 4
+    out(B % 8)      // 12: out b
+} while A != 0      // 14: jnz 0
+"
+        );
+        assert_eq!(program.loops(), &[Loop { head: 0, jump: 14 }]);
+        assert_eq!(program.blocks().len(), 1);
+    }
+
+    #[test]
+    fn test_gotos() {
+        // A forward jump, and a loop inside a loop
+        let program = decompiled("jnz 4\nout 1\nadv 1\nout a\njnz 4\njnz 0");
+        assert_eq!(
+            program.pseudo_code(),
+            "do {
+    if A != 0 goto 4 //  0: jnz 4
+    out(1)          //  2: out 1
+    4:
+    do {
+        A = A >> 1  //  4: adv 1
+        out(A % 8)  //  6: out a
+    } while A != 0  //  8: jnz 4
+} while A != 0      // 10: jnz 0
+"
+        );
+        let starts: Vec<usize> = program.blocks().iter().map(|b| b.start).collect();
+        assert_eq!(starts, vec![0, 2, 4, 10]);
+
+        // These two loops cross, so the inner jump back stays a goto
+        let crossing = decompiled("adv 1\nout a\njnz 0\nout b\njnz 2");
+        assert_eq!(crossing.loops(), &[Loop { head: 0, jump: 4 }]);
+        assert!(crossing.pseudo_code().contains("\nif A != 0 goto 2 "));
+    }
+
+    #[test]
+    fn test_dot() {
+        let dot = decompiled("adv 3\nout a\njnz 0").dot();
+        assert!(dot.starts_with("digraph day17 {\n"));
+        assert!(dot.contains(
+            "    b0 [label=\"0: A = A >> 3\\l2: out(A % 8)\\l4: if A != 0 goto 0\\l\"];\n"
+        ));
+        assert!(dot.contains("    b0 -> b0 [label=\"A != 0\"];\n"));
+        assert!(dot.contains("    b0 -> halt [label=\"A == 0\"];\n"));
+        let straight = decompiled("jnz 4\nout 1\nout 2").dot();
+        assert!(straight.contains("    b2 -> b4;\n"));
+        assert!(straight.contains("    b0 -> b4 [label=\"A != 0\"];\n"));
+    }
+
+    #[test]
+    fn test_bad_jump() {
+        assert_eq!(
+            decompile(&[5, 4, 3, 1]),
+            Err(VmError::Decode(
+                2,
+                "jumps to 1, the middle of an instruction".to_string()
+            ))
+        );
+        // Jumping past the end just halts
+        assert!(decompile(&[3, 6]).is_ok());
+    }
+}
@@ -0,0 +1,224 @@
+//! Finding the register A that makes a program print itself.
+//!
+//! Puzzle programs are one loop: a body that prints one value, shifts A right
+//! by a fixed number of bits, then `jnz 0`. If B and C are always set before
+//! they're read, an iteration's output only depends on A at the top of the
+//! loop. Iteration `i` sees A shifted right by `i * shift`, so the last value
+//! printed depends only on the top digit of A (in base `2^shift`), the one
+//! before on the top two, and so on. Choosing digits from the most
+//! significant end, smallest first, and backtracking on a mismatch visits
+//! every A of the right length in increasing order, so the first hit is the
+//! smallest and running out proves there is none.
+use crate::Number;
+
+use std::fmt::{Display, Formatter};
+use vmlib::threebit::{Combo, Instruction, A, B, C};
+use vmlib::{disassemble, InstructionSet, Machine, VmError};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum QuineError {
+    /// The program doesn't decode
+    Vm(VmError),
+    /// The program isn't a loop the search understands, and why
+    Shape(String),
+    /// Every A that prints the right number of values was tried
+    NoQuine,
+}
+
+impl Display for QuineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            QuineError::Vm(e) => write!(f, "{}", e),
+            QuineError::Shape(reason) => write!(f, "unsupported program: {}", reason),
+            QuineError::NoQuine => write!(f, "no value of A makes the program print itself"),
+        };
+    }
+}
+
+/// The loop a program has to be, with the final `jnz 0` left out of `body`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct LoopShape {
+    body: Vec<Number>,
+    shift: u32,
+}
+
+fn shape_error<T>(reason: &str) -> Result<T, QuineError> {
+    return Err(QuineError::Shape(reason.to_string()));
+}
+
+/// Registers an instruction reads, then the one it writes.
+fn registers_used(instruction: Instruction) -> (Vec<usize>, Option<usize>) {
+    let combo = |op: Combo| match op {
+        Combo::Register(r) => vec![r],
+        Combo::Literal(_) => vec![],
+    };
+    let with_a = |op: Combo| [vec![A], combo(op)].concat();
+    return match instruction {
+        Instruction::Adv(op) => (with_a(op), Some(A)),
+        Instruction::Bxl(_) => (vec![B], Some(B)),
+        Instruction::Bst(op) => (combo(op), Some(B)),
+        Instruction::Jnz(_) => (vec![A], None),
+        Instruction::Bxc(_) => (vec![B, C], Some(B)),
+        Instruction::Out(op) => (combo(op), None),
+        Instruction::Bdv(op) => (with_a(op), Some(B)),
+        Instruction::Cdv(op) => (with_a(op), Some(C)),
+    };
+}
+
+fn loop_shape(program: &[Number]) -> Result<LoopShape, QuineError> {
+    let listing = disassemble::<Instruction>(program).map_err(QuineError::Vm)?;
+    let Some((&(_, last), body)) = listing.split_last() else {
+        return shape_error("the program is empty");
+    };
+    if last != Instruction::Jnz(0) {
+        return shape_error("it should end with jnz 0");
+    }
+
+    let mut shift = None;
+    let mut outputs = 0;
+    let mut written = vec![A];
+    for &(ip, instruction) in body {
+        let (reads, writes) = registers_used(instruction);
+        if let Some(r) = reads.iter().find(|r| !written.contains(r)) {
+            return shape_error(&format!(
+                "{} at {} reads {} before it is set, so iterations depend on each other",
+                instruction,
+                ip,
+                Instruction::REGISTERS[*r]
+            ));
+        }
+        match instruction {
+            Instruction::Jnz(_) => return shape_error("only the last instruction may jump"),
+            Instruction::Out(_) => outputs += 1,
+            Instruction::Adv(Combo::Literal(bits @ 1..=3)) if shift.is_none() => {
+                shift = Some(bits as u32);
+            }
+            Instruction::Adv(_) => {
+                return shape_error("A should only change once, by adv 1, 2 or 3");
+            }
+            _ => (),
+        }
+        written.extend(writes);
+    }
+    if outputs != 1 {
+        return shape_error("each iteration should print exactly one value");
+    }
+    let Some(shift) = shift else {
+        return shape_error("A never changes, so it loops forever or never");
+    };
+    let body_len = program.len() - 2;
+    return Ok(LoopShape {
+        body: program[..body_len].to_vec(),
+        shift: shift,
+    });
+}
+
+/// What one pass through the loop prints for a value of A.
+fn iteration(body: &mut Machine<Instruction>, a: Number) -> Number {
+    body.reset([a, 0, 0]);
+    // Straight line code with one out, checked by loop_shape
+    body.run(body.program().len()).unwrap();
+    return body.output[0];
+}
+
+/// Digits of A still to choose are `0..digit`, and `a` is A's top digits.
+fn search(
+    body: &mut Machine<Instruction>,
+    target: &[Number],
+    shift: u32,
+    digit: usize,
+    a: Number,
+) -> Option<Number> {
+    if digit == 0 {
+        return Some(a);
+    }
+    let position = digit - 1;
+    // A's top digit can't be 0, or it would print one value fewer
+    let lowest = if position == target.len() - 1 && position > 0 {
+        1
+    } else {
+        0
+    };
+    for d in lowest..(1 << shift) {
+        let candidate = (a << shift) | d;
+        if iteration(body, candidate) != target[position] {
+            continue;
+        }
+        if let Some(found) = search(body, target, shift, position, candidate) {
+            return Some(found);
+        }
+    }
+    return None;
+}
+
+/// The smallest A that makes `program` print itself.
+/// ```
+/// use day17::quine::{find_quine, QuineError};
+/// assert_eq!(find_quine(&[0, 3, 5, 4, 3, 0]), Ok(117440));
+/// assert_eq!(find_quine(&[0, 1, 5, 4, 3, 0]), Err(QuineError::NoQuine));
+/// ```
+pub fn find_quine(program: &[Number]) -> Result<Number, QuineError> {
+    let shape = loop_shape(program)?;
+    if program.len() * shape.shift as usize > Number::BITS as usize {
+        return shape_error("A would need more bits than a register has");
+    }
+    let mut body = Machine::new(shape.body, [0, 0, 0]);
+    return search(&mut body, program, shape.shift, program.len(), 0).ok_or(QuineError::NoQuine);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vmlib::assemble;
+
+    fn program(text: &str) -> Vec<Number> {
+        return assemble::<Instruction>(text).unwrap();
+    }
+
+    fn prints(program: &[Number], a: Number) -> Vec<Number> {
+        let mut machine: Machine<Instruction> = Machine::new(program.to_vec(), [a, 0, 0]);
+        machine.run(10_000).unwrap();
+        return machine.output;
+    }
+
+    #[test]
+    fn test_find_quine() {
+        // The shape of a real input: B and C are worked out from A every time
+        let real = program("bst a\nbxl 1\ncdv b\nbxc 6\nadv 3\nbxl 4\nout b\njnz 0");
+        let a = find_quine(&real).unwrap();
+        assert_eq!(prints(&real, a), real);
+        for smaller in a.saturating_sub(5000)..a {
+            assert_ne!(prints(&real, smaller), real);
+        }
+    }
+
+    #[test]
+    fn test_other_shifts() {
+        // Small enough to check there really is no answer by trying every A
+        for (text, shift) in [
+            ("adv 2\nout a\njnz 0", 2),
+            ("bst a\nbxl 3\nout b\nadv 1\njnz 0", 1),
+        ] {
+            let program = program(text);
+            assert_eq!(find_quine(&program), Err(QuineError::NoQuine));
+            for a in 0..(1 << (shift * program.len())) {
+                assert_ne!(prints(&program, a), program);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shapes() {
+        let shape = |text: &str| matches!(find_quine(&program(text)), Err(QuineError::Shape(_)));
+        assert!(shape("adv 3\nout a"));
+        assert!(shape("adv 3\nout b\njnz 0"));
+        assert!(shape("bxl 1\nadv 3\nout b\njnz 0"));
+        assert!(shape("adv 3\nadv 3\nout a\njnz 0"));
+        assert!(shape("adv a\nout a\njnz 0"));
+        assert!(shape("out a\njnz 0"));
+        assert!(shape("adv 3\njnz 0"));
+        assert!(shape("adv 3\njnz 0\nout a\njnz 0"));
+        assert!(!shape("bst a\nadv 3\nout b\njnz 0"));
+        assert!(matches!(find_quine(&[0, 7, 3, 0]), Err(QuineError::Vm(_))));
+    }
+}
@@ -5,8 +5,9 @@ use std::collections::HashSet;
 use std::collections::VecDeque;
 
 pub use filelib::load_no_blanks;
+use filelib::{parse_number, split_pair, ParseError};
 use gridlib::{Grid, GridCoordinate, GridPrintable, GridTraversable, SimpleGridOverlay};
-use log::info;
+use tracelib::{debug, enabled, trace, Level};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum GridItem {
@@ -25,16 +26,39 @@ impl GridPrintable for GridItem {
     }
 }
 
-fn parse_input(s: &Vec<String>) -> Vec<GridCoordinate> {
+fn parse_input(s: &Vec<String>) -> Result<Vec<GridCoordinate>, ParseError> {
     let mut result = vec![];
     for line in s {
-        let (x_s, y_s) = line.split_once(",").unwrap();
-        let x = x_s.trim().parse().unwrap();
-        let y = y_s.trim().parse().unwrap();
+        let (x_s, y_s) = split_pair(line, ",")?;
+        let x = parse_number(x_s)?;
+        let y = parse_number(y_s)?;
         let coord = GridCoordinate::new(x, y);
         result.push(coord);
     }
-    return result;
+    return Ok(result);
+}
+
+/// Check every byte is an `x,y` pair that lands inside a `width` by `height` memory space.
+/// ```
+/// let bytes = vec!["5,4".to_string(), "4,2".to_string()];
+/// assert!(day18::check_input(&bytes, 7, 7).is_ok());
+/// assert!(day18::check_input(&bytes, 5, 5).is_err());
+/// assert!(day18::check_input(&vec!["5;4".to_string()], 7, 7).is_err());
+/// ```
+pub fn check_input(
+    string_list: &Vec<String>,
+    width: usize,
+    height: usize,
+) -> Result<(), ParseError> {
+    for byte in parse_input(string_list)? {
+        if byte.x >= width || byte.y >= height {
+            return Err(ParseError::new(&format!(
+                "{:?} is outside the memory space",
+                byte
+            )));
+        }
+    }
+    return Ok(());
 }
 
 fn bfs(
@@ -76,45 +100,14 @@ fn bfs(
 }
 
 fn print_path(grid: &Grid<GridItem>, path: Vec<SimpleGridOverlay>) {
-    let lines = grid.grid_strings_with_overlay(path);
-    for line in lines {
-        info!("{}", line);
+    if !enabled!(Level::TRACE) {
+        return;
     }
-    info!("----");
-    info!("");
+    trace!("\n{}", grid.grid_strings_with_overlay(path).join("\n"));
 }
 
 /// Find path from top left to bottom right, using the first x amount of input as walls.
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "5,4",
-///     "4,2",
-///     "4,5",
-///     "3,0",
-///     "2,1",
-///     "6,3",
-///     "2,4",
-///     "1,5",
-///     "0,6",
-///     "3,3",
-///     "2,6",
-///     "5,1",
-///     "1,2",
-///     "5,5",
-///     "2,5",
-///     "6,5",
-///     "1,4",
-///     "0,4",
-///     "6,4",
-///     "1,1",
-///     "6,1",
-///     "1,0",
-///     "0,5",
-///     "1,6",
-///     "2,0"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day18::puzzle_a(&vec1, 7, 7, 12), 22);
-/// ```
+#[doc = filelib::fixture_doc!("day18-1", a, |fixture| day18::puzzle_a(&fixture.lines(), 7, 7, 12))]
 pub fn puzzle_a(
     string_list: &Vec<String>,
     width: usize,
@@ -125,7 +118,7 @@ pub fn puzzle_a(
     let start = GridCoordinate::new(0, 0);
     values[start.x + start.y * width] = GridItem::Person;
 
-    let bytes = parse_input(string_list);
+    let bytes = parse_input(string_list).unwrap();
     let size = cmp::min(first_x_values, values.len());
     for i in 0..size {
         let byte = bytes[i];
@@ -164,7 +157,7 @@ fn binary_search(
             // treat as greater than
             hind = half - 1;
         } else {
-            info!("Found a path for {} bytes", half);
+            debug!("Found a path for {} bytes", half);
             print_path(&new_grid, path);
             lind = half + 1;
         }
@@ -174,36 +167,7 @@ fn binary_search(
 }
 
 /// Get Grid coordinate of first byte that makes no possible path
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "5,4",
-///     "4,2",
-///     "4,5",
-///     "3,0",
-///     "2,1",
-///     "6,3",
-///     "2,4",
-///     "1,5",
-///     "0,6",
-///     "3,3",
-///     "2,6",
-///     "5,1",
-///     "1,2",
-///     "5,5",
-///     "2,5",
-///     "6,5",
-///     "1,4",
-///     "0,4",
-///     "6,4",
-///     "1,1",
-///     "6,1",
-///     "1,0",
-///     "0,5",
-///     "1,6",
-///     "2,0"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day18::puzzle_b(&vec1, 7, 7, 12), (6,1));
-/// ```
+#[doc = filelib::fixture_doc!("day18-1", b, |fixture| { let (x, y) = day18::puzzle_b(&fixture.lines(), 7, 7, 12); format!("{},{}", x, y) })]
 pub fn puzzle_b(
     string_list: &Vec<String>,
     width: usize,
@@ -214,7 +178,7 @@ pub fn puzzle_b(
     let start = GridCoordinate::new(0, 0);
     values[start.x + start.y * width] = GridItem::Person;
 
-    let bytes = parse_input(string_list);
+    let bytes = parse_input(string_list).unwrap();
     let size = cmp::min(start_pos, values.len());
     for i in 0..size {
         let byte = bytes[i];
@@ -2,8 +2,9 @@ extern crate filelib;
 
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use filelib::ParseError;
 pub use filelib::{load, split_lines_by_blanks};
-use log::info;
+use tracelib::trace;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum TowelColor {
@@ -14,42 +15,55 @@ enum TowelColor {
     Green,
 }
 
-fn parse_towels(input: &String) -> Vec<Vec<TowelColor>> {
-    let mut result = vec![];
-    for towel in input.split(", ") {
-        let mut current = vec![];
-        for c in towel.chars() {
-            current.push(match c {
-                'w' => TowelColor::White,
-                'u' => TowelColor::Blue,
-                'b' => TowelColor::Black,
-                'r' => TowelColor::Red,
-                'g' => TowelColor::Green,
-                _ => panic!("Unknown color in towels {}", c),
-            });
-        }
-        result.push(current);
+fn parse_stripes(input: &str) -> Result<Vec<TowelColor>, ParseError> {
+    let mut current = vec![];
+    for c in input.chars() {
+        current.push(match c {
+            'w' => TowelColor::White,
+            'u' => TowelColor::Blue,
+            'b' => TowelColor::Black,
+            'r' => TowelColor::Red,
+            'g' => TowelColor::Green,
+            _ => return Err(ParseError::new(&format!("Unknown color {}", c))),
+        });
+    }
+    if current.is_empty() {
+        return Err(ParseError::new(
+            "towels and patterns need at least one stripe",
+        ));
     }
-    return result;
+    return Ok(current);
 }
 
-fn parse_patterns(input: &Vec<String>) -> Vec<Vec<TowelColor>> {
-    let mut result = vec![];
-    for pattern in input {
-        let mut current = vec![];
-        for c in pattern.chars() {
-            current.push(match c {
-                'w' => TowelColor::White,
-                'u' => TowelColor::Blue,
-                'b' => TowelColor::Black,
-                'r' => TowelColor::Red,
-                'g' => TowelColor::Green,
-                _ => panic!("Unknown color in towels {}", c),
-            });
-        }
-        result.push(current);
-    }
-    return result;
+fn parse_towels(input: &String) -> Result<Vec<Vec<TowelColor>>, ParseError> {
+    return input.split(", ").map(parse_stripes).collect();
+}
+
+fn parse_patterns(input: &Vec<String>) -> Result<Vec<Vec<TowelColor>>, ParseError> {
+    return input.iter().map(|line| parse_stripes(line)).collect();
+}
+
+fn parse(
+    string_list: &Vec<Vec<String>>,
+) -> Result<(Vec<Vec<TowelColor>>, Vec<Vec<TowelColor>>), ParseError> {
+    let [towels, patterns] = string_list.as_slice() else {
+        return Err(ParseError::new("expected towels then patterns"));
+    };
+    let [towels] = towels.as_slice() else {
+        return Err(ParseError::new("towels should be one line"));
+    };
+    return Ok((parse_towels(towels)?, parse_patterns(patterns)?));
+}
+
+/// Check the towels and patterns only use the five known colors.
+/// ```
+/// let good = vec![vec!["r, wr, b".to_string()], vec!["brwrr".to_string()]];
+/// assert!(day19::check_input(&good).is_ok());
+/// let bad = vec![vec!["r, wx".to_string()], vec!["brwrr".to_string()]];
+/// assert!(day19::check_input(&bad).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<Vec<String>>) -> Result<(), ParseError> {
+    return parse(string_list).map(|_| ());
 }
 
 fn is_possible(towels: &Vec<Vec<TowelColor>>, pattern: &Vec<TowelColor>) -> bool {
@@ -61,7 +75,7 @@ fn is_possible(towels: &Vec<Vec<TowelColor>>, pattern: &Vec<TowelColor>) -> bool
     while let Some((cur_to_solve, cur_solution)) = queue.pop_front() {
         if cur_to_solve.len() == 0 {
             // Solved!
-            info!("Solution found for {:?} - {:?}", towels, cur_solution);
+            trace!("Solution found for {:?} - {:?}", towels, cur_solution);
             return true;
         }
         if visited.contains(&cur_to_solve) {
@@ -87,24 +101,9 @@ fn is_possible(towels: &Vec<Vec<TowelColor>>, pattern: &Vec<TowelColor>) -> bool
 }
 
 /// Arrange some towels, get possible to solve
-/// ```
-/// let vec1: Vec<Vec<String>> = vec![vec![
-///     "r, wr, b, g, bwu, rb, gb, br"
-/// ].iter().map(|s| s.to_string()).collect(), vec![
-///     "brwrr",
-///     "bggr",
-///     "gbbr",
-///     "rrbgbr",
-///     "ubwu",
-///     "bwurrg",
-///     "brgr",
-///     "bbrgwb"
-/// ].iter().map(|s| s.to_string()).collect()];
-/// assert_eq!(day19::puzzle_a(&vec1), 6);
-/// ```
+#[doc = filelib::fixture_doc!("day19-1", a, |fixture| day19::puzzle_a(&fixture.groups()))]
 pub fn puzzle_a(string_list: &Vec<Vec<String>>) -> usize {
-    let towels = parse_towels(string_list.first().unwrap().first().unwrap());
-    let patterns = parse_patterns(string_list.last().unwrap());
+    let (towels, patterns) = parse(string_list).unwrap();
     let possible: Vec<Vec<TowelColor>> = patterns
         .into_iter()
         .filter(|pattern| is_possible(&towels, pattern))
@@ -150,24 +149,9 @@ fn get_all_possible(
 }
 
 /// Provide all possible arrangements instead.
-/// ```
-/// let vec1: Vec<Vec<String>> = vec![vec![
-///     "r, wr, b, g, bwu, rb, gb, br"
-/// ].iter().map(|s| s.to_string()).collect(), vec![
-///     "brwrr",
-///     "bggr",
-///     "gbbr",
-///     "rrbgbr",
-///     "ubwu",
-///     "bwurrg",
-///     "brgr",
-///     "bbrgwb"
-/// ].iter().map(|s| s.to_string()).collect()];
-/// assert_eq!(day19::puzzle_b(&vec1), 16);
-/// ```
+#[doc = filelib::fixture_doc!("day19-1", b, |fixture| day19::puzzle_b(&fixture.groups()))]
 pub fn puzzle_b(string_list: &Vec<Vec<String>>) -> usize {
-    let towels = parse_towels(string_list.first().unwrap().first().unwrap());
-    let patterns = parse_patterns(string_list.last().unwrap());
+    let (towels, patterns) = parse(string_list).unwrap();
     let mut possible = 0;
     let mut cache = Cache::new();
     let default_solution = vec![];
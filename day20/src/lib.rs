@@ -3,9 +3,10 @@ extern crate filelib;
 use std::collections::{HashMap, VecDeque};
 
 pub use filelib::load_no_blanks;
+use filelib::{grid_size, ParseError};
 use gridlib::{Grid, GridCoordinate, GridPrintable, GridTraversable};
 use itertools::Itertools;
-use log::info;
+use tracelib::debug;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum GridItem {
@@ -26,12 +27,13 @@ impl GridPrintable for GridItem {
     }
 }
 
-fn parse_map(string_list: &Vec<String>) -> (Grid<GridItem>, GridCoordinate, GridCoordinate) {
-    let width = string_list.first().unwrap().len();
-    let height = string_list.len();
+fn parse_map(
+    string_list: &Vec<String>,
+) -> Result<(Grid<GridItem>, GridCoordinate, GridCoordinate), ParseError> {
+    let (width, height) = grid_size(string_list)?;
     let mut values = vec![];
-    let mut start = GridCoordinate::new(0, 0);
-    let mut end = GridCoordinate::new(width - 1, height - 1);
+    let mut start = None;
+    let mut end = None;
 
     for (y, line) in string_list.into_iter().enumerate() {
         for (x, c) in line.chars().enumerate() {
@@ -42,32 +44,38 @@ fn parse_map(string_list: &Vec<String>) -> (Grid<GridItem>, GridCoordinate, Grid
                 '#' => {
                     values.push(GridItem::Wall);
                 }
-                'S' => {
-                    start.x = x;
-                    start.y = y;
+                'S' if start.is_none() => {
+                    start = Some(GridCoordinate::new(x, y));
                     values.push(GridItem::Start);
                 }
-                'E' => {
-                    end.x = x;
-                    end.y = y;
+                'E' if end.is_none() => {
+                    end = Some(GridCoordinate::new(x, y));
                     values.push(GridItem::End);
                 }
-                _ => panic!("Unknown char {}", c),
+                _ => return Err(ParseError::new(&format!("Unexpected char {}", c))),
             }
         }
     }
 
-    return (Grid::new(width, height, values), start, end);
+    let (Some(start), Some(end)) = (start, end) else {
+        return Err(ParseError::new("racetrack needs a start and an end"));
+    };
+    return Ok((Grid::new(width, height, values), start, end));
+}
+
+/// Check the racetrack is a rectangle with one start and one end.
+/// ```
+/// assert!(day20::check_input(&vec!["#####".to_string(), "#S.E#".to_string()]).is_ok());
+/// assert!(day20::check_input(&vec!["#####".to_string(), "#S.E".to_string()]).is_err());
+/// assert!(day20::check_input(&vec!["#S.x#".to_string()]).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return parse_map(string_list).map(|_| ());
 }
 
 /*/
 fn print_path(grid: &Grid<GridItem>, path: Vec<SimpleGridOverlay>) {
-    let lines = grid.grid_strings_with_overlay(path);
-    for line in lines {
-        info!("{}", line);
-    }
-    info!("----");
-    info!("");
+    trace!("\n{}", grid.grid_strings_with_overlay(path).join("\n"));
 }
 */
 
@@ -90,7 +98,7 @@ fn bfs(
         }
         dists.insert(cur_coord, length);
         if cur_coord == end {
-            info!("path found");
+            debug!("path found");
             continue;
         }
 
@@ -116,55 +124,17 @@ fn bfs(
 }
 
 /// How many cheats will save >= 100 picoseconds?
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "###############",
-///     "#...#...#.....#",
-///     "#.#.#.#.#.###.#",
-///     "#S#...#.#.#...#",
-///     "#######.#.#.###",
-///     "#######.#.#...#",
-///     "#######.#.###.#",
-///     "###..E#...#...#",
-///     "###.#######.###",
-///     "#...###...#...#",
-///     "#.#####.#.###.#",
-///     "#.#...#.#.#...#",
-///     "#.#.#.#.#.#.###",
-///     "#...#...#...###",
-///     "###############"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day20::puzzle_a(&vec1, 2), 14+14+2+4+2+3+1+1+1+1+1);
-/// ```
+#[doc = filelib::fixture_doc!("day20-1", a, |fixture| day20::puzzle_a(&fixture.lines(), 2))]
 pub fn puzzle_a(string_list: &Vec<String>, min_save: usize) -> usize {
-    let (grid, start, end) = parse_map(string_list);
+    let (grid, start, end) = parse_map(string_list).unwrap();
     let normal_solution = bfs(&grid, start, end, min_save, 2);
     return normal_solution;
 }
 
 /// How many cheats will save >= 100 picoseconds, if yoou can cheat for 20?
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "###############",
-///     "#...#...#.....#",
-///     "#.#.#.#.#.###.#",
-///     "#S#...#.#.#...#",
-///     "#######.#.#.###",
-///     "#######.#.#...#",
-///     "#######.#.###.#",
-///     "###..E#...#...#",
-///     "###.#######.###",
-///     "#...###...#...#",
-///     "#.#####.#.###.#",
-///     "#.#...#.#.#...#",
-///     "#.#.#.#.#.#.###",
-///     "#...#...#...###",
-///     "###############"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day20::puzzle_b(&vec1, 50), 32+31+29+39+25+23+20+19+12+14+12+22+4+3);
-/// ```
+#[doc = filelib::fixture_doc!("day20-1", b, |fixture| day20::puzzle_b(&fixture.lines(), 50))]
 pub fn puzzle_b(string_list: &Vec<String>, min_save: usize) -> usize {
-    let (grid, start, end) = parse_map(string_list);
+    let (grid, start, end) = parse_map(string_list).unwrap();
     let normal_solution = bfs(&grid, start, end, min_save, 20);
     return normal_solution;
 }
@@ -0,0 +1,436 @@
+//! Keypads as data, and chains of robots typing on them.
+//!
+//! A keypad is a `Grid<Option<Button>>`, where `None` is a gap no robot
+//! arm may point at. The door's keypad is pressed by a robot, that robot is
+//! steered from a directional keypad pressed by the next robot, and so on
+//! up to the directional keypad you press. Every arm starts on `A`.
+use filelib::ParseError;
+use gridlib::{distances, Direction, Grid, GridCoordinate, GridTraversable};
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Button {
+    Number(usize),
+    Activate,
+    DirButton(Direction),
+    /// Any other label, for keypads of your own
+    Key(char),
+}
+
+impl Button {
+    /// `0`-`9`, `A`, and `^`, `>`, `v`, `<` for directions.
+    pub fn from_char(c: char) -> Button {
+        return match c {
+            '0'..='9' => Button::Number(c.to_digit(10).unwrap() as usize),
+            'A' => Button::Activate,
+            '^' => Button::DirButton(Direction::NORTH),
+            '>' => Button::DirButton(Direction::EAST),
+            'v' => Button::DirButton(Direction::SOUTH),
+            '<' => Button::DirButton(Direction::WEST),
+            _ => Button::Key(c),
+        };
+    }
+}
+
+impl Display for Button {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            Button::Number(x) => write!(f, "{}", x),
+            Button::Activate => write!(f, "A"),
+            Button::DirButton(Direction::NORTH) => write!(f, "^"),
+            Button::DirButton(Direction::EAST) => write!(f, ">"),
+            Button::DirButton(Direction::SOUTH) => write!(f, "v"),
+            Button::DirButton(Direction::WEST) => write!(f, "<"),
+            Button::DirButton(d) => write!(f, "{}", d),
+            Button::Key(c) => write!(f, "{}", c),
+        };
+    }
+}
+
+/// Presses written the way the puzzle does, eg `<A^A>^^AvvvA`.
+pub fn sequence_string(buttons: &[Button]) -> String {
+    return buttons.iter().map(|b| b.to_string()).collect();
+}
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::NORTH,
+    Direction::EAST,
+    Direction::SOUTH,
+    Direction::WEST,
+];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Keypad {
+    grid: Grid<Option<Button>>,
+    positions: HashMap<Button, GridCoordinate>,
+}
+
+impl Keypad {
+    /// A keypad from its layout, which needs an `A`, no button twice, and
+    /// every button reachable from `A` without crossing a gap.
+    pub fn new(grid: Grid<Option<Button>>) -> Result<Keypad, ParseError> {
+        let mut positions = HashMap::new();
+        for c in grid.coord_iter() {
+            if let Some(button) = grid.get_value(c).unwrap() {
+                if positions.insert(button, c).is_some() {
+                    return Err(ParseError::new(&format!(
+                        "{} is on the keypad twice",
+                        button
+                    )));
+                }
+            }
+        }
+        let Some(&start) = positions.get(&Button::Activate) else {
+            return Err(ParseError::new(
+                "The keypad needs an A for the arm to start on",
+            ));
+        };
+        let reachable = distances(&grid, start, |b| b.is_some());
+        for c in grid.coord_iter() {
+            if let Some(button) = grid.get_value(c).unwrap() {
+                if !reachable.contains_key(&c) {
+                    return Err(ParseError::new(&format!(
+                        "{} can't be reached from A without crossing a gap",
+                        button
+                    )));
+                }
+            }
+        }
+        return Ok(Keypad {
+            grid: grid,
+            positions: positions,
+        });
+    }
+
+    /// A keypad drawn as rows of buttons, with spaces for gaps. Short rows
+    /// are filled out with gaps.
+    /// ```
+    /// let keypad = day21::keypad::Keypad::parse(" ^A\n<v>").unwrap();
+    /// assert_eq!(keypad, day21::keypad::Keypad::directional());
+    /// assert!(day21::keypad::Keypad::parse("12\n21A").is_err());
+    /// ```
+    pub fn parse(layout: &str) -> Result<Keypad, ParseError> {
+        let rows: Vec<&str> = layout.lines().collect();
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut values = vec![];
+        for row in &rows {
+            let mut buttons: Vec<Option<Button>> = row
+                .chars()
+                .map(|c| match c {
+                    ' ' => None,
+                    c => Some(Button::from_char(c)),
+                })
+                .collect();
+            buttons.resize(width, None);
+            values.extend(buttons);
+        }
+        return Keypad::new(Grid::new(width, rows.len(), values));
+    }
+
+    /// The door's keypad.
+    pub fn numeric() -> Keypad {
+        return Keypad::parse("789\n456\n123\n 0A").unwrap();
+    }
+
+    /// The keypad robots are steered from.
+    pub fn directional() -> Keypad {
+        return Keypad::parse(" ^A\n<v>").unwrap();
+    }
+
+    pub fn grid(&self) -> &Grid<Option<Button>> {
+        return &self.grid;
+    }
+
+    pub fn position(&self, button: Button) -> Option<GridCoordinate> {
+        return self.positions.get(&button).copied();
+    }
+
+    /// The button an arm at `at` would press, `None` over a gap or off the edge.
+    pub fn button_at(&self, at: GridCoordinate) -> Option<Button> {
+        return self.grid.get_value(at).flatten();
+    }
+
+    /// Every shortest way from one button to another that never crosses a gap.
+    ///
+    /// Steps from `to` are counted over the buttons first, so a way round a
+    /// gap is found even when it has to head away from `to` for a while.
+    fn moves(&self, from: Button, to: Button) -> Vec<Vec<Direction>> {
+        let (from, to) = (self.positions[&from], self.positions[&to]);
+        let steps = distances(&self.grid, to, |b| b.is_some());
+        let mut result = vec![];
+        self.walk(from, to, &steps, &mut vec![], &mut result);
+        return result;
+    }
+
+    fn walk(
+        &self,
+        at: GridCoordinate,
+        to: GridCoordinate,
+        steps: &HashMap<GridCoordinate, usize>,
+        path: &mut Vec<Direction>,
+        result: &mut Vec<Vec<Direction>>,
+    ) {
+        if at == to {
+            result.push(path.clone());
+            return;
+        }
+        for direction in DIRECTIONS {
+            let next = self.grid.get_coordinate_by_direction(at, direction);
+            // Only cells one step closer, which are never gaps
+            let Some(next) = next.filter(|n| steps.get(n) == Some(&(steps[&at] - 1))) else {
+                continue;
+            };
+            path.push(direction);
+            self.walk(next, to, steps, path, result);
+            path.pop();
+        }
+    }
+
+    /// A code typed on this keypad, one button per character.
+    pub fn parse_code(&self, code: &str) -> Result<Vec<Button>, ParseError> {
+        let mut buttons = vec![];
+        for c in code.chars() {
+            let button = Button::from_char(c);
+            if !self.positions.contains_key(&button) {
+                return Err(ParseError::new(&format!("{} isn't on the keypad", c)));
+            }
+            buttons.push(button);
+        }
+        return Ok(buttons);
+    }
+}
+
+/// Cheapest cost of a move, by keypad and the buttons the arm goes between.
+type Cache = HashMap<(usize, Button, Button), usize>;
+
+/// The keypads from the door up to yours, with the cheapest presses
+/// remembered between codes.
+#[derive(Debug, Clone)]
+pub struct KeypadChain {
+    keypads: Vec<Keypad>,
+    cache: Cache,
+}
+
+impl KeypadChain {
+    /// `robots` robots on copies of `pad` between the robot at the `door`
+    /// and you, who also press a `pad`. The puzzle has 2 in part a and 25 in
+    /// part b.
+    pub fn new(door: Keypad, pad: Keypad, robots: usize) -> Result<KeypadChain, ParseError> {
+        for direction in DIRECTIONS {
+            if pad.position(Button::DirButton(direction)).is_none() {
+                return Err(ParseError::new(&format!(
+                    "The directional keypad has no {}",
+                    Button::DirButton(direction)
+                )));
+            }
+        }
+        let mut keypads = vec![door];
+        keypads.extend(vec![pad; robots + 1]);
+        return Ok(KeypadChain {
+            keypads: keypads,
+            cache: HashMap::new(),
+        });
+    }
+
+    /// The usual numeric door and directional keypads.
+    pub fn puzzle(robots: usize) -> KeypadChain {
+        return KeypadChain::new(Keypad::numeric(), Keypad::directional(), robots).unwrap();
+    }
+
+    /// The door's keypad first, yours last.
+    pub fn keypads(&self) -> &[Keypad] {
+        return &self.keypads;
+    }
+
+    /// Presses on the keypad after `layer` that move its arm from `from` to
+    /// `to` and press it, for each shortest way there.
+    fn options(&self, layer: usize, from: Button, to: Button) -> Vec<Vec<Button>> {
+        return self.keypads[layer]
+            .moves(from, to)
+            .into_iter()
+            .map(|path| {
+                let mut presses: Vec<Button> = path.into_iter().map(Button::DirButton).collect();
+                presses.push(Button::Activate);
+                return presses;
+            })
+            .collect();
+    }
+
+    /// What it costs you to have `layer`'s arm go from `from` to `to` and press it.
+    fn step(&mut self, layer: usize, from: Button, to: Button) -> usize {
+        if let Some(&cost) = self.cache.get(&(layer, from, to)) {
+            return cost;
+        }
+        let cost = self
+            .options(layer, from, to)
+            .iter()
+            .map(|presses| self.cost(layer + 1, presses))
+            .min()
+            .unwrap();
+        self.cache.insert((layer, from, to), cost);
+        return cost;
+    }
+
+    /// What it costs you to have `layer`'s arm press `buttons`, starting on `A`.
+    fn cost(&mut self, layer: usize, buttons: &[Button]) -> usize {
+        if layer + 1 == self.keypads.len() {
+            // Your own keypad, so one press each
+            return buttons.len();
+        }
+        let mut last = Button::Activate;
+        let mut total = 0;
+        for &button in buttons {
+            total += self.step(layer, last, button);
+            last = button;
+        }
+        return total;
+    }
+
+    /// How many presses you need to type `code` on the door.
+    ///
+    /// Each move's cost is worked out once per keypad, so this is fast for
+    /// any number of robots. Panics if the code isn't all on the door's keypad.
+    pub fn length(&mut self, code: &[Button]) -> usize {
+        return self.cost(0, code);
+    }
+
+    fn expand(&mut self, layer: usize, buttons: &[Button], result: &mut Vec<Button>) {
+        if layer + 1 == self.keypads.len() {
+            result.extend(buttons);
+            return;
+        }
+        let mut last = Button::Activate;
+        for &button in buttons {
+            let options = self.options(layer, last, button);
+            let best = options
+                .iter()
+                .min_by_key(|presses| self.cost(layer + 1, presses))
+                .unwrap();
+            self.expand(layer + 1, best, result);
+            last = button;
+        }
+    }
+
+    /// One of the shortest sequences you can press to type `code` on the door.
+    ///
+    /// The sequence is `length` long, which grows about 2.5 times per robot,
+    /// so this is only practical for a handful of them.
+    /// ```
+    /// use day21::keypad::{sequence_string, KeypadChain};
+    /// let mut chain = KeypadChain::puzzle(0);
+    /// let code = chain.keypads()[0].parse_code("029A").unwrap();
+    /// assert_eq!(sequence_string(&chain.sequence(&code)), "<A^A^^>AvvvA");
+    /// ```
+    pub fn sequence(&mut self, code: &[Button]) -> Vec<Button> {
+        let mut result = vec![];
+        self.expand(0, code, &mut result);
+        return result;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Presses needed to go from `start` to `end` on the door and press it.
+    fn door_step(robots: usize, start: Button, end: Button) -> usize {
+        return KeypadChain::puzzle(robots).step(0, start, end);
+    }
+
+    #[test]
+    fn test_a_to_0() {
+        // <A
+        assert_eq!(door_step(0, Button::Activate, Button::Number(0)), 2);
+        // v<<A>^>A, at this level >>^ and >^> cost the same
+        assert_eq!(door_step(1, Button::Activate, Button::Number(0)), 8);
+        // v<A<AA>^>AvAA^<A>A
+        assert_eq!(door_step(2, Button::Activate, Button::Number(0)), 18);
+    }
+
+    #[test]
+    fn test_0_to_2() {
+        // ^A
+        assert_eq!(door_step(0, Button::Number(0), Button::Number(2)), 2);
+        // <A>A
+        assert_eq!(door_step(1, Button::Number(0), Button::Number(2)), 4);
+    }
+
+    #[test]
+    fn test_2_to_9() {
+        // ^^>A, no better than >^^A
+        assert_eq!(door_step(0, Button::Number(2), Button::Number(9)), 4);
+        // <AA>vA^A
+        assert_eq!(door_step(1, Button::Number(2), Button::Number(9)), 8);
+    }
+
+    #[test]
+    fn test_9_to_a() {
+        // vvvA
+        assert_eq!(door_step(0, Button::Number(9), Button::Activate), 4);
+        // v<AAA^>A
+        assert_eq!(door_step(1, Button::Number(9), Button::Activate), 8);
+    }
+
+    #[test]
+    fn test_gap_avoided() {
+        // From A to 7 can't go left along the bottom row first
+        let keypad = Keypad::numeric();
+        let moves = keypad.moves(Button::Activate, Button::Number(7));
+        assert!(!moves.is_empty());
+        assert!(moves
+            .iter()
+            .all(|m| m[..2] != [Direction::WEST, Direction::WEST]));
+        assert_eq!(moves.len(), 9);
+    }
+
+    #[test]
+    fn test_sequence() {
+        let mut chain = KeypadChain::puzzle(2);
+        let code = Keypad::numeric().parse_code("029A").unwrap();
+        let sequence = chain.sequence(&code);
+        assert_eq!(sequence.len(), 68);
+        assert_eq!(chain.length(&code), 68);
+        assert!(sequence_string(&sequence).ends_with('A'));
+    }
+
+    #[test]
+    fn test_other_layouts() {
+        // A keypad with a gap in the middle, and a directional pad laid out in a line
+        let door = Keypad::parse("12A\n3 4\n567").unwrap();
+        let pad = Keypad::parse("<v^>A").unwrap();
+        let mut chain = KeypadChain::new(door, pad, 1).unwrap();
+        let code = chain.keypads()[0].parse_code("74A").unwrap();
+        assert_eq!(chain.sequence(&code).len(), chain.length(&code));
+        assert!(chain.keypads()[0].parse_code("8A").is_err());
+        assert!(KeypadChain::new(Keypad::numeric(), Keypad::numeric(), 1).is_err());
+        assert!(Keypad::parse("12\n3 ").is_err());
+    }
+
+    #[test]
+    fn test_detour() {
+        // 1 is only reached by going down, along and back up
+        let door = Keypad::parse("A 1\n234").unwrap();
+        assert_eq!(
+            door.moves(Button::Activate, Button::Number(1)),
+            vec![vec![
+                Direction::SOUTH,
+                Direction::EAST,
+                Direction::EAST,
+                Direction::NORTH
+            ]]
+        );
+        let mut chain = KeypadChain::new(door, Keypad::directional(), 1).unwrap();
+        let code = chain.keypads()[0].parse_code("1A").unwrap();
+        // v<A >A A <^A >A
+        assert_eq!(chain.step(0, Button::Activate, Button::Number(1)), 11);
+        assert_eq!(chain.sequence(&code).len(), chain.length(&code));
+        // Nothing reaches 1 at all
+        assert!(Keypad::parse("A 1\n2  ").is_err());
+    }
+}
@@ -0,0 +1,207 @@
+//! Running a sequence of presses through a chain of keypads, to see what
+//! it types on the door.
+//!
+//! The presses are worked through one keypad at a time, from yours down to
+//! the door. Each press remembers which of yours caused it, so when an arm
+//! swings over a gap the error can say exactly which press did it.
+use crate::keypad::{sequence_string, Button, Keypad, KeypadChain};
+
+use gridlib::{Direction, GridCoordinate, GridTraversable};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SimulationError {
+    /// Your press `step` sent the arm over keypad `keypad`, 0 being the
+    /// door's, from `from` towards `direction` onto a gap or off the edge
+    Panic {
+        step: usize,
+        keypad: usize,
+        from: GridCoordinate,
+        direction: Direction,
+    },
+    /// Your press `step` made a robot press `button`, which isn't a direction
+    /// or `A`, on keypad `keypad`, so the robot below doesn't know what to do
+    NotDirectional {
+        step: usize,
+        keypad: usize,
+        button: Button,
+    },
+}
+
+impl Display for SimulationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            SimulationError::Panic {
+                step,
+                keypad,
+                from,
+                direction,
+            } => write!(
+                f,
+                "press {} moves the arm over keypad {} {} from ({}, {}) off the buttons",
+                step,
+                keypad,
+                Button::DirButton(*direction),
+                from.x,
+                from.y
+            ),
+            SimulationError::NotDirectional {
+                step,
+                keypad,
+                button,
+            } => write!(
+                f,
+                "press {} presses {} on keypad {}, which doesn't steer a robot",
+                step, button, keypad
+            ),
+        };
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+/// What the arm over `keypad`, number `index` in the chain, presses when
+/// steered by `presses`. Each press keeps the step of yours it came from.
+fn decode(
+    keypad: &Keypad,
+    index: usize,
+    presses: &[(usize, Button)],
+) -> Result<Vec<(usize, Button)>, SimulationError> {
+    let mut arm = keypad.position(Button::Activate).unwrap();
+    let mut result = vec![];
+    for &(step, press) in presses {
+        match press {
+            Button::Activate => result.push((step, keypad.button_at(arm).unwrap())),
+            Button::DirButton(direction) => {
+                let next = keypad.grid().get_coordinate_by_direction(arm, direction);
+                let Some(next) = next.filter(|&n| keypad.button_at(n).is_some()) else {
+                    return Err(SimulationError::Panic {
+                        step: step,
+                        keypad: index,
+                        from: arm,
+                        direction: direction,
+                    });
+                };
+                arm = next;
+            }
+            button => {
+                return Err(SimulationError::NotDirectional {
+                    step: step,
+                    keypad: index + 1,
+                    button: button,
+                })
+            }
+        }
+    }
+    return Ok(result);
+}
+
+/// Press `presses` on your keypad, the last in `chain`, and give back what
+/// gets pressed on every keypad, the door's first and yours last.
+/// ```
+/// use day21::keypad::KeypadChain;
+/// use day21::simulate::simulate;
+/// let chain = KeypadChain::puzzle(1);
+/// let presses = chain.keypads()[2].parse_code("v<<A>>^A<A>AvA<^AA>A<vAAA>^A").unwrap();
+/// let typed = simulate(&chain, &presses).unwrap();
+/// assert_eq!(typed[1].len(), 12);
+/// assert_eq!(day21::keypad::sequence_string(&typed[0]), "029A");
+/// ```
+pub fn simulate(
+    chain: &KeypadChain,
+    presses: &[Button],
+) -> Result<Vec<Vec<Button>>, SimulationError> {
+    let keypads = chain.keypads();
+    let mut layer: Vec<(usize, Button)> = presses.iter().copied().enumerate().collect();
+    let mut result = vec![presses.to_vec()];
+    for index in (0..keypads.len() - 1).rev() {
+        layer = decode(&keypads[index], index, &layer)?;
+        result.push(layer.iter().map(|&(_, button)| button).collect());
+    }
+    result.reverse();
+    return Ok(result);
+}
+
+/// Just the code `presses` types on the door, as text.
+pub fn typed_code(chain: &KeypadChain, presses: &[Button]) -> Result<String, SimulationError> {
+    let typed = simulate(chain, presses)?;
+    return Ok(sequence_string(&typed[0]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn presses(text: &str) -> Vec<Button> {
+        return Keypad::directional().parse_code(text).unwrap();
+    }
+
+    #[test]
+    fn test_optimizer_sequences_type_the_code() {
+        for robots in 0..4 {
+            let mut chain = KeypadChain::puzzle(robots);
+            for code in ["029A", "980A", "179A", "456A", "379A", "7A", "A"] {
+                let buttons = chain.keypads()[0].parse_code(code).unwrap();
+                let sequence = chain.sequence(&buttons);
+                assert_eq!(sequence.len(), chain.length(&buttons));
+                assert_eq!(typed_code(&chain, &sequence), Ok(code.to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_example_sequence() {
+        // The puzzle's own sequence for 029A, which isn't the one `sequence` picks
+        let chain = KeypadChain::puzzle(2);
+        let given = "<vA<AA>>^AvAA<^A>A<v<A>>^AvA^A<vA>^A<v<A>^A>AAvA^A<v<A>A>^AAAvA<^A>A";
+        let typed = simulate(&chain, &presses(given)).unwrap();
+        assert_eq!(sequence_string(&typed[2]), "v<<A>>^A<A>AvA<^AA>A<vAAA>^A");
+        assert_eq!(sequence_string(&typed[1]), "<A^A>^^AvvvA");
+        assert_eq!(sequence_string(&typed[0]), "029A");
+    }
+
+    #[test]
+    fn test_panic() {
+        // A to 0 to the gap, on the third press
+        let chain = KeypadChain::puzzle(0);
+        assert_eq!(
+            simulate(&chain, &presses("<A<A")),
+            Err(SimulationError::Panic {
+                step: 2,
+                keypad: 0,
+                from: GridCoordinate::new(1, 3),
+                direction: Direction::WEST,
+            })
+        );
+        // The robot on the directional pad goes off its top edge
+        let chain = KeypadChain::puzzle(1);
+        assert!(matches!(
+            simulate(&chain, &presses("<A^A")),
+            Err(SimulationError::Panic {
+                step: 2,
+                keypad: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_not_directional() {
+        let pad = Keypad::parse("<v^>A#").unwrap();
+        let chain = KeypadChain::new(Keypad::numeric(), pad.clone(), 1).unwrap();
+        let presses = pad.parse_code(">A").unwrap();
+        let error = simulate(&chain, &presses).unwrap_err();
+        assert_eq!(
+            error,
+            SimulationError::NotDirectional {
+                step: 1,
+                keypad: 1,
+                button: Button::Key('#'),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "press 1 presses # on keypad 1, which doesn't steer a robot"
+        );
+    }
+}
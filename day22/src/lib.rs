@@ -4,7 +4,10 @@ use std::cmp;
 use std::collections::{HashMap, HashSet};
 
 pub use filelib::load_no_blanks;
-use log::info;
+use filelib::{parse_number, ParseError};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use tracelib::trace;
 
 type SecretNumber = i64;
 
@@ -25,6 +28,31 @@ fn prune(v: SecretNumber) -> SecretNumber {
     return v % 16777216;
 }
 
+fn parse_secrets(string_list: &Vec<String>) -> Result<Vec<SecretNumber>, ParseError> {
+    let mut result = vec![];
+    for line in string_list {
+        let n: SecretNumber = parse_number(line)?;
+        if n < 0 || n != prune(n) {
+            return Err(ParseError::new(&format!(
+                "{} is not a pruned secret number",
+                n
+            )));
+        }
+        result.push(n);
+    }
+    return Ok(result);
+}
+
+/// Check every line is a secret number that has already been pruned.
+/// ```
+/// assert!(day22::check_input(&vec!["1".to_string(), "2024".to_string()]).is_ok());
+/// assert!(day22::check_input(&vec!["-1".to_string()]).is_err());
+/// assert!(day22::check_input(&vec!["16777216".to_string()]).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return parse_secrets(string_list).map(|_| ());
+}
+
 fn evolve_secret_number_x_times(n: SecretNumber, x: usize) -> SecretNumber {
     let mut cur = n;
     for _ in 0..x {
@@ -34,24 +62,14 @@ fn evolve_secret_number_x_times(n: SecretNumber, x: usize) -> SecretNumber {
 }
 
 /// Find the 2000th secret number and sum them
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "1",
-///     "10",
-///     "100",
-///     "2024"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day22::puzzle_a(&vec1), 37327623);
-/// ```
+#[doc = filelib::fixture_doc!("day22-1", a, |fixture| day22::puzzle_a(&fixture.lines()))]
 pub fn puzzle_a(string_list: &Vec<String>) -> SecretNumber {
-    let input: Vec<SecretNumber> = string_list
-        .iter()
-        .map(|x| x.parse::<SecretNumber>().unwrap())
-        .collect();
-    return input
-        .into_iter()
-        .map(|x| evolve_secret_number_x_times(x, 2000))
-        .sum();
+    let input: Vec<SecretNumber> = parse_secrets(string_list).unwrap();
+    #[cfg(feature = "parallel")]
+    let input = input.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let input = input.into_iter();
+    return input.map(|x| evolve_secret_number_x_times(x, 2000)).sum();
 }
 
 type Cache = HashSet<(SecretNumber, SecretNumber, SecretNumber, SecretNumber)>;
@@ -70,24 +88,28 @@ fn calc_monkey_data(
     initial_values: Vec<SecretNumber>,
     num_changes: usize,
 ) -> Vec<(Vec<SecretNumber>, Vec<SecretNumber>, Vec<SecretNumber>)> {
-    let mut monkey_data: Vec<(Vec<SecretNumber>, Vec<SecretNumber>, Vec<SecretNumber>)> = vec![];
-
-    // For each monkey
-    for monkey_initial in initial_values {
-        let mut last_value = monkey_initial;
-        // Generate the next 2000 secret numbers, and get the first digit of each.
-        let a = get_all_evolutions_in_x_times(last_value, num_changes);
-        let b: Vec<SecretNumber> = a.iter().map(|x| x % 10).collect();
-        let mut c = vec![];
-        // And get the differences between those
-        for i in b.iter() {
-            let diff = i.clone() - last_value % 10;
-            c.push(diff);
-            last_value = i.clone();
-        }
-        monkey_data.push((a, b, c));
-    }
-    return monkey_data;
+    // Each monkey evolves independently
+    #[cfg(feature = "parallel")]
+    let initial_values = initial_values.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let initial_values = initial_values.into_iter();
+
+    return initial_values
+        .map(|monkey_initial| {
+            let mut last_value = monkey_initial;
+            // Generate the next 2000 secret numbers, and get the first digit of each.
+            let a = get_all_evolutions_in_x_times(last_value, num_changes);
+            let b: Vec<SecretNumber> = a.iter().map(|x| x % 10).collect();
+            let mut c = vec![];
+            // And get the differences between those
+            for i in b.iter() {
+                let diff = i.clone() - last_value % 10;
+                c.push(diff);
+                last_value = i.clone();
+            }
+            return (a, b, c);
+        })
+        .collect();
 }
 
 fn calc_runs(
@@ -114,25 +136,14 @@ fn calc_runs(
 }
 
 /// Generate secret number 2001 -> 4000, and find the run of price differences in that, such that it maximizes the overall profit.
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "1",
-///     "2",
-///     "3",
-///     "2024"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day22::puzzle_b(&vec1, 0, 2000), 23);
-/// ```
+#[doc = filelib::fixture_doc!("day22-2", b, |fixture| day22::puzzle_b(&fixture.lines(), 0, 2000))]
 /// Skip iterations is here because I misunderstood the question, lol.
 pub fn puzzle_b(
     string_list: &Vec<String>,
     skip_iterations: usize,
     then_iterations: usize,
 ) -> SecretNumber {
-    let input: Vec<SecretNumber> = string_list
-        .iter()
-        .map(|x| x.parse::<SecretNumber>().unwrap())
-        .collect();
+    let input: Vec<SecretNumber> = parse_secrets(string_list).unwrap();
     let initial_values: Vec<SecretNumber> = input
         .into_iter()
         .map(|x| evolve_secret_number_x_times(x, skip_iterations))
@@ -156,7 +167,7 @@ pub fn puzzle_b(
             }
             cache.insert(*key);
 
-            info!("key: {:?}, gave sum {}", key, cur_sum);
+            trace!("key: {:?}, gave sum {}", key, cur_sum);
             highest_sum = cmp::max(highest_sum, cur_sum);
         }
     }
@@ -2,21 +2,46 @@ extern crate filelib;
 use std::collections::{HashMap, HashSet};
 
 pub use filelib::load_no_blanks;
+use filelib::{split_pair, ParseError};
 use itertools::Itertools;
-use log::info;
+use tracelib::trace;
 
 type Node<'a> = &'a str;
 type AdjacencyGraph<'a> = HashMap<Node<'a>, HashSet<Node<'a>>>;
 
-fn parse_connections(string_list: &Vec<String>) -> AdjacencyGraph {
+fn parse_node(s: &str) -> Result<Node<'_>, ParseError> {
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err(ParseError::new(&format!("Bad computer name {}", s)));
+    }
+    return Ok(s);
+}
+
+fn parse_connections(string_list: &Vec<String>) -> Result<AdjacencyGraph<'_>, ParseError> {
     let mut graph = AdjacencyGraph::new();
 
     for line in string_list {
-        let (node_a, node_b) = line.split_once("-").unwrap();
+        let (node_a, node_b) = split_pair(line, "-")?;
+        let (node_a, node_b) = (parse_node(node_a)?, parse_node(node_b)?);
+        if node_a == node_b {
+            return Err(ParseError::new(&format!(
+                "{} is connected to itself",
+                node_a
+            )));
+        }
         graph.entry(node_a).or_insert(HashSet::new()).insert(node_b);
         graph.entry(node_b).or_insert(HashSet::new()).insert(node_a);
     }
-    return graph;
+    return Ok(graph);
+}
+
+/// Check every line connects two different computers with lowercase names.
+/// ```
+/// assert!(day23::check_input(&vec!["kh-tc".to_string(), "qp-kh".to_string()]).is_ok());
+/// assert!(day23::check_input(&vec!["kh-kh".to_string()]).is_err());
+/// assert!(day23::check_input(&vec!["kh tc".to_string()]).is_err());
+/// ```
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return parse_connections(string_list).map(|_| ());
 }
 
 fn find_triangles<'a>(graph: &AdjacencyGraph<'a>) -> HashSet<Vec<Node<'a>>> {
@@ -30,7 +55,7 @@ fn find_triangles<'a>(graph: &AdjacencyGraph<'a>) -> HashSet<Vec<Node<'a>>> {
             if graph[neighbor_a].contains(neighbor_b) {
                 let mut triangle = vec![*node, *neighbor_a, *neighbor_b];
                 triangle.sort();
-                info!("triangle found: {:?}", triangle);
+                trace!("triangle found: {:?}", triangle);
                 triangles.insert(triangle);
             }
         }
@@ -39,45 +64,9 @@ fn find_triangles<'a>(graph: &AdjacencyGraph<'a>) -> HashSet<Vec<Node<'a>>> {
 }
 
 /// Find triplets of connected computers that start with t
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "kh-tc",
-///     "qp-kh",
-///     "de-cg",
-///     "ka-co",
-///     "yn-aq",
-///     "qp-ub",
-///     "cg-tb",
-///     "vc-aq",
-///     "tb-ka",
-///     "wh-tc",
-///     "yn-cg",
-///     "kh-ub",
-///     "ta-co",
-///     "de-co",
-///     "tc-td",
-///     "tb-wq",
-///     "wh-td",
-///     "ta-ka",
-///     "td-qp",
-///     "aq-cg",
-///     "wq-ub",
-///     "ub-vc",
-///     "de-ta",
-///     "wq-aq",
-///     "wq-vc",
-///     "wh-yn",
-///     "ka-de",
-///     "kh-ta",
-///     "co-tc",
-///     "wh-qp",
-///     "tb-vc",
-///     "td-yn"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day23::puzzle_a(&vec1), 7);
-/// ```
+#[doc = filelib::fixture_doc!("day23-1", a, |fixture| day23::puzzle_a(&fixture.lines()))]
 pub fn puzzle_a(string_list: &Vec<String>) -> usize {
-    let connections = parse_connections(string_list);
+    let connections = parse_connections(string_list).unwrap();
     let triangles = find_triangles(&connections);
     let filtered: Vec<_> = triangles
         .into_iter()
@@ -135,45 +124,9 @@ fn find_all_cliques<'a>(graph: &AdjacencyGraph<'a>) -> Vec<HashSet<Node<'a>>> {
 }
 
 /// Find largest combination that are all connected to each other.
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "kh-tc",
-///     "qp-kh",
-///     "de-cg",
-///     "ka-co",
-///     "yn-aq",
-///     "qp-ub",
-///     "cg-tb",
-///     "vc-aq",
-///     "tb-ka",
-///     "wh-tc",
-///     "yn-cg",
-///     "kh-ub",
-///     "ta-co",
-///     "de-co",
-///     "tc-td",
-///     "tb-wq",
-///     "wh-td",
-///     "ta-ka",
-///     "td-qp",
-///     "aq-cg",
-///     "wq-ub",
-///     "ub-vc",
-///     "de-ta",
-///     "wq-aq",
-///     "wq-vc",
-///     "wh-yn",
-///     "ka-de",
-///     "kh-ta",
-///     "co-tc",
-///     "wh-qp",
-///     "tb-vc",
-///     "td-yn"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(day23::puzzle_b(&vec1), "co,de,ka,ta");
-/// ```
+#[doc = filelib::fixture_doc!("day23-1", b, |fixture| day23::puzzle_b(&fixture.lines()))]
 pub fn puzzle_b(string_list: &Vec<String>) -> String {
-    let connections = parse_connections(string_list);
+    let connections = parse_connections(string_list).unwrap();
     let all_cliques = find_all_cliques(&connections);
     let mut max_clique: Vec<&str> = all_cliques
         .iter()
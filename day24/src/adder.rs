@@ -0,0 +1,484 @@
+//! Checking a circuit is a ripple carry adder by its shape, and fixing one
+//! that isn't by swapping gate outputs.
+//!
+//! Bit 0 is a half adder, `z00 = x00 XOR y00` carrying `x00 AND y00`, and
+//! every bit after it a full adder:
+//! ```text
+//! s = x XOR y        z = s XOR carry
+//! a = x AND y        b = s AND carry        carry out = a OR b
+//! ```
+//! with the last carry out being the top z. Gates are found by the wires
+//! they read, starting from x and y, which no swap can move. So a swapped
+//! output shows up as a wire that isn't read by the gate the pattern says
+//! should read it.
+use circuitlib::{Circuit, CircuitError, Gate, Simulation, WireId};
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+
+/// Up to this many bits, `confirm` tries every pair of inputs.
+const EXHAUSTIVE_BITS: usize = 8;
+/// Random pairs `confirm` adds for wider circuits.
+const SAMPLES: usize = 1000;
+
+/// Where a circuit first stops looking like an adder.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Fault {
+    pub bit: usize,
+    /// The wire that isn't where it should be
+    pub wire: String,
+    pub reason: String,
+}
+
+impl Display for Fault {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "bit {}: {} {}", self.bit, self.wire, self.reason);
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum AdderError {
+    /// The x, y and z wires aren't numbered like an adder's
+    Shape(String),
+    /// No set of up to this many swaps gets past the fault
+    Unrepairable(Fault, usize),
+    /// The circuit has an adder's shape but adds `x` and `y` up to `got`
+    Wrong {
+        x: u128,
+        y: u128,
+        got: u128,
+    },
+    Circuit(CircuitError),
+}
+
+impl Display for AdderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            AdderError::Shape(reason) => write!(f, "not an adder: {}", reason),
+            AdderError::Unrepairable(fault, swaps) => {
+                write!(f, "no {} swaps fix {}", swaps, fault)
+            }
+            AdderError::Wrong { x, y, got } => write!(f, "{} + {} came out as {}", x, y, got),
+            AdderError::Circuit(e) => write!(f, "{}", e),
+        };
+    }
+}
+
+impl std::error::Error for AdderError {}
+
+fn named(circuit: &Circuit, prefix: &str, bit: usize) -> Result<WireId, CircuitError> {
+    return circuit.wire(&format!("{}{:02}", prefix, bit));
+}
+
+/// How many bits the circuit adds: `x00` to `xNN` and the same for y, with
+/// one more z for the final carry.
+pub fn width(circuit: &Circuit) -> Result<usize, AdderError> {
+    let bits = circuit.wires_with_prefix("x").len();
+    let (ys, zs) = (
+        circuit.wires_with_prefix("y").len(),
+        circuit.wires_with_prefix("z").len(),
+    );
+    if bits == 0 || ys != bits || zs != bits + 1 {
+        return Err(AdderError::Shape(format!(
+            "{} x, {} y and {} z wires, where n, n and n + 1 are needed",
+            bits, ys, zs
+        )));
+    }
+    for bit in 0..=bits {
+        for prefix in ["x", "y", "z"] {
+            if bit < bits || prefix == "z" {
+                named(circuit, prefix, bit).map_err(|e| AdderError::Shape(e.to_string()))?;
+            }
+        }
+    }
+    return Ok(bits);
+}
+
+/// The output of the two input `gate` reading `a` and `b`.
+fn find(circuit: &Circuit, gate: Gate, a: WireId, b: WireId) -> Option<WireId> {
+    return circuit
+        .readers(a)
+        .find(|g| g.gate == gate && g.inputs.len() == 2 && g.inputs.contains(&b))
+        .map(|g| g.output);
+}
+
+/// Follow the pattern from bit 0 up, stopping at the first thing out of place.
+fn scan(circuit: &Circuit, bits: usize) -> Result<(), Fault> {
+    let fault = |bit: usize, wire: WireId, reason: String| Fault {
+        bit: bit,
+        wire: circuit.name(wire).to_string(),
+        reason: reason,
+    };
+    let name = |wire: WireId| circuit.name(wire);
+    let wire = |prefix: &str, bit: usize| named(circuit, prefix, bit).unwrap();
+    let missing = |bit: usize, a: WireId, gate: Gate, b: WireId| {
+        fault(
+            bit,
+            a,
+            format!("is not read by a {} with {}", gate, name(b)),
+        )
+    };
+    // The carry out of `bit` has to be the top z, and only the last one can be
+    let carry_out = |bit: usize, carry: WireId| {
+        let top = wire("z", bits);
+        if bit + 1 == bits && carry != top {
+            return Err(fault(
+                bit,
+                carry,
+                format!("is the last carry, so should be {}", name(top)),
+            ));
+        }
+        if bit + 1 < bits && name(carry).starts_with('z') {
+            return Err(fault(
+                bit,
+                carry,
+                "is a carry, so shouldn't be a z".to_string(),
+            ));
+        }
+        return Ok(());
+    };
+
+    let (x, y, z) = (wire("x", 0), wire("y", 0), wire("z", 0));
+    let sum = find(circuit, Gate::Xor, x, y).ok_or_else(|| missing(0, x, Gate::Xor, y))?;
+    if sum != z {
+        return Err(fault(
+            0,
+            sum,
+            format!("is x00 XOR y00, so should be {}", name(z)),
+        ));
+    }
+    let mut carry = find(circuit, Gate::And, x, y).ok_or_else(|| missing(0, x, Gate::And, y))?;
+    carry_out(0, carry)?;
+
+    for bit in 1..bits {
+        let (x, y, z) = (wire("x", bit), wire("y", bit), wire("z", bit));
+        let s = find(circuit, Gate::Xor, x, y).ok_or_else(|| missing(bit, x, Gate::Xor, y))?;
+        let a = find(circuit, Gate::And, x, y).ok_or_else(|| missing(bit, x, Gate::And, y))?;
+        match find(circuit, Gate::Xor, s, carry) {
+            Some(out) if out != z => {
+                let reason = format!(
+                    "is {} XOR {}, so should be {}",
+                    name(s),
+                    name(carry),
+                    name(z)
+                );
+                return Err(fault(bit, out, reason));
+            }
+            Some(_) => (),
+            None => {
+                // If z's gate reads one of the two, the other is the odd one out
+                let reads = |w: WireId| match circuit.driver(z) {
+                    Some(g) => g.gate == Gate::Xor && g.inputs.contains(&w),
+                    None => false,
+                };
+                if reads(carry) {
+                    return Err(missing(bit, s, Gate::Xor, carry));
+                }
+                if reads(s) {
+                    return Err(missing(bit, carry, Gate::Xor, s));
+                }
+                let reason = format!("should be {} XOR {}", name(s), name(carry));
+                return Err(fault(bit, z, reason));
+            }
+        }
+        let b =
+            find(circuit, Gate::And, s, carry).ok_or_else(|| missing(bit, s, Gate::And, carry))?;
+        carry = match find(circuit, Gate::Or, a, b) {
+            Some(out) => out,
+            None if circuit.readers(b).any(|g| g.gate == Gate::Or) => {
+                return Err(missing(bit, a, Gate::Or, b));
+            }
+            None => return Err(missing(bit, b, Gate::Or, a)),
+        };
+        carry_out(bit, carry)?;
+    }
+    return Ok(());
+}
+
+/// The first place the circuit stops looking like an adder, or `None` if
+/// it is one.
+/// ```
+/// use circuitlib::{Circuit, Gate};
+/// let mut builder = Circuit::builder();
+/// builder.gate(Gate::Xor, &["x00", "y00"], "z01").unwrap();
+/// builder.gate(Gate::And, &["x00", "y00"], "z00").unwrap();
+/// let circuit = builder.build().unwrap();
+/// let fault = day24::adder::find_fault(&circuit).unwrap().unwrap();
+/// assert_eq!(fault.to_string(), "bit 0: z01 is x00 XOR y00, so should be z00");
+/// ```
+pub fn find_fault(circuit: &Circuit) -> Result<Option<Fault>, AdderError> {
+    let bits = width(circuit)?;
+    return Ok(scan(circuit, bits).err());
+}
+
+/// Driven wires up to three gates on from the inputs of the bits around
+/// `bit`, which is as far as a full adder and the carry into the next go.
+fn neighbourhood(circuit: &Circuit, bits: usize, bit: usize) -> Vec<WireId> {
+    let mut frontier: Vec<WireId> = vec![];
+    for near in bit.saturating_sub(1)..(bit + 2).min(bits) {
+        frontier.push(named(circuit, "x", near).unwrap());
+        frontier.push(named(circuit, "y", near).unwrap());
+    }
+    let mut found = BTreeSet::new();
+    for _ in 0..3 {
+        frontier = frontier
+            .iter()
+            .flat_map(|&w| circuit.readers(w).map(|g| g.output))
+            .filter(|&w| found.insert(w))
+            .collect();
+    }
+    for near in bit..=(bit + 1).min(bits) {
+        found.insert(named(circuit, "z", near).unwrap());
+    }
+    return found.into_iter().collect();
+}
+
+/// Depth first over swaps that each get past the first fault.
+fn search(
+    circuit: &mut Circuit,
+    bits: usize,
+    left: usize,
+    swaps: &mut Vec<(WireId, WireId)>,
+) -> bool {
+    let Err(fault) = scan(circuit, bits) else {
+        return true;
+    };
+    if left == 0 {
+        return false;
+    }
+    let candidates = neighbourhood(circuit, bits, fault.bit);
+    for (i, &p) in candidates.iter().enumerate() {
+        for &q in &candidates[i + 1..] {
+            if circuit.swap_outputs(p, q).is_err() {
+                continue;
+            }
+            let progress = match scan(circuit, bits) {
+                Ok(()) => true,
+                Err(next) => next.bit > fault.bit,
+            };
+            if progress {
+                swaps.push((p, q));
+                if search(circuit, bits, left - 1, swaps) {
+                    return true;
+                }
+                swaps.pop();
+            }
+            // Swapping back can't make a loop, it was fine before
+            circuit.swap_outputs(p, q).unwrap();
+        }
+    }
+    return false;
+}
+
+/// Swap pairs of gate outputs until the circuit has an adder's shape, using
+/// as few swaps as possible and at most `max_swaps`. The swaps are left in
+/// place and given back by wire name.
+///
+/// Each swap has to get past the first fault without a new one showing up
+/// before it, so only wires around that fault are tried.
+pub fn repair(
+    circuit: &mut Circuit,
+    max_swaps: usize,
+) -> Result<Vec<(String, String)>, AdderError> {
+    let bits = width(circuit)?;
+    let Err(first) = scan(circuit, bits) else {
+        return Ok(vec![]);
+    };
+    for limit in 1..=max_swaps {
+        let mut swaps = vec![];
+        if search(circuit, bits, limit, &mut swaps) {
+            let mut names = vec![];
+            for (p, q) in swaps {
+                let (p, q) = (circuit.name(p).to_string(), circuit.name(q).to_string());
+                names.push((p.clone().min(q.clone()), p.max(q)));
+            }
+            return Ok(names);
+        }
+    }
+    return Err(AdderError::Unrepairable(first, max_swaps));
+}
+
+/// Stand-in for a random number generator, so the samples are the same
+/// every run. This is splitmix64.
+fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    return z ^ (z >> 31);
+}
+
+/// Inputs to add: every pair for narrow adders, otherwise carries into and
+/// out of each bit and a fixed set of random pairs.
+fn samples(bits: usize) -> Vec<(u128, u128)> {
+    let mask = (1u128 << bits) - 1;
+    if bits <= EXHAUSTIVE_BITS {
+        return (0..=mask)
+            .flat_map(|x| (0..=mask).map(move |y| (x, y)))
+            .collect();
+    }
+    let mut pairs = vec![(0, 0), (mask, mask)];
+    for bit in 0..bits {
+        pairs.push((1 << bit, 1 << bit));
+        pairs.push((mask, 1 << bit));
+    }
+    let mut state = bits as u64;
+    let mut random = || {
+        let wide = (next_random(&mut state) as u128) << 64 | next_random(&mut state) as u128;
+        return wide & mask;
+    };
+    for _ in 0..SAMPLES {
+        pairs.push((random(), random()));
+    }
+    return pairs;
+}
+
+/// Check the circuit really adds by simulating it.
+///
+/// Narrow circuits are tried on every pair of inputs. Wider ones get a carry
+/// into and out of every bit and some random pairs, changing the inputs
+/// incrementally between them.
+pub fn confirm(circuit: &Circuit) -> Result<(), AdderError> {
+    let bits = width(circuit)?;
+    if bits > 127 {
+        return Err(AdderError::Shape(format!(
+            "{} bits is too wide to simulate",
+            bits
+        )));
+    }
+    let inputs: HashMap<String, bool> = circuit
+        .inputs()
+        .into_iter()
+        .map(|w| (circuit.name(w).to_string(), false))
+        .collect();
+    let mut simulation = Simulation::new(circuit, &inputs).map_err(AdderError::Circuit)?;
+    for (x, y) in samples(bits) {
+        simulation.set_number("x", x).map_err(AdderError::Circuit)?;
+        simulation.set_number("y", y).map_err(AdderError::Circuit)?;
+        let got = simulation.number("z");
+        if got != x + y {
+            return Err(AdderError::Wrong {
+                x: x,
+                y: y,
+                got: got,
+            });
+        }
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A working adder with readable wire names, as `(a, gate, b, out)`.
+    fn gates(bits: usize) -> Vec<(String, Gate, String, String)> {
+        let carry = |i: usize| {
+            if i + 1 == bits {
+                return format!("z{:02}", bits);
+            }
+            return format!("c{:02}", i);
+        };
+        let mut gates = vec![
+            (
+                "x00".to_string(),
+                Gate::Xor,
+                "y00".to_string(),
+                "z00".to_string(),
+            ),
+            ("x00".to_string(), Gate::And, "y00".to_string(), carry(0)),
+        ];
+        for i in 1..bits {
+            let (x, y) = (format!("x{:02}", i), format!("y{:02}", i));
+            let (s, a, b) = (
+                format!("s{:02}", i),
+                format!("a{:02}", i),
+                format!("b{:02}", i),
+            );
+            gates.push((x.clone(), Gate::Xor, y.clone(), s.clone()));
+            gates.push((s.clone(), Gate::Xor, carry(i - 1), format!("z{:02}", i)));
+            gates.push((x, Gate::And, y, a.clone()));
+            gates.push((s, Gate::And, carry(i - 1), b.clone()));
+            gates.push((a, Gate::Or, b, carry(i)));
+        }
+        return gates;
+    }
+
+    fn build(gates: &[(String, Gate, String, String)]) -> Circuit {
+        let mut builder = Circuit::builder();
+        for (a, gate, b, out) in gates {
+            builder.gate(*gate, &[a, b], out).unwrap();
+        }
+        return builder.build().unwrap();
+    }
+
+    /// An adder with the outputs of these pairs of wires swapped.
+    fn broken(bits: usize, swaps: &[(&str, &str)]) -> Circuit {
+        let mut gates = gates(bits);
+        for (p, q) in swaps {
+            for gate in gates.iter_mut() {
+                if gate.3 == *p {
+                    gate.3 = q.to_string();
+                } else if gate.3 == *q {
+                    gate.3 = p.to_string();
+                }
+            }
+        }
+        return build(&gates);
+    }
+
+    #[test]
+    fn test_working_adder() {
+        for bits in [1, 2, 5, 12] {
+            let circuit = build(&gates(bits));
+            assert_eq!(width(&circuit), Ok(bits));
+            assert_eq!(find_fault(&circuit), Ok(None));
+            assert_eq!(confirm(&circuit), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_faults() {
+        let fault = |swaps: &[(&str, &str)]| find_fault(&broken(5, swaps)).unwrap().unwrap();
+        // z and the carry out of the same bit
+        let swapped = fault(&[("z02", "c02")]);
+        assert_eq!((swapped.bit, swapped.wire.as_str()), (2, "c02"));
+        assert_eq!(swapped.reason, "is s02 XOR c01, so should be z02");
+        // x XOR y and x AND y, so z's gate reads the wrong sum
+        let swapped = fault(&[("s03", "a03")]);
+        assert_eq!((swapped.bit, swapped.wire.as_str()), (3, "a03"));
+        assert_eq!(swapped.reason, "is not read by a XOR with c02");
+        assert!(confirm(&broken(5, &[("z02", "c02")])).is_err());
+    }
+
+    #[test]
+    fn test_repair() {
+        let mut circuit = broken(12, &[("z03", "c03"), ("s06", "a06"), ("z09", "b09")]);
+        let mut swaps = repair(&mut circuit, 4).unwrap();
+        swaps.sort();
+        assert_eq!(
+            swaps,
+            vec![
+                ("a06".to_string(), "s06".to_string()),
+                ("b09".to_string(), "z09".to_string()),
+                ("c03".to_string(), "z03".to_string()),
+            ]
+        );
+        assert_eq!(find_fault(&circuit), Ok(None));
+        assert_eq!(confirm(&circuit), Ok(()));
+
+        let mut circuit = broken(6, &[("z02", "c02"), ("z04", "c04")]);
+        assert!(matches!(
+            repair(&mut circuit, 1),
+            Err(AdderError::Unrepairable(Fault { bit: 2, .. }, 1))
+        ));
+    }
+
+    #[test]
+    fn test_not_an_adder() {
+        let mut builder = Circuit::builder();
+        builder.gate(Gate::And, &["x00", "y00"], "z00").unwrap();
+        let circuit = builder.build().unwrap();
+        assert!(matches!(width(&circuit), Err(AdderError::Shape(_))));
+    }
+}
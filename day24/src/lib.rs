@@ -1,53 +1,50 @@
 extern crate filelib;
 
-use std::collections::HashMap;
+use adder::AdderError;
+use circuitlib::{dot, verilog, Circuit, Gate, Simulation};
+use std::collections::{HashMap, HashSet};
 
 pub use filelib::{load, split_lines_by_blanks};
-use log::{error, info};
+use filelib::{parse_number, split_pair, ParseError};
+use tracelib::{debug, error};
+
+pub mod adder;
+#[cfg(feature = "reference")]
+pub mod reference;
 
 type Number = u128;
 type Key = String;
-type Instruction = (Key, Key, Key, Operation);
+type Instruction = (Key, Key, Key, Gate);
+
+/// The real inputs have four pairs of outputs swapped.
+const SWAPS: usize = 4;
 
-fn parse_variables(lines: &Vec<String>) -> HashMap<Key, Number> {
+fn parse_variables(lines: &Vec<String>) -> Result<HashMap<Key, Number>, ParseError> {
     let mut map = HashMap::new();
     for line in lines {
-        let (name, num_s) = line.split_once(": ").unwrap();
-        let num: Number = num_s.parse().unwrap();
-        map.insert(name.to_string(), num);
-    }
-    return map;
-}
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-enum Operation {
-    And,
-    Or,
-    Xor,
-}
-
-impl Operation {
-    fn operate(&self, a: Number, b: Number) -> Number {
-        return match self {
-            Operation::And => a & b,
-            Operation::Or => a | b,
-            Operation::Xor => a ^ b,
-        };
+        let (name, num_s) = split_pair(line, ": ")?;
+        let num: Number = parse_number(num_s)?;
+        if num > 1 {
+            return Err(ParseError::new(&format!("{} is not a bit", num)));
+        }
+        if map.insert(name.to_string(), num).is_some() {
+            return Err(ParseError::new(&format!("{} is set twice", name)));
+        }
     }
+    return Ok(map);
 }
 
-fn parse_operations(lines: &Vec<String>) -> Vec<Instruction> {
+fn parse_operations(lines: &Vec<String>) -> Result<Vec<Instruction>, ParseError> {
     let mut result = vec![];
+    let mut outputs = HashSet::new();
     for line in lines {
-        let (operands, store_in) = line.split_once(" -> ").unwrap();
-        let (first_arg, rest) = operands.split_once(" ").unwrap();
-        let (op_str, second_arg) = rest.split_once(" ").unwrap();
-        let op = match op_str {
-            "AND" => Operation::And,
-            "OR" => Operation::Or,
-            "XOR" => Operation::Xor,
-            _ => panic!("Unknown operation {}", op_str),
-        };
+        let (operands, store_in) = split_pair(line, " -> ")?;
+        let (first_arg, rest) = split_pair(operands, " ")?;
+        let (op_str, second_arg) = split_pair(rest, " ")?;
+        let op: Gate = op_str.parse().map_err(|e: String| ParseError::new(&e))?;
+        if !outputs.insert(store_in) {
+            return Err(ParseError::new(&format!("{} is driven twice", store_in)));
+        }
         result.push((
             first_arg.to_string(),
             second_arg.to_string(),
@@ -55,306 +52,194 @@ fn parse_operations(lines: &Vec<String>) -> Vec<Instruction> {
             op,
         ));
     }
-    return result;
+    return Ok(result);
 }
 
-fn can_do_operation(first_arg: &Key, second_arg: &Key, state: &HashMap<Key, Number>) -> bool {
-    return state.contains_key(first_arg) && state.contains_key(second_arg);
+fn parse(
+    string_list: &Vec<Vec<String>>,
+) -> Result<(HashMap<Key, Number>, Vec<Instruction>), ParseError> {
+    let [variables, operations] = string_list.as_slice() else {
+        return Err(ParseError::new("expected wire values then gates"));
+    };
+    return Ok((parse_variables(variables)?, parse_operations(operations)?));
 }
 
-fn do_operation(
-    first_arg: &Key,
-    second_arg: &Key,
-    store_in: &Key,
-    op: Operation,
-    state: &mut HashMap<Key, Number>,
-) {
-    let a: Number = *state
-        .get(first_arg)
-        .expect("can do operation should have already ran");
-    let b: Number = *state
-        .get(second_arg)
-        .expect("can do operation should have already ran");
-    let result = op.operate(a, b);
-    state.insert(store_in.clone(), result);
-}
-
-fn do_instructions(instructions: &Vec<Instruction>, state: &mut HashMap<Key, Number>) {
-    let possible: Vec<Instruction> = instructions
-        .iter()
-        .filter(|(a, b, _, _)| can_do_operation(a, b, &state))
-        .cloned()
-        .collect();
-    if possible.len() == 0 {
-        // Impossible to solve now
-        return;
+/// Every gate and given wire, checked for loops, gates that can't take two
+/// inputs, and wires that have a starting value and a gate, or neither.
+fn build_circuit(
+    variables: &HashMap<Key, Number>,
+    instructions: &Vec<Instruction>,
+) -> Result<Circuit, ParseError> {
+    let error = |e: circuitlib::CircuitError| ParseError::new(&e.to_string());
+    let mut builder = Circuit::builder();
+    for name in variables.keys() {
+        builder.input(name);
     }
-
-    let not_possible: Vec<Instruction> = instructions
-        .iter()
-        .filter(|(a, b, _, _)| !can_do_operation(a, b, &state))
-        .cloned()
-        .collect();
-    let required: Vec<Instruction> = not_possible
-        .iter()
-        .filter(|(_, _, store, _)| store.starts_with("z"))
-        .cloned()
-        .collect();
-    for (a, b, c, op) in possible {
-        info!(
-            "{:?} {:?} {:?} -> {:?}",
-            a.clone(),
-            b.clone(),
-            op,
-            c.clone()
-        );
-        do_operation(&a, &b, &c, op, state);
+    for (a, b, out, op) in instructions {
+        builder.gate(*op, &[a, b], out).map_err(error)?;
     }
-    if required.len() == 0 {
-        return;
+    let circuit = builder.build().map_err(error)?;
+    for wire in circuit.inputs() {
+        let name = circuit.name(wire);
+        if !variables.contains_key(name) {
+            return Err(ParseError::new(&format!(
+                "{} has no starting value and no gate drives it",
+                name
+            )));
+        }
     }
-    // Otherwise recurse
-    do_instructions(&not_possible, state);
-}
-
-fn get_number_in_letter(state: &HashMap<Key, Number>, key_starts_with: &str) -> Number {
-    let mut num = 0;
-    let mut keys: Vec<&Key> = state
-        .keys()
-        .filter(|k| k.starts_with(key_starts_with))
-        .collect();
-    keys.sort();
-    keys.reverse();
-    for k in keys {
-        num *= 2;
-        num += state.get(k).unwrap();
+    let mut given: Vec<&Key> = variables.keys().collect();
+    given.sort();
+    for name in given {
+        let wire = circuit.wire(name).map_err(error)?;
+        if circuit.driver(wire).is_some() {
+            return Err(ParseError::new(&format!(
+                "{} has a starting value but a gate drives it",
+                name
+            )));
+        }
     }
-    return num;
+    return Ok(circuit);
 }
 
-/// Run through all of the operations and get the number from the z registers.
+/// Check the initial wires are bits, and that the gates are ones `circuitlib`
+/// knows, with no loops and no wire driven by two of them. Every wire needs
+/// exactly one of a starting value or a gate.
 /// ```
-/// let vec1: Vec<Vec<String>> = vec![vec![
-///     "x00: 1",
-///     "x01: 0",
-///     "x02: 1",
-///     "x03: 1",
-///     "x04: 0",
-///     "y00: 1",
-///     "y01: 1",
-///     "y02: 1",
-///     "y03: 1",
-///     "y04: 1"].iter().map(|s| s.to_string()).collect(),
-///     vec!["ntg XOR fgs -> mjb",
-///     "y02 OR x01 -> tnw",
-///     "kwq OR kpj -> z05",
-///     "x00 OR x03 -> fst",
-///     "tgd XOR rvg -> z01",
-///     "vdt OR tnw -> bfw",
-///     "bfw AND frj -> z10",
-///     "ffh OR nrd -> bqk",
-///     "y00 AND y03 -> djm",
-///     "y03 OR y00 -> psh",
-///     "bqk OR frj -> z08",
-///     "tnw OR fst -> frj",
-///     "gnj AND tgd -> z11",
-///     "bfw XOR mjb -> z00",
-///     "x03 OR x00 -> vdt",
-///     "gnj AND wpb -> z02",
-///     "x04 AND y00 -> kjc",
-///     "djm OR pbm -> qhw",
-///     "nrd AND vdt -> hwm",
-///     "kjc AND fst -> rvg",
-///     "y04 OR y02 -> fgs",
-///     "y01 AND x02 -> pbm",
-///     "ntg OR kjc -> kwq",
-///     "psh XOR fgs -> tgd",
-///     "qhw XOR tgd -> z09",
-///     "pbm OR djm -> kpj",
-///     "x03 XOR y03 -> ffh",
-///     "x00 XOR y04 -> ntg",
-///     "bfw OR bqk -> z06",
-///     "nrd XOR fgs -> wpb",
-///     "frj XOR qhw -> z04",
-///     "bqk OR frj -> z07",
-///     "y03 OR x01 -> nrd",
-///     "hwm AND bqk -> z03",
-///     "tgd XOR rvg -> z12",
-///     "tnw OR pbm -> gnj",
-/// ].iter().map(|s| s.to_string()).collect()];
-/// assert_eq!(day24::puzzle_a(&vec1), 2024);
+/// let wires = vec!["x00: 1".to_string(), "y00: 0".to_string()];
+/// let good = vec![wires.clone(), vec!["x00 AND y00 -> z00".to_string()]];
+/// assert!(day24::check_input(&good).is_ok());
+/// let bad = vec![wires.clone(), vec!["x00 MAYBE y00 -> z00".to_string()]];
+/// assert!(day24::check_input(&bad).is_err());
+/// let nand = vec![wires.clone(), vec!["x00 NAND y00 -> z00".to_string()]];
+/// assert!(day24::check_input(&nand).is_ok());
+/// let looped = vec![wires.clone(), vec!["x00 AND b -> a".to_string(), "a OR y00 -> b".to_string()]];
+/// assert!(day24::check_input(&looped).is_err());
+/// let twice = vec![wires.clone(), vec!["x00 AND y00 -> z00".to_string(), "x00 OR y00 -> z00".to_string()]];
+/// assert!(day24::check_input(&twice).is_err());
+/// let undriven = vec![wires.clone(), vec!["x00 AND q -> z00".to_string()]];
+/// assert!(day24::check_input(&undriven).is_err());
+/// let mut given = wires;
+/// given.push("z00: 1".to_string());
+/// let given = vec![given, vec!["x00 AND y00 -> z00".to_string()]];
+/// assert!(day24::check_input(&given).is_err());
 /// ```
-pub fn puzzle_a(string_list: &Vec<Vec<String>>) -> Number {
-    let mut state = parse_variables(string_list.first().unwrap());
-    let instructions = parse_operations(string_list.last().unwrap());
-    do_instructions(&instructions, &mut state);
-    return get_number_in_letter(&state, "z");
+pub fn check_input(string_list: &Vec<Vec<String>>) -> Result<(), ParseError> {
+    let (variables, instructions) = parse(string_list)?;
+    return build_circuit(&variables, &instructions).map(|_| ());
+}
+
+/// Wires worth a look in a diagram: the ones `adder::repair` would swap,
+/// or where the circuit stops looking like an adder if that can't be fixed.
+fn suspicious(circuit: &Circuit) -> Vec<String> {
+    let mut repaired = circuit.clone();
+    return match adder::repair(&mut repaired, SWAPS) {
+        Ok(swaps) => swaps.into_iter().flat_map(|(a, b)| [a, b]).collect(),
+        Err(AdderError::Unrepairable(fault, _)) => vec![fault.wire],
+        Err(_) => vec![],
+    };
 }
 
-/// Do num_swaps to find the answer. I don't have good example input for this...
+/// The gates as a graphviz diagram, coloured by gate, with the wires part b
+/// would swap outlined in red.
 /// ```
-/// let vec1: Vec<Vec<String>> = vec![vec![
-///     "x00: 1",
-///     "x01: 0",
-///     "x02: 1",
-///     "x03: 1",
-///     "x04: 0",
-///     "y00: 1",
-///     "y01: 1",
-///     "y02: 1",
-///     "y03: 1",
-///     "y04: 1"].iter().map(|s| s.to_string()).collect(),
-///     vec!["ntg XOR fgs -> mjb",
-///     "y02 OR x01 -> tnw",
-///     "kwq OR kpj -> z05",
-///     "x00 OR x03 -> fst",
-///     "tgd XOR rvg -> z01",
-///     "vdt OR tnw -> bfw",
-///     "bfw AND frj -> z10",
-///     "ffh OR nrd -> bqk",
-///     "y00 AND y03 -> djm",
-///     "y03 OR y00 -> psh",
-///     "bqk OR frj -> z08",
-///     "tnw OR fst -> frj",
-///     "gnj AND tgd -> z11",
-///     "bfw XOR mjb -> z00",
-///     "x03 OR x00 -> vdt",
-///     "gnj AND wpb -> z02",
-///     "x04 AND y00 -> kjc",
-///     "djm OR pbm -> qhw",
-///     "nrd AND vdt -> hwm",
-///     "kjc AND fst -> rvg",
-///     "y04 OR y02 -> fgs",
-///     "y01 AND x02 -> pbm",
-///     "ntg OR kjc -> kwq",
-///     "psh XOR fgs -> tgd",
-///     "qhw XOR tgd -> z09",
-///     "pbm OR djm -> kpj",
-///     "x03 XOR y03 -> ffh",
-///     "x00 XOR y04 -> ntg",
-///     "bfw OR bqk -> z06",
-///     "nrd XOR fgs -> wpb",
-///     "frj XOR qhw -> z04",
-///     "bqk OR frj -> z07",
-///     "y03 OR x01 -> nrd",
-///     "hwm AND bqk -> z03",
-///     "tgd XOR rvg -> z12",
-///     "tnw OR pbm -> gnj",
-/// ].iter().map(|s| s.to_string()).collect()];
-/// assert_eq!(day24::puzzle_b(&vec1, 6), "ffh,mjb,tgd,wpb,z02,z03,z05,z06,z07,z08,z10,z11");
-pub fn puzzle_b(string_list: &Vec<Vec<String>>, num_swaps: usize) -> String {
-    let instructions: Vec<(String, String, String, Operation)> =
-        parse_operations(string_list.last().unwrap());
+/// let fixture = filelib::load_fixture(filelib::fixtures_dir!(), "day24-2");
+/// let dot = day24::to_dot(&fixture.groups()).unwrap();
+/// assert!(dot.contains("\"z02\" [label=\"z02\\nOR\", gate=\"OR\", fillcolor=palegreen, color=red, penwidth=3];"));
+/// ```
+pub fn to_dot(string_list: &Vec<Vec<String>>) -> Result<String, ParseError> {
+    let (variables, instructions) = parse(string_list)?;
+    let circuit = build_circuit(&variables, &instructions)?;
+    let highlight = suspicious(&circuit);
+    let highlight: Vec<&str> = highlight.iter().map(|s| s.as_str()).collect();
+    return Ok(dot::to_dot(&circuit, &highlight));
+}
 
-    let mut successful_swaps: Vec<Key> = vec![];
+/// The gates as a structural Verilog module called `day24`.
+pub fn to_verilog(string_list: &Vec<Vec<String>>) -> Result<String, ParseError> {
+    let (variables, instructions) = parse(string_list)?;
+    let circuit = build_circuit(&variables, &instructions)?;
+    return Ok(verilog::to_verilog(&circuit, "day24"));
+}
 
-    // look for the following nodes that are in error, and hope we just end up with the correct amount
-    // The other way I thought of only would take 3000 years to run
-    for (in_a, in_b, out, op) in instructions.clone() {
-        let start_x_a = in_a.starts_with("x");
-        let start_x_b = in_b.starts_with("x");
-        let start_y_a = in_a.starts_with("y");
-        let start_y_b = in_b.starts_with("y");
-        let start_z = out.starts_with("z");
-        if op == Operation::And && ((start_x_a && start_y_b) || (start_x_b && start_y_a)) {
-            if start_z && out != "z00" {
-                // problem node, add this output.
-                // This is essentially a misplaced adder
-                // Look up how an adder circuit works
-                successful_swaps.push(out.clone());
-                info!(
-                    "Identified issue rule 1 on {:?} {:?} {:?} -> {:?}",
-                    in_a.clone(),
-                    op,
-                    in_b.clone(),
-                    out.clone()
-                );
-                continue;
-            }
-            // We want to drop down in logic for other ruels later
-        } else if op == Operation::Xor
-            && !start_x_a
-            && !start_y_b
-            && !start_z
-            && !start_y_a
-            && !start_x_b
-        {
-            // A XOR must go onto an X and y, or output to a z
-            successful_swaps.push(out.clone());
-            info!(
-                "Identified issue rule 2 on {:?} {:?} {:?} -> {:?}",
-                in_a.clone(),
-                op,
-                in_b.clone(),
-                out.clone()
-            );
-            continue;
-        } else if op != Operation::Xor && start_z && out != "z45" {
-            // Other than z45 that zs must be on a Xor, you can find this by manually inspecting the circuit diagram as a graph.
-            // z45 happens to be special in this input - becaues its the final output.
-            successful_swaps.push(out.clone());
-            info!(
-                "Identified issue rule 3 on {:?} {:?} {:?} -> {:?}",
-                in_a.clone(),
-                op,
-                in_b.clone(),
-                out.clone()
-            );
-            continue;
-        }
-        if op == Operation::And
-            && ((start_x_a && start_y_b && in_a != "x00")
-                || (start_x_b && start_y_a && in_b != "x00"))
-        {
-            let mut found = false;
-            // the intermediate result of an x AND y should be an operand of one of the OR rules
-            for (pos_in_a, pos_in_b, _, op) in instructions.iter() {
-                if *op == Operation::Or && (out == pos_in_a.clone() || out == pos_in_b.clone()) {
-                    found = true;
-                    break;
-                }
-            }
-            if !found {
-                info!(
-                    "Identified issue rule 4 on {:?} {:?} {:?} -> {:?}",
-                    in_a.clone(),
-                    op,
-                    in_b.clone(),
-                    out.clone()
-                );
-                successful_swaps.push(out.clone());
-            }
-        }
-        // the intermediate result of 0 XOR y should be an operand of one of the AND rules
-        else if op == Operation::Xor
-            && ((start_x_a && start_y_b && in_a != "x00")
-                || (start_x_b && start_y_a && in_b != "x00"))
-        {
-            let mut found = false;
-            // the intermediate result of an x AND y should be an operand of one of the OR rules
-            for (pos_in_a, pos_in_b, _, op) in instructions.iter() {
-                if *op == Operation::And && (out == pos_in_a.clone() || out == pos_in_b.clone()) {
-                    found = true;
-                    break;
-                }
-            }
-            if !found {
-                info!(
-                    "Identified issue rule 5 on {:?} {:?} {:?} -> {:?}",
-                    in_a.clone(),
-                    op,
-                    in_b.clone(),
-                    out.clone()
-                );
-                successful_swaps.push(out.clone());
-            }
-        }
+/// Turn a DOT diagram or Verilog module, say one edited after `to_dot`,
+/// back into puzzle input. Neither holds the starting values, so those are
+/// `wires`, the first group of an input.
+/// ```
+/// let fixture = filelib::load_fixture(filelib::fixtures_dir!(), "day24-2");
+/// let groups = fixture.groups();
+/// for netlist in [day24::to_dot(&groups).unwrap(), day24::to_verilog(&groups).unwrap()] {
+///     let input = day24::from_netlist(&netlist, &groups[0]).unwrap();
+///     assert_eq!(day24::puzzle_a(&input), day24::puzzle_a(&groups));
+/// }
+/// let lut = "module m(x00, y00, z00);\n  assign z00 = 4'h6 >> {y00, x00};\nendmodule";
+/// let input = day24::from_netlist(lut, &groups[0]).unwrap();
+/// assert_eq!(input[1], vec!["x00 LUT2:6 y00 -> z00"]);
+/// let inverter = "module m(x00, z00);\n  not g0(z00, x00);\nendmodule";
+/// assert!(day24::from_netlist(inverter, &groups[0]).is_err());
+/// ```
+pub fn from_netlist(netlist: &str, wires: &Vec<String>) -> Result<Vec<Vec<String>>, ParseError> {
+    let circuit = if netlist.contains("digraph") {
+        dot::parse(netlist)
+    } else {
+        verilog::parse(netlist)
+    }
+    .map_err(|e| ParseError::new(&e.to_string()))?;
+    let mut lines = vec![];
+    for gate in circuit.topological() {
+        let output = circuit.name(gate.output);
+        let [a, b] = gate.inputs[..] else {
+            return Err(ParseError::new(&format!(
+                "{} is driven by a gate with {} inputs, but day24 gates have two",
+                output,
+                gate.inputs.len()
+            )));
+        };
+        lines.push(format!(
+            "{} {} {} -> {}",
+            circuit.name(a),
+            gate.gate,
+            circuit.name(b),
+            output
+        ));
     }
+    let input = vec![wires.clone(), lines];
+    check_input(&input)?;
+    return Ok(input);
+}
+
+/// Run through all of the operations and get the number from the z registers.
+#[doc = filelib::fixture_doc!("day24-1", a, |fixture| day24::puzzle_a(&fixture.groups()))]
+pub fn puzzle_a(string_list: &Vec<Vec<String>>) -> Number {
+    let (variables, instructions) = parse(string_list).unwrap();
+    let circuit = build_circuit(&variables, &instructions).unwrap();
+    let inputs: HashMap<Key, bool> = variables
+        .into_iter()
+        .map(|(name, value)| (name, value == 1))
+        .collect();
+    let simulation = Simulation::new(&circuit, &inputs).unwrap();
+    debug!("Evaluated {} gates", circuit.gates().len());
+    return simulation.number("z");
+}
 
-    if successful_swaps.len() != num_swaps * 2 {
-        error!("Unexpected number of swaps {}", successful_swaps.len());
+/// Find the `num_swaps` pairs of gate outputs that stop the circuit being an
+/// adder, and list the wires in them.
+#[doc = filelib::fixture_doc!("day24-2", b, |fixture| day24::puzzle_b(&fixture.groups(), 2))]
+pub fn puzzle_b(string_list: &Vec<Vec<String>>, num_swaps: usize) -> String {
+    let (variables, instructions) = parse(string_list).unwrap();
+    let mut circuit = build_circuit(&variables, &instructions).unwrap();
+    let swaps = adder::repair(&mut circuit, num_swaps).unwrap();
+    adder::confirm(&circuit).unwrap();
+    if swaps.len() != num_swaps {
+        error!("Only needed {} of {} swaps", swaps.len(), num_swaps);
     }
 
-    successful_swaps.sort();
-    return successful_swaps.join(",");
+    let mut wires: Vec<Key> = vec![];
+    for (a, b) in swaps {
+        debug!("Swapped {} and {}", a, b);
+        wires.push(a);
+        wires.push(b);
+    }
+    wires.sort();
+    return wires.join(",");
 }
@@ -3,14 +3,14 @@ use day24::puzzle_b;
 use day24::{load, split_lines_by_blanks};
 
 fn main() {
-    colog::init();
+    tracelib::init();
+    let _day = tracelib::day_span(env!("CARGO_PKG_NAME")).entered();
     let filename = "input";
-    let lines = load(filename);
-    let groups = split_lines_by_blanks(&lines);
+    let groups = tracelib::parse_span().in_scope(|| split_lines_by_blanks(&load(filename)));
 
-    let value = puzzle_a(&groups);
+    let value = tracelib::part_span("a").in_scope(|| puzzle_a(&groups));
     println!("Answer to 1st question: {}", value);
 
-    let value_b = puzzle_b(&groups, 4);
+    let value_b = tracelib::part_span("b").in_scope(|| puzzle_b(&groups, 4));
     println!("Answer to 2nd question: {}", value_b);
 }
@@ -0,0 +1,225 @@
+//! Slow versions of the puzzles, to check the fast ones against.
+
+use crate::{parse_operations, Instruction};
+use std::collections::HashMap;
+
+/// Run the circuit on `x` and `y`, reading the answer off the z wires.
+///
+/// `None` if any wire never gets a value, because of a loop or a missing input.
+fn add(gates: &Vec<Instruction>, bits: usize, x: u64, y: u64) -> Option<u64> {
+    let mut values: HashMap<&str, u64> = HashMap::new();
+    let names: Vec<(String, String)> = (0..bits)
+        .map(|i| (format!("x{:02}", i), format!("y{:02}", i)))
+        .collect();
+    for (i, (x_name, y_name)) in names.iter().enumerate() {
+        values.insert(x_name, (x >> i) & 1);
+        values.insert(y_name, (y >> i) & 1);
+    }
+
+    let mut progress = true;
+    while progress {
+        progress = false;
+        for (a, b, out, op) in gates {
+            if values.contains_key(out.as_str()) {
+                continue;
+            }
+            if let (Some(&a), Some(&b)) = (values.get(a.as_str()), values.get(b.as_str())) {
+                values.insert(out, op.evaluate(&[a == 1, b == 1]) as u64);
+                progress = true;
+            }
+        }
+    }
+
+    let mut sum = 0;
+    for (_, _, out, _) in gates {
+        let bit = values.get(out.as_str())?;
+        if let Some(index) = out.strip_prefix('z') {
+            sum |= bit << index.parse::<u64>().ok()?;
+        }
+    }
+    return Some(sum);
+}
+
+/// Try every pair of inputs, so only practical for a handful of bits.
+fn is_adder(gates: &Vec<Instruction>, bits: usize) -> bool {
+    for x in 0..1 << bits {
+        for y in 0..1 << bits {
+            if add(gates, bits, x, y) != Some(x + y) {
+                return false;
+            }
+        }
+    }
+    return true;
+}
+
+fn choose_swaps(
+    gates: &mut Vec<Instruction>,
+    bits: usize,
+    first: usize,
+    num_swaps: usize,
+    swapped: &mut Vec<usize>,
+    result: &mut Vec<String>,
+) {
+    if num_swaps == 0 {
+        if is_adder(gates, bits) {
+            let mut names: Vec<String> = swapped.iter().map(|&i| gates[i].2.clone()).collect();
+            names.sort();
+            result.push(names.join(","));
+        }
+        return;
+    }
+    for i in first..gates.len() {
+        if swapped.contains(&i) {
+            continue;
+        }
+        for j in i + 1..gates.len() {
+            if swapped.contains(&j) {
+                continue;
+            }
+            let out_i = gates[i].2.clone();
+            gates[i].2 = gates[j].2.clone();
+            gates[j].2 = out_i;
+            swapped.push(i);
+            swapped.push(j);
+            choose_swaps(gates, bits, i + 1, num_swaps - 1, swapped, result);
+            swapped.pop();
+            swapped.pop();
+            let out_i = gates[i].2.clone();
+            gates[i].2 = gates[j].2.clone();
+            gates[j].2 = out_i;
+        }
+    }
+}
+
+/// Every way of swapping `num_swaps` pairs of gate outputs that turns the gates into an adder.
+///
+/// Each is formatted like the answer to part b. This tries every combination of
+/// swaps against every input, so keep the circuit to a few bits.
+/// ```
+/// let gates = vec![
+///     "x00 XOR y00 -> c00".to_string(),
+///     "x00 AND y00 -> z00".to_string(),
+///     "x01 XOR y01 -> s01".to_string(),
+///     "s01 XOR c00 -> z01".to_string(),
+///     "x01 AND y01 -> a01".to_string(),
+///     "s01 AND c00 -> b01".to_string(),
+///     "a01 OR b01 -> z02".to_string(),
+/// ];
+/// let input = vec![vec!["x00: 0".to_string()], gates];
+/// assert_eq!(day24::reference::find_swaps(&input, 1), vec!["c00,z00"]);
+/// ```
+pub fn find_swaps(string_list: &Vec<Vec<String>>, num_swaps: usize) -> Vec<String> {
+    let mut gates = parse_operations(string_list.last().unwrap()).unwrap();
+    let bits = gates
+        .iter()
+        .flat_map(|(a, b, _, _)| [a, b])
+        .filter_map(|wire| wire.strip_prefix('x')?.parse::<usize>().ok())
+        .max()
+        .map_or(0, |highest| highest + 1);
+    let mut result = vec![];
+    choose_swaps(&mut gates, bits, 0, num_swaps, &mut vec![], &mut result);
+    result.sort();
+    return result;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use circuitlib::Gate;
+    use proptest::prelude::*;
+
+    /// A working ripple carry adder, carries are `c`, the last one is the top z.
+    fn adder(bits: usize) -> Vec<Instruction> {
+        let gate = |a: String, b: String, out: String, op: Gate| (a, b, out, op);
+        let carry = |i: usize| {
+            if i + 1 == bits {
+                return format!("z{:02}", bits);
+            }
+            return format!("c{:02}", i);
+        };
+        let mut gates = vec![
+            gate("x00".into(), "y00".into(), "z00".into(), Gate::Xor),
+            gate("x00".into(), "y00".into(), carry(0), Gate::And),
+        ];
+        for i in 1..bits {
+            let (x, y) = (format!("x{:02}", i), format!("y{:02}", i));
+            let z = format!("z{:02}", i);
+            let sum = format!("s{:02}", i);
+            let both = format!("a{:02}", i);
+            let carried = format!("b{:02}", i);
+            gates.push(gate(x.clone(), y.clone(), sum.clone(), Gate::Xor));
+            gates.push(gate(sum.clone(), carry(i - 1), z, Gate::Xor));
+            gates.push(gate(x, y, both.clone(), Gate::And));
+            gates.push(gate(sum, carry(i - 1), carried.clone(), Gate::And));
+            gates.push(gate(both, carried, carry(i), Gate::Or));
+        }
+        return gates;
+    }
+
+    /// An adder with some gate outputs swapped so it no longer adds, and how many swaps that took.
+    ///
+    /// These are the same kinds of swap as the real inputs.
+    /// Each is inside one full adder, below the final carry, and no bit has two.
+    /// A z is swapped with another wire from its adder, or x XOR y with x AND y.
+    /// The swaps never make a loop.
+    fn broken_adder_strategy() -> impl Strategy<Value = (Vec<Vec<String>>, usize)> {
+        // Offsets into a full adder: x XOR y, z XOR, x AND y, carry AND, carry OR
+        let swap = prop::sample::select(vec![(1, 0), (1, 2), (1, 3), (1, 4), (0, 2)]);
+        return (3..5usize)
+            .prop_flat_map(move |bits| {
+                let count = adder(bits).len();
+                return (
+                    Just(bits),
+                    prop::sample::subsequence((1..bits - 1).collect::<Vec<usize>>(), 1..bits - 1),
+                    prop::collection::vec(swap.clone(), 2),
+                    prop::collection::vec(any::<bool>(), count),
+                );
+            })
+            .prop_filter_map(
+                "swaps must break the adder",
+                |(bits, swapped_bits, swaps, flips)| {
+                    let mut gates = adder(bits);
+                    for (bit, (i, j)) in swapped_bits.iter().zip(swaps.iter()) {
+                        // Bit 0 is two gates, then five for each full adder
+                        let (i, j) = (2 + (bit - 1) * 5 + i, 2 + (bit - 1) * 5 + j);
+                        let out_i = gates[i].2.clone();
+                        gates[i].2 = gates[j].2.clone();
+                        gates[j].2 = out_i;
+                    }
+                    // Part a has to work on it, so no loops
+                    if add(&gates, bits, 0, 0).is_none() || is_adder(&gates, bits) {
+                        return None;
+                    }
+                    let lines = gates
+                        .into_iter()
+                        .zip(flips)
+                        .map(|((a, b, out, op), flip)| {
+                            let (a, b) = if flip { (b, a) } else { (a, b) };
+                            return format!("{} {} {} -> {}", a, op, b, out);
+                        })
+                        .collect::<Vec<String>>();
+                    // Every input needs a value, though part b never reads them
+                    let values = (0..bits)
+                        .flat_map(|i| [format!("x{:02}: 0", i), format!("y{:02}: 0", i)])
+                        .collect::<Vec<String>>();
+                    return Some((values, lines, swapped_bits.len()));
+                },
+            )
+            .prop_flat_map(|(values, lines, num_swaps)| {
+                (Just(values), Just(lines).prop_shuffle(), Just(num_swaps))
+            })
+            .prop_map(|(values, lines, num_swaps)| (vec![values, lines], num_swaps));
+    }
+
+    proptest! {
+        // Brute forcing two swaps is slow, so fewer cases than usual
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn prop_repair_finds_the_swaps((input, num_swaps) in broken_adder_strategy()) {
+            let fast = crate::puzzle_b(&input, num_swaps);
+            let slow = find_swaps(&input, num_swaps);
+            prop_assert!(slow.contains(&fast), "repair found {} but brute force found {:?}", fast, slow);
+        }
+    }
+}
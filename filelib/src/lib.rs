@@ -1,4 +1,7 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use std::fs;
+use std::str::FromStr;
 
 /// Load the "input" file
 pub fn load(filename: &str) -> String {
@@ -7,7 +10,7 @@ pub fn load(filename: &str) -> String {
 }
 
 /// remove blank lines
-fn remove_blanks(text_input: &str) -> Vec<String> {
+pub fn remove_blanks(text_input: &str) -> Vec<String> {
     return text_input
         .lines()
         .filter(|&s| !s.is_empty() && !s.trim().is_empty())
@@ -128,9 +131,230 @@ pub fn parse_path_to_coords(line: &str) -> Vec<(i32, i32)> {
     return vec_version;
 }
 
+/// Puzzle input that a day could not make sense of.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(reason: &str) -> ParseError {
+        return ParseError {
+            reason: reason.to_string(),
+        };
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "invalid input: {}", self.reason);
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse a number, ignoring surrounding whitespace.
+/// ```
+/// assert_eq!(filelib::parse_number::<u32>(" 12 "), Ok(12));
+/// assert!(filelib::parse_number::<u8>("300").is_err());
+/// assert!(filelib::parse_number::<i32>("").is_err());
+/// ```
+pub fn parse_number<T: FromStr>(s: &str) -> Result<T, ParseError> {
+    return s
+        .trim()
+        .parse::<T>()
+        .map_err(|_| ParseError::new(&format!("{:?} is not a number", s)));
+}
+
+/// Split a line in two at the first `separator`.
+/// ```
+/// assert_eq!(filelib::split_pair("47|53", "|"), Ok(("47", "53")));
+/// assert!(filelib::split_pair("4753", "|").is_err());
+/// ```
+pub fn split_pair<'a>(s: &'a str, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+    return s
+        .split_once(separator)
+        .ok_or_else(|| ParseError::new(&format!("{:?} has no {:?}", s, separator)));
+}
+
+/// Width and height of lines that should form a rectangle, in characters.
+/// ```
+/// let lines = vec!["..#".to_string(), "#..".to_string()];
+/// assert_eq!(filelib::grid_size(&lines), Ok((3, 2)));
+/// let ragged = vec!["..#".to_string(), "#.".to_string()];
+/// assert!(filelib::grid_size(&ragged).is_err());
+/// assert!(filelib::grid_size(&vec![]).is_err());
+/// ```
+pub fn grid_size(lines: &Vec<String>) -> Result<(usize, usize), ParseError> {
+    let Some(first) = lines.first() else {
+        return Err(ParseError::new("no grid"));
+    };
+    let width = first.chars().count();
+    if width == 0 {
+        return Err(ParseError::new("grid has no columns"));
+    }
+    for line in lines {
+        if line.chars().count() != width {
+            return Err(ParseError::new(&format!(
+                "{:?} is not {} characters wide",
+                line, width
+            )));
+        }
+    }
+    return Ok((width, lines.len()));
+}
+
+/// An example input along with the answers it should give.
+///
+/// Fixtures live in the top level `examples` folder as a pair of files,
+/// `day14-1.txt` holding the input and `day14-1.expected` holding lines like
+/// ```text
+/// a: 12
+/// b: 5
+/// params: 7 11
+/// ```
+/// `params` are the extra arguments a day needs for the example, such as a
+/// smaller grid. `params a:` and `params b:` override them for one part.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Fixture {
+    pub name: String,
+    pub text: String,
+    answers: HashMap<String, String>,
+    params: HashMap<String, Vec<usize>>,
+}
+
+impl Fixture {
+    /// ```
+    /// let fixture = filelib::Fixture::new("day01-1", "3 4\n\n4 3\n", "# comment\na: 11\nparams b: 2 3\n");
+    /// assert_eq!(fixture.lines(), vec!["3 4", "4 3"]);
+    /// assert_eq!(fixture.answer("a"), Some("11"));
+    /// assert_eq!(fixture.answer("b"), None);
+    /// assert_eq!(fixture.params("a"), None);
+    /// assert_eq!(fixture.params("b"), Some(vec![2, 3]));
+    /// ```
+    pub fn new(name: &str, text: &str, expected: &str) -> Fixture {
+        let mut answers = HashMap::new();
+        let mut params = HashMap::new();
+        for line in remove_blanks(expected) {
+            if line.trim().starts_with("#") {
+                continue;
+            }
+            let (key, value) = line.split_once(":").unwrap();
+            let key = key.trim();
+            let value = value.trim();
+            if let Some(part) = key.strip_prefix("params") {
+                let numbers = value
+                    .split_whitespace()
+                    .map(|v| v.parse::<usize>().unwrap())
+                    .collect();
+                params.insert(part.trim().to_string(), numbers);
+            } else {
+                answers.insert(key.to_string(), value.to_string());
+            }
+        }
+        return Fixture {
+            name: name.to_string(),
+            text: text.to_string(),
+            answers: answers,
+            params: params,
+        };
+    }
+
+    /// The input without blank lines, as from `load_no_blanks`
+    pub fn lines(&self) -> Vec<String> {
+        return remove_blanks(&self.text);
+    }
+
+    /// The input split by blank lines, as from `split_lines_by_blanks`
+    pub fn groups(&self) -> Vec<Vec<String>> {
+        return split_lines_by_blanks(&self.text);
+    }
+
+    /// Expected answer for part `a` or `b`, if the example has one.
+    pub fn answer(&self, part: &str) -> Option<&str> {
+        return self.answers.get(part).map(|s| s.as_str());
+    }
+
+    /// Extra arguments for a part, if the example needs any.
+    pub fn params(&self, part: &str) -> Option<Vec<usize>> {
+        return self
+            .params
+            .get(part)
+            .or_else(|| self.params.get(""))
+            .cloned();
+    }
+}
+
+/// Load `<name>.txt` and `<name>.expected` from a fixtures folder.
+///
+/// A missing `.expected` file just means there are no known answers.
+pub fn load_fixture(dir: &str, name: &str) -> Fixture {
+    let base = std::path::Path::new(dir).join(name);
+    let text = fs::read_to_string(base.with_extension("txt")).expect("Fixture not found");
+    let expected = fs::read_to_string(base.with_extension("expected")).unwrap_or_default();
+    return Fixture::new(name, &text, &expected);
+}
+
+/// Path to the shared fixtures folder, from a crate next to it.
+#[macro_export]
+macro_rules! fixtures_dir {
+    () => {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../examples")
+    };
+}
+
+/// Build a doc-test that checks a fixture's answer, for use as `#[doc = ...]`.
+///
+/// The closure style argument names the fixture for use in the call.
+/// ```ignore
+/// /// Calculate the safety factor
+/// #[doc = filelib::fixture_doc!("day14-1", a, |fixture| day14::puzzle_a(&fixture.lines(), 7, 11))]
+/// pub fn puzzle_a(string_list: &Vec<String>, height: usize, width: usize) -> usize {
+/// ```
+#[macro_export]
+macro_rules! fixture_doc {
+    ($name:literal, $part:ident, |$fixture:ident| $call:expr) => {
+        concat!(
+            "```\n",
+            "let ",
+            stringify!($fixture),
+            " = filelib::load_fixture(filelib::fixtures_dir!(), \"",
+            $name,
+            "\");\n",
+            "assert_eq!((",
+            stringify!($call),
+            ").to_string(), ",
+            stringify!($fixture),
+            ".answer(\"",
+            stringify!($part),
+            "\").unwrap());\n",
+            "```"
+        )
+    };
+}
+
+/// Build a unit test that checks a fixture's answer.
+/// ```ignore
+/// filelib::fixture_test!(test_second_example, "day16-2", b, |fixture| puzzle_b(&fixture.lines()));
+/// ```
+#[macro_export]
+macro_rules! fixture_test {
+    ($test:ident, $name:literal, $part:ident, |$fixture:ident| $call:expr) => {
+        #[test]
+        fn $test() {
+            let $fixture = $crate::load_fixture($crate::fixtures_dir!(), $name);
+            assert_eq!(
+                ($call).to_string(),
+                $fixture.answer(stringify!($part)).unwrap()
+            );
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_remove_blanks() {
@@ -154,4 +378,43 @@ mod tests {
     fn test_parse_line_to_coords() {
         assert_eq!(parse_line_to_linecoords("6,4 -> 2,0"), (6, 4, 2, 0));
     }
+
+    #[test]
+    fn test_fixture_params_fall_back() {
+        let fixture = Fixture::new("day20-1", "", "a: 44\nparams: 100\nparams b: 50\n");
+        assert_eq!(fixture.params("a"), Some(vec![100]));
+        assert_eq!(fixture.params("b"), Some(vec![50]));
+    }
+
+    /// Text made of short lines, some blank or only spaces.
+    fn text_strategy() -> impl Strategy<Value = String> {
+        return prop::collection::vec("[ a-c#.]{0,4}", 0..20).prop_map(|lines| lines.join("\n"));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_groups_have_no_blank_lines(text in text_strategy()) {
+            for group in split_lines_by_blanks(&text) {
+                prop_assert!(!group.is_empty());
+                prop_assert!(group.iter().all(|line| !line.trim().is_empty()));
+            }
+        }
+
+        #[test]
+        fn prop_groups_keep_every_line_in_order(text in text_strategy()) {
+            let flat: Vec<String> = split_lines_by_blanks(&text).into_iter().flatten().collect();
+            prop_assert_eq!(flat, remove_blanks(&text));
+        }
+
+        #[test]
+        fn prop_groups_round_trip(text in text_strategy()) {
+            let groups = split_lines_by_blanks(&text);
+            let joined = groups
+                .iter()
+                .map(|group| group.join("\n"))
+                .collect::<Vec<String>>()
+                .join("\n\n");
+            prop_assert_eq!(split_lines_by_blanks(&joined), groups);
+        }
+    }
 }
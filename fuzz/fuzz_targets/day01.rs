@@ -0,0 +1,8 @@
+#![no_main]
+
+use filelib::remove_blanks;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+    let _ = day01::check_input(&remove_blanks(data));
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use filelib::split_lines_by_blanks;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+    let _ = day19::check_input(&split_lines_by_blanks(data));
+});
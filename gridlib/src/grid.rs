@@ -369,6 +369,7 @@ impl<T: Copy + GridPrintable> Grid<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     #[should_panic]
@@ -695,4 +696,54 @@ mod tests {
         let strings = grid.grid_strings_with_overlay(overlay).join("\n");
         assert_eq!(strings, "+---+\n@@..|\n|@@.|\n|...|\n+--@+");
     }
+
+    /// A grid of up to 8x8 small numbers.
+    fn grid_strategy() -> impl Strategy<Value = Grid<u8>> {
+        return (1usize..8, 1usize..8).prop_flat_map(|(width, height)| {
+            prop::collection::vec(any::<u8>(), width * height)
+                .prop_map(move |values| Grid::new(width, height, values))
+        });
+    }
+
+    /// Equal length lines of printable characters.
+    fn lines_strategy() -> impl Strategy<Value = Vec<String>> {
+        return (1usize..8, 1usize..8).prop_flat_map(|(width, height)| {
+            prop::collection::vec(
+                prop::collection::vec(prop::char::range('!', '~'), width)
+                    .prop_map(|chars| chars.into_iter().collect::<String>()),
+                height,
+            )
+        });
+    }
+
+    proptest! {
+        #[test]
+        fn prop_four_rotations_are_identity(grid in grid_strategy()) {
+            let mut rotated = grid.clone();
+            for _ in 0..4 {
+                rotated.rotate_clockwise();
+            }
+            prop_assert_eq!(rotated, grid);
+        }
+
+        #[test]
+        fn prop_rotation_swaps_sides(grid in grid_strategy()) {
+            let mut rotated = grid.clone();
+            rotated.rotate_clockwise();
+            prop_assert_eq!(rotated.get_width(), grid.get_height());
+            prop_assert_eq!(rotated.get_height(), grid.get_width());
+            // The top left corner ends up top right
+            let corner = grid.get_value(GridCoordinate::new(0, 0));
+            let moved = rotated.get_value(GridCoordinate::new(rotated.get_width() - 1, 0));
+            prop_assert_eq!(corner, moved);
+        }
+
+        #[test]
+        fn prop_grid_strings_round_trip(lines in lines_strategy()) {
+            let width = lines[0].len();
+            let values: Vec<char> = lines.iter().flat_map(|line| line.chars()).collect();
+            let grid = Grid::new(width, lines.len(), values);
+            prop_assert_eq!(grid.grid_strings(), lines);
+        }
+    }
 }
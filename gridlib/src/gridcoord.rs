@@ -142,6 +142,7 @@ pub type GridCoordinateInf64 = GridCoordinateInf<i64>;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
     use std::cmp::Ordering;
 
     #[test]
@@ -257,4 +258,54 @@ mod tests {
         cur = cur.move_dir(Direction::SOUTHWEST);
         assert_eq!(cur, GridCoordinateInf64::new(0, 0));
     }
+
+    fn coord_strategy() -> impl Strategy<Value = GridCoordinate> {
+        // Small values so equal coordinates come up often
+        return (0usize..4, 0usize..4).prop_map(|(x, y)| GridCoordinate::new(x, y));
+    }
+
+    fn coord_inf_strategy() -> impl Strategy<Value = GridCoordinateInf64> {
+        return (-2i64..2, -2i64..2).prop_map(|(x, y)| GridCoordinateInf64::new(x, y));
+    }
+
+    /// `cmp` agrees with `==` and `partial_cmp`, and flips when swapped.
+    fn check_consistent<T: Ord + std::fmt::Debug>(a: &T, b: &T) -> Result<(), TestCaseError> {
+        prop_assert_eq!(a.cmp(b) == Ordering::Equal, a == b);
+        prop_assert_eq!(a.partial_cmp(b), Some(a.cmp(b)));
+        prop_assert_eq!(a.cmp(b), b.cmp(a).reverse());
+        return Ok(());
+    }
+
+    fn check_transitive<T: Ord>(a: &T, b: &T, c: &T) -> Result<(), TestCaseError> {
+        if a <= b && b <= c {
+            prop_assert!(a <= c);
+        }
+        return Ok(());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_coord_order_total(a in coord_strategy(), b in coord_strategy(), c in coord_strategy()) {
+            check_consistent(&a, &b)?;
+            check_transitive(&a, &b, &c)?;
+        }
+
+        #[test]
+        fn prop_coord_inf_order_total(
+            a in coord_inf_strategy(),
+            b in coord_inf_strategy(),
+            c in coord_inf_strategy()
+        ) {
+            check_consistent(&a, &b)?;
+            check_transitive(&a, &b, &c)?;
+        }
+
+        #[test]
+        fn prop_coord_sort_is_reading_order(mut coords in prop::collection::vec(coord_strategy(), 0..10)) {
+            coords.sort();
+            for pair in coords.windows(2) {
+                prop_assert!((pair[0].y, pair[0].x) <= (pair[1].y, pair[1].x));
+            }
+        }
+    }
 }
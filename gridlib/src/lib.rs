@@ -16,3 +16,9 @@ pub use crate::grid::GridPrintable;
 pub use crate::grid::GridRotation;
 pub use crate::grid::GridTraversable;
 pub use crate::grid::SimpleGridOverlay;
+
+mod search;
+
+pub use crate::search::distances;
+pub use crate::search::flood_fill;
+pub use crate::search::shortest_path;
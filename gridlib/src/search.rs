@@ -0,0 +1,140 @@
+use crate::grid::{Grid, GridTraversable};
+use crate::gridcoord::GridCoordinate;
+
+use std::collections::{HashMap, VecDeque};
+
+/// Steps from `start` to every cell reachable through cardinal moves onto
+/// cells where `passable` holds. `start` is always included at 0.
+pub fn distances<T, F>(
+    grid: &Grid<T>,
+    start: GridCoordinate,
+    passable: F,
+) -> HashMap<GridCoordinate, usize>
+where
+    T: Copy,
+    F: Fn(T) -> bool,
+{
+    let mut result = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(cur) = queue.pop_front() {
+        let steps = result[&cur];
+        for next in grid.get_adjacent_coordinates(cur) {
+            if result.contains_key(&next) || !passable(grid.get_value(next).unwrap()) {
+                continue;
+            }
+            result.insert(next, steps + 1);
+            queue.push_back(next);
+        }
+    }
+    return result;
+}
+
+/// A shortest path from `start` to `end` moving in cardinal directions,
+/// including both ends, or `None` if `end` can't be reached.
+/// ```
+/// use gridlib::{shortest_path, Grid, GridCoordinate};
+/// let grid = Grid::new(3, 2, "..#...".chars().collect());
+/// let path = shortest_path(&grid, GridCoordinate::new(0, 0), GridCoordinate::new(2, 1), |c| c != '#');
+/// assert_eq!(path.unwrap().len(), 4);
+/// ```
+pub fn shortest_path<T, F>(
+    grid: &Grid<T>,
+    start: GridCoordinate,
+    end: GridCoordinate,
+    passable: F,
+) -> Option<Vec<GridCoordinate>>
+where
+    T: Copy,
+    F: Fn(T) -> bool,
+{
+    let mut previous: HashMap<GridCoordinate, GridCoordinate> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(cur) = queue.pop_front() {
+        if cur == end {
+            let mut path = vec![end];
+            while let Some(&prev) = previous.get(path.last().unwrap()) {
+                path.push(prev);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in grid.get_adjacent_coordinates(cur) {
+            if next == start || previous.contains_key(&next) {
+                continue;
+            }
+            if !passable(grid.get_value(next).unwrap()) {
+                continue;
+            }
+            previous.insert(next, cur);
+            queue.push_back(next);
+        }
+    }
+    return None;
+}
+
+/// Every cell connected to `start` through cells holding the same value,
+/// in the order they were found.
+pub fn flood_fill<T>(grid: &Grid<T>, start: GridCoordinate) -> Vec<GridCoordinate>
+where
+    T: Copy + PartialEq,
+{
+    let Some(value) = grid.get_value(start) else {
+        return vec![];
+    };
+    let mut found: Vec<(GridCoordinate, usize)> =
+        distances(grid, start, |v| v == value).into_iter().collect();
+    found.sort_by_key(|&(coord, steps)| (steps, coord));
+    return found.into_iter().map(|(coord, _)| coord).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze() -> Grid<char> {
+        let lines = ["S.#.", "#.#.", "...E"];
+        let values = lines.iter().flat_map(|l| l.chars()).collect();
+        return Grid::new(4, 3, values);
+    }
+
+    #[test]
+    fn test_distances() {
+        let grid = maze();
+        let dist = distances(&grid, GridCoordinate::new(0, 0), |c| c != '#');
+        assert_eq!(dist[&GridCoordinate::new(0, 0)], 0);
+        assert_eq!(dist[&GridCoordinate::new(3, 2)], 5);
+        assert_eq!(dist[&GridCoordinate::new(3, 0)], 7);
+        assert_eq!(dist.len(), 9);
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let grid = maze();
+        let start = GridCoordinate::new(0, 0);
+        let end = GridCoordinate::new(3, 2);
+        let path = shortest_path(&grid, start, end, |c| c != '#').unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!(path[0], start);
+        assert_eq!(path[5], end);
+        for pair in path.windows(2) {
+            assert!(grid.get_adjacent_coordinates(pair[0]).contains(&pair[1]));
+        }
+        assert_eq!(
+            shortest_path(&grid, start, start, |c| c != '#'),
+            Some(vec![start])
+        );
+        assert_eq!(shortest_path(&grid, start, end, |c| c == '.'), None);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = maze();
+        let region = flood_fill(&grid, GridCoordinate::new(2, 0));
+        assert_eq!(
+            region,
+            vec![GridCoordinate::new(2, 0), GridCoordinate::new(2, 1)]
+        );
+        assert_eq!(flood_fill(&grid, GridCoordinate::new(1, 0)).len(), 5);
+        assert_eq!(flood_fill(&grid, GridCoordinate::new(9, 9)), vec![]);
+    }
+}
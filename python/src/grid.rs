@@ -0,0 +1,251 @@
+use gridlib::{Direction, Grid, GridCoordinate, GridRotation, GridTraversable};
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+/// `gridlib::Direction`, `Direction.NORTH` and so on.
+#[pyclass(name = "Direction", eq, eq_int, hash, frozen)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PyDirection {
+    NORTH,
+    EAST,
+    SOUTH,
+    WEST,
+    NORTHEAST,
+    SOUTHEAST,
+    SOUTHWEST,
+    NORTHWEST,
+}
+
+impl From<PyDirection> for Direction {
+    fn from(d: PyDirection) -> Direction {
+        return match d {
+            PyDirection::NORTH => Direction::NORTH,
+            PyDirection::EAST => Direction::EAST,
+            PyDirection::SOUTH => Direction::SOUTH,
+            PyDirection::WEST => Direction::WEST,
+            PyDirection::NORTHEAST => Direction::NORTHEAST,
+            PyDirection::SOUTHEAST => Direction::SOUTHEAST,
+            PyDirection::SOUTHWEST => Direction::SOUTHWEST,
+            PyDirection::NORTHWEST => Direction::NORTHWEST,
+        };
+    }
+}
+
+impl From<Direction> for PyDirection {
+    fn from(d: Direction) -> PyDirection {
+        return match d {
+            Direction::NORTH => PyDirection::NORTH,
+            Direction::EAST => PyDirection::EAST,
+            Direction::SOUTH => PyDirection::SOUTH,
+            Direction::WEST => PyDirection::WEST,
+            Direction::NORTHEAST => PyDirection::NORTHEAST,
+            Direction::SOUTHEAST => PyDirection::SOUTHEAST,
+            Direction::SOUTHWEST => PyDirection::SOUTHWEST,
+            Direction::NORTHWEST => PyDirection::NORTHWEST,
+        };
+    }
+}
+
+#[pymethods]
+impl PyDirection {
+    /// North, east, south, west
+    #[staticmethod]
+    fn cardinal() -> Vec<PyDirection> {
+        return Direction::cardinal_iterator().map(|&d| d.into()).collect();
+    }
+
+    /// All eight, clockwise from north
+    #[staticmethod]
+    fn all() -> Vec<PyDirection> {
+        return Direction::iterator().map(|&d| d.into()).collect();
+    }
+}
+
+/// `gridlib::GridCoordinate`, x to the right and y down from the top left.
+#[pyclass(name = "GridCoordinate", eq, hash, frozen, ord)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct PyGridCoordinate(GridCoordinate);
+
+#[pymethods]
+impl PyGridCoordinate {
+    #[new]
+    fn new(x: usize, y: usize) -> PyGridCoordinate {
+        return PyGridCoordinate(GridCoordinate::new(x, y));
+    }
+
+    #[getter]
+    fn x(&self) -> usize {
+        return self.0.x;
+    }
+
+    #[getter]
+    fn y(&self) -> usize {
+        return self.0.y;
+    }
+
+    fn __repr__(&self) -> String {
+        return format!("GridCoordinate({}, {})", self.0.x, self.0.y);
+    }
+}
+
+/// A `gridlib::Grid` of characters, one per cell.
+#[pyclass(name = "Grid")]
+#[derive(Debug, Clone)]
+pub struct PyGrid(Grid<char>);
+
+impl PyGrid {
+    fn value(&self, coord: PyGridCoordinate) -> PyResult<char> {
+        return self
+            .0
+            .get_value(coord.0)
+            .ok_or_else(|| PyIndexError::new_err(format!("{} is off the grid", coord.0)));
+    }
+}
+
+#[pymethods]
+impl PyGrid {
+    /// `values` holds every cell, row by row from the top left.
+    #[new]
+    fn new(width: usize, height: usize, values: &str) -> PyResult<PyGrid> {
+        let values: Vec<char> = values.chars().collect();
+        if values.len() != width * height {
+            return Err(PyValueError::new_err(format!(
+                "{} values don't make a {}x{} grid",
+                values.len(),
+                width,
+                height
+            )));
+        }
+        return Ok(PyGrid(Grid::new(width, height, values)));
+    }
+
+    /// One row per line, like most puzzle inputs.
+    #[staticmethod]
+    fn from_lines(lines: Vec<String>) -> PyResult<PyGrid> {
+        let width = lines.first().map_or(0, |l| l.chars().count());
+        for (row, line) in lines.iter().enumerate() {
+            let length = line.chars().count();
+            if length != width {
+                return Err(PyValueError::new_err(format!(
+                    "line {} is {} long, but the first line is {}",
+                    row, length, width
+                )));
+            }
+        }
+        return PyGrid::new(width, lines.len(), &lines.concat());
+    }
+
+    #[getter]
+    fn width(&self) -> usize {
+        return self.0.get_width();
+    }
+
+    #[getter]
+    fn height(&self) -> usize {
+        return self.0.get_height();
+    }
+
+    fn __getitem__(&self, coord: PyGridCoordinate) -> PyResult<char> {
+        return self.value(coord);
+    }
+
+    fn __setitem__(&mut self, coord: PyGridCoordinate, value: char) -> PyResult<()> {
+        self.value(coord)?;
+        self.0.set_value(coord.0, value);
+        return Ok(());
+    }
+
+    /// Every coordinate, row by row.
+    fn coords(&self) -> Vec<PyGridCoordinate> {
+        return self.0.coord_iter().map(PyGridCoordinate).collect();
+    }
+
+    /// Every coordinate holding `value`.
+    fn find(&self, value: char) -> Vec<PyGridCoordinate> {
+        return self
+            .0
+            .coord_iter()
+            .filter(|&c| self.0.get_value(c) == Some(value))
+            .map(PyGridCoordinate)
+            .collect();
+    }
+
+    /// The neighbour in a direction, `None` off the edge.
+    fn step(&self, coord: PyGridCoordinate, direction: PyDirection) -> Option<PyGridCoordinate> {
+        return self
+            .0
+            .get_coordinate_by_direction(coord.0, direction.into())
+            .map(PyGridCoordinate);
+    }
+
+    /// Neighbours on the grid, the 4 cardinal ones or all 8 with `diagonal`.
+    #[pyo3(signature = (coord, diagonal=false))]
+    fn neighbours(&self, coord: PyGridCoordinate, diagonal: bool) -> Vec<PyGridCoordinate> {
+        let coords = if diagonal {
+            self.0.get_all_adjacent_coordinates(coord.0)
+        } else {
+            self.0.get_adjacent_coordinates(coord.0)
+        };
+        return coords.into_iter().map(PyGridCoordinate).collect();
+    }
+
+    fn rotate_clockwise(&mut self) {
+        self.0.rotate_clockwise();
+    }
+
+    /// The rows as strings, `gridlib`'s `grid_strings`.
+    fn lines(&self) -> Vec<String> {
+        return self.0.grid_strings();
+    }
+
+    fn __str__(&self) -> String {
+        return self.lines().join("\n");
+    }
+}
+
+/// Steps from `start` to every cell reachable without crossing `walls`.
+#[pyfunction]
+#[pyo3(signature = (grid, start, walls="#"))]
+fn distances(
+    grid: &PyGrid,
+    start: PyGridCoordinate,
+    walls: &str,
+) -> HashMap<PyGridCoordinate, usize> {
+    return gridlib::distances(&grid.0, start.0, |c| !walls.contains(c))
+        .into_iter()
+        .map(|(coord, steps)| (PyGridCoordinate(coord), steps))
+        .collect();
+}
+
+/// A shortest path from `start` to `end` including both, or `None`.
+#[pyfunction]
+#[pyo3(signature = (grid, start, end, walls="#"))]
+fn shortest_path(
+    grid: &PyGrid,
+    start: PyGridCoordinate,
+    end: PyGridCoordinate,
+    walls: &str,
+) -> Option<Vec<PyGridCoordinate>> {
+    let path = gridlib::shortest_path(&grid.0, start.0, end.0, |c| !walls.contains(c))?;
+    return Some(path.into_iter().map(PyGridCoordinate).collect());
+}
+
+/// Every cell connected to `start` through cells with the same character.
+#[pyfunction]
+fn flood_fill(grid: &PyGrid, start: PyGridCoordinate) -> Vec<PyGridCoordinate> {
+    return gridlib::flood_fill(&grid.0, start.0)
+        .into_iter()
+        .map(PyGridCoordinate)
+        .collect();
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyDirection>()?;
+    m.add_class::<PyGridCoordinate>()?;
+    m.add_class::<PyGrid>()?;
+    m.add_function(wrap_pyfunction!(distances, m)?)?;
+    m.add_function(wrap_pyfunction!(shortest_path, m)?)?;
+    m.add_function(wrap_pyfunction!(flood_fill, m)?)?;
+    return Ok(());
+}
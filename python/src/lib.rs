@@ -0,0 +1,103 @@
+//! The `aoc2024` Python module: every day's solver, and the `gridlib` types.
+//!
+//! Build it into the current virtualenv with `maturin develop`, see the
+//! README. Days go through `solutionlib`, so new days show up here as soon as
+//! they're registered there.
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use solutionlib::{find_solution, solutions, InputShape, Part, PuzzleInput, Solution};
+
+mod grid;
+
+fn solution(day: u32) -> PyResult<Solution> {
+    return find_solution(day)
+        .ok_or_else(|| PyValueError::new_err(format!("Day {} isn't solved", day)));
+}
+
+/// Every day that can be solved, in order.
+#[pyfunction]
+fn days() -> Vec<u32> {
+    return solutions().iter().map(|s| s.day).collect();
+}
+
+/// The params a day uses for its real input, eg `[103, 101]` for day 14.
+#[pyfunction]
+fn default_params(day: u32) -> PyResult<Vec<usize>> {
+    return Ok(solution(day)?.params.to_vec());
+}
+
+/// Split an input the way the day reads it: a `str`, a `list[str]` of
+/// non-blank lines, or a `list[list[str]]` of groups split by blank lines.
+#[pyfunction]
+fn parse(py: Python<'_>, day: u32, text: &str) -> PyResult<PyObject> {
+    let object = match solution(day)?.parse(text) {
+        PuzzleInput::Text(text) => text.into_pyobject(py)?.into_any(),
+        PuzzleInput::Lines(lines) => lines.into_pyobject(py)?.into_any(),
+        PuzzleInput::Groups(groups) => groups.into_pyobject(py)?.into_any(),
+    };
+    return Ok(object.unbind());
+}
+
+/// Raw text, or what `parse` gave back, possibly edited.
+fn puzzle_input(solution: &Solution, input: &Bound<'_, PyAny>) -> PyResult<PuzzleInput> {
+    if let Ok(text) = input.downcast::<PyString>() {
+        return Ok(solution.parse(text.to_str()?));
+    }
+    return Ok(match solution.shape {
+        InputShape::Text => PuzzleInput::Text(input.extract()?),
+        InputShape::Lines => PuzzleInput::Lines(input.extract()?),
+        InputShape::Groups => PuzzleInput::Groups(input.extract()?),
+    });
+}
+
+fn solve_part(
+    day: u32,
+    part: Part,
+    input: &Bound<'_, PyAny>,
+    params: Option<Vec<usize>>,
+) -> PyResult<String> {
+    let solution = solution(day)?;
+    let params = params.unwrap_or(solution.params.to_vec());
+    if params.len() != solution.params.len() {
+        return Err(PyValueError::new_err(format!(
+            "Day {} takes {} params, got {}",
+            day,
+            solution.params.len(),
+            params.len()
+        )));
+    }
+    let input = puzzle_input(&solution, input)?;
+    solution
+        .check_with(&input, &params)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    return Ok(solution.solve_with(part, &input, &params));
+}
+
+/// Part a of a day, on raw text or the output of `parse`.
+///
+/// Leave `params` out for a real input. Input the day can't parse raises
+/// `ValueError` before anything is solved.
+#[pyfunction]
+#[pyo3(signature = (day, input, params=None))]
+fn part_a(day: u32, input: &Bound<'_, PyAny>, params: Option<Vec<usize>>) -> PyResult<String> {
+    return solve_part(day, Part::A, input, params);
+}
+
+/// Part b of a day, see `part_a`.
+#[pyfunction]
+#[pyo3(signature = (day, input, params=None))]
+fn part_b(day: u32, input: &Bound<'_, PyAny>, params: Option<Vec<usize>>) -> PyResult<String> {
+    return solve_part(day, Part::B, input, params);
+}
+
+#[pymodule]
+fn aoc2024(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(days, m)?)?;
+    m.add_function(wrap_pyfunction!(default_params, m)?)?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(part_a, m)?)?;
+    m.add_function(wrap_pyfunction!(part_b, m)?)?;
+    grid::register(m)?;
+    return Ok(());
+}
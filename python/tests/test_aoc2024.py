@@ -0,0 +1,88 @@
+from pathlib import Path
+
+import pytest
+
+import aoc2024
+from aoc2024 import Direction, Grid, GridCoordinate
+
+EXAMPLES = Path(__file__).resolve().parents[2] / "examples"
+
+
+def example(name):
+    return (EXAMPLES / f"{name}.txt").read_text()
+
+
+def test_days():
+    assert aoc2024.days() == list(range(1, 25))
+    assert aoc2024.default_params(14) == [103, 101]
+    with pytest.raises(ValueError):
+        aoc2024.default_params(26)
+
+
+def test_parts():
+    text = example("day01-1")
+    assert aoc2024.part_a(1, text) == "11"
+    assert aoc2024.part_b(1, text) == "31"
+
+
+def test_parse_and_edit():
+    groups = aoc2024.parse(13, example("day13-1"))
+    assert len(groups) == 4
+    assert groups[0][0].startswith("Button A")
+    # Only the first claw machine
+    assert aoc2024.part_a(13, groups[:1]) == "280"
+
+
+def test_params():
+    text = example("day14-1")
+    assert aoc2024.part_a(14, text, [7, 11]) == "12"
+    with pytest.raises(ValueError):
+        aoc2024.part_a(14, text, [7])
+
+
+def test_bad_input():
+    # Checked before solving, so a plain ValueError rather than a Rust panic
+    with pytest.raises(ValueError, match="invalid input"):
+        aoc2024.part_a(16, "no maze here")
+
+
+def test_grid():
+    grid = Grid.from_lines(["S.#", "..#", "#.E"])
+    assert (grid.width, grid.height) == (3, 3)
+    assert grid[GridCoordinate(2, 2)] == "E"
+    grid[GridCoordinate(0, 2)] = "."
+    assert grid.lines() == ["S.#", "..#", "..E"]
+    assert str(grid) == "S.#\n..#\n..E"
+    assert grid.find("S") == [GridCoordinate(0, 0)]
+    with pytest.raises(IndexError):
+        grid[GridCoordinate(3, 0)]
+    with pytest.raises(ValueError):
+        Grid(2, 2, "abc")
+    # Adds up to 3x2, but the rows are ragged
+    with pytest.raises(ValueError):
+        Grid.from_lines(["ab", "abc", "a"])
+
+
+def test_moves():
+    grid = Grid(3, 2, "abcdef")
+    corner = GridCoordinate(0, 0)
+    assert grid.step(corner, Direction.EAST) == GridCoordinate(1, 0)
+    assert grid.step(corner, Direction.NORTH) is None
+    assert len(grid.neighbours(corner)) == 2
+    assert len(grid.neighbours(corner, diagonal=True)) == 3
+    assert Direction.cardinal() == [Direction.NORTH, Direction.EAST, Direction.SOUTH, Direction.WEST]
+    assert len(Direction.all()) == 8
+    grid.rotate_clockwise()
+    assert grid.lines() == ["da", "eb", "fc"]
+
+
+def test_search():
+    grid = Grid.from_lines(["S.#.", "#.#.", "...E"])
+    start, end = GridCoordinate(0, 0), GridCoordinate(3, 2)
+    path = aoc2024.shortest_path(grid, start, end)
+    assert len(path) == 6
+    assert path[0] == start and path[-1] == end
+    assert aoc2024.shortest_path(grid, start, end, walls="#E") is None
+    distances = aoc2024.distances(grid, start)
+    assert distances[GridCoordinate(3, 0)] == 7
+    assert sorted(aoc2024.flood_fill(grid, GridCoordinate(2, 0))) == [GridCoordinate(2, 0), GridCoordinate(2, 1)]
@@ -0,0 +1,11 @@
+mod solution;
+
+pub use crate::solution::InputShape;
+pub use crate::solution::Part;
+pub use crate::solution::PuzzleInput;
+pub use crate::solution::Solution;
+
+mod registry;
+
+pub use crate::registry::find_solution;
+pub use crate::registry::solutions;
@@ -0,0 +1,274 @@
+use crate::solution::{InputShape, PuzzleInput, Solution};
+
+/// Every solved day, in order.
+pub fn solutions() -> Vec<Solution> {
+    return vec![
+        Solution {
+            day: 1,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day01::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day01::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day01::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 2,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day02::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day02::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day02::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 3,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day03::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day03::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day03::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 4,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day04::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day04::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day04::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 5,
+            shape: InputShape::Text,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day05::check_input(input.text()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day05::puzzle_a(input.text()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day05::puzzle_b(input.text()).to_string(),
+        },
+        Solution {
+            day: 6,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day06::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day06::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day06::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 7,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day07::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day07::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day07::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 8,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day08::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day08::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day08::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 9,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day09::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day09::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day09::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 10,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day10::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day10::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day10::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 11,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day11::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day11::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day11::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 12,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day12::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day12::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day12::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 13,
+            shape: InputShape::Groups,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day13::check_input(input.groups()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day13::puzzle_a(input.groups()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day13::puzzle_b(input.groups()).to_string(),
+        },
+        Solution {
+            day: 14,
+            shape: InputShape::Lines,
+            // height, width
+            params: &[103, 101],
+            check: |input: &PuzzleInput, _: &[usize]| day14::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, p: &[usize]| {
+                day14::puzzle_a(input.lines(), p[0], p[1]).to_string()
+            },
+            part_b: |input: &PuzzleInput, p: &[usize]| {
+                day14::puzzle_b(input.lines(), p[0], p[1]).to_string()
+            },
+        },
+        Solution {
+            day: 15,
+            shape: InputShape::Groups,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day15::check_input(input.groups()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day15::puzzle_a(input.groups()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day15::puzzle_b(input.groups()).to_string(),
+        },
+        Solution {
+            day: 16,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day16::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day16::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day16::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 17,
+            shape: InputShape::Groups,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day17::check_input(input.groups()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day17::puzzle_a(input.groups()),
+            part_b: |input: &PuzzleInput, _: &[usize]| day17::puzzle_b(input.groups()).to_string(),
+        },
+        Solution {
+            day: 18,
+            shape: InputShape::Lines,
+            // width, height, bytes already fallen
+            params: &[71, 71, 1024],
+            check: |input: &PuzzleInput, p: &[usize]| day18::check_input(input.lines(), p[0], p[1]),
+            part_a: |input: &PuzzleInput, p: &[usize]| {
+                day18::puzzle_a(input.lines(), p[0], p[1], p[2]).to_string()
+            },
+            part_b: |input: &PuzzleInput, p: &[usize]| {
+                let (x, y) = day18::puzzle_b(input.lines(), p[0], p[1], p[2]);
+                return format!("{},{}", x, y);
+            },
+        },
+        Solution {
+            day: 19,
+            shape: InputShape::Groups,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day19::check_input(input.groups()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day19::puzzle_a(input.groups()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day19::puzzle_b(input.groups()).to_string(),
+        },
+        Solution {
+            day: 20,
+            shape: InputShape::Lines,
+            // minimum picoseconds saved
+            params: &[100],
+            check: |input: &PuzzleInput, _: &[usize]| day20::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, p: &[usize]| {
+                day20::puzzle_a(input.lines(), p[0]).to_string()
+            },
+            part_b: |input: &PuzzleInput, p: &[usize]| {
+                day20::puzzle_b(input.lines(), p[0]).to_string()
+            },
+        },
+        Solution {
+            day: 21,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day21::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day21::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day21::puzzle_b(input.lines()).to_string(),
+        },
+        Solution {
+            day: 22,
+            shape: InputShape::Lines,
+            // skipped iterations, iterations
+            params: &[0, 2000],
+            check: |input: &PuzzleInput, _: &[usize]| day22::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day22::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, p: &[usize]| {
+                day22::puzzle_b(input.lines(), p[0], p[1]).to_string()
+            },
+        },
+        Solution {
+            day: 23,
+            shape: InputShape::Lines,
+            params: &[],
+            check: |input: &PuzzleInput, _: &[usize]| day23::check_input(input.lines()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day23::puzzle_a(input.lines()).to_string(),
+            part_b: |input: &PuzzleInput, _: &[usize]| day23::puzzle_b(input.lines()),
+        },
+        Solution {
+            day: 24,
+            shape: InputShape::Groups,
+            // swaps
+            params: &[4],
+            check: |input: &PuzzleInput, _: &[usize]| day24::check_input(input.groups()),
+            part_a: |input: &PuzzleInput, _: &[usize]| day24::puzzle_a(input.groups()).to_string(),
+            part_b: |input: &PuzzleInput, p: &[usize]| day24::puzzle_b(input.groups(), p[0]),
+        },
+    ];
+}
+
+/// Look up a single day.
+/// ```
+/// assert_eq!(solutionlib::find_solution(7).unwrap().name(), "day07");
+/// assert!(solutionlib::find_solution(26).is_none());
+/// ```
+pub fn find_solution(day: u32) -> Option<Solution> {
+    return solutions().into_iter().find(|s| s.day == day);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Part;
+    use filelib::{fixtures_dir, load_fixture};
+    use std::fs;
+
+    #[test]
+    fn test_days_in_order() {
+        let days: Vec<u32> = solutions().iter().map(|s| s.day).collect();
+        assert_eq!(days[0], 1);
+        assert!(days.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_examples() {
+        let mut names: Vec<String> = fs::read_dir(fixtures_dir!())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .filter_map(|name| name.strip_suffix(".txt").map(str::to_string))
+            // The template's stub fixture belongs to no day
+            .filter(|name| name.starts_with("day"))
+            .collect();
+        names.sort();
+        assert!(!names.is_empty());
+
+        for name in names {
+            let fixture = load_fixture(fixtures_dir!(), &name);
+            let day: u32 = name[3..5].parse().unwrap();
+            let solution = find_solution(day).unwrap();
+            let input = solution.parse(&fixture.text);
+            for &part in Part::iterator() {
+                let part_name = part.to_string();
+                let Some(expected) = fixture.answer(&part_name) else {
+                    continue;
+                };
+                let params = fixture
+                    .params(&part_name)
+                    .unwrap_or(solution.params.to_vec());
+                let answer = solution.solve_with(part, &input, &params);
+                assert_eq!(answer, expected, "{} part {}", name, part);
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use filelib::{remove_blanks, split_lines_by_blanks, ParseError};
+use std::fmt::{Display, Formatter};
+use tracelib::{day_span, parse_span, part_span};
+
+/// How a day wants its input file handed to it.
+///
+/// These mirror the three ways the `main.rs` files load their input.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum InputShape {
+    /// The whole file as a single string, `load`
+    Text,
+    /// One entry per non-blank line, `load_no_blanks`
+    Lines,
+    /// Lines grouped by blank lines, `split_lines_by_blanks`
+    Groups,
+}
+
+/// Puzzle input after it has been split into the shape a day expects.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PuzzleInput {
+    Text(String),
+    Lines(Vec<String>),
+    Groups(Vec<Vec<String>>),
+}
+
+impl PuzzleInput {
+    /// Split raw file contents into the requested shape.
+    /// ```
+    /// use solutionlib::{InputShape, PuzzleInput};
+    /// let text = "a\nb\n\nc\n";
+    /// assert_eq!(PuzzleInput::parse(InputShape::Text, text), PuzzleInput::Text(text.to_string()));
+    /// assert_eq!(
+    ///     PuzzleInput::parse(InputShape::Lines, text),
+    ///     PuzzleInput::Lines(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+    /// );
+    /// assert_eq!(
+    ///     PuzzleInput::parse(InputShape::Groups, text),
+    ///     PuzzleInput::Groups(vec![vec!["a".to_string(), "b".to_string()], vec!["c".to_string()]])
+    /// );
+    /// ```
+    pub fn parse(shape: InputShape, text: &str) -> PuzzleInput {
+        return match shape {
+            InputShape::Text => PuzzleInput::Text(text.to_string()),
+            InputShape::Lines => PuzzleInput::Lines(remove_blanks(text)),
+            InputShape::Groups => PuzzleInput::Groups(split_lines_by_blanks(text)),
+        };
+    }
+
+    pub fn text(&self) -> &String {
+        match self {
+            PuzzleInput::Text(s) => return s,
+            _ => panic!("Expected text input, got {:?}", self.shape()),
+        }
+    }
+
+    pub fn lines(&self) -> &Vec<String> {
+        match self {
+            PuzzleInput::Lines(l) => return l,
+            _ => panic!("Expected line input, got {:?}", self.shape()),
+        }
+    }
+
+    pub fn groups(&self) -> &Vec<Vec<String>> {
+        match self {
+            PuzzleInput::Groups(g) => return g,
+            _ => panic!("Expected grouped input, got {:?}", self.shape()),
+        }
+    }
+
+    pub fn shape(&self) -> InputShape {
+        return match self {
+            PuzzleInput::Text(_) => InputShape::Text,
+            PuzzleInput::Lines(_) => InputShape::Lines,
+            PuzzleInput::Groups(_) => InputShape::Groups,
+        };
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    pub fn iterator() -> std::slice::Iter<'static, Part> {
+        static PARTS: [Part; 2] = [Part::A, Part::B];
+        return PARTS.iter();
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Part::A => "a",
+            Part::B => "b",
+        };
+        return write!(f, "{}", s);
+    }
+}
+
+/// A single day, wired up so it can be run without knowing its signature.
+///
+/// Any extra parameters a day takes (grid sizes, iteration counts) are passed
+/// as `params`, which default to the values its `main.rs` uses for the real
+/// input. Examples usually need different ones. `check` is the day's
+/// `check_input`, for turning input it can't handle into an error before
+/// solving rather than a panic during.
+#[derive(Debug, Copy, Clone)]
+pub struct Solution {
+    pub day: u32,
+    pub shape: InputShape,
+    pub params: &'static [usize],
+    pub check: fn(&PuzzleInput, &[usize]) -> Result<(), ParseError>,
+    pub part_a: fn(&PuzzleInput, &[usize]) -> String,
+    pub part_b: fn(&PuzzleInput, &[usize]) -> String,
+}
+
+impl Solution {
+    /// Crate / folder name for this day, eg `day07`
+    pub fn name(&self) -> String {
+        return format!("day{:02}", self.day);
+    }
+
+    pub fn parse(&self, text: &str) -> PuzzleInput {
+        let _day = day_span(format_args!("day{:02}", self.day)).entered();
+        return parse_span().in_scope(|| PuzzleInput::parse(self.shape, text));
+    }
+
+    /// Check the input with the default params for the real input.
+    pub fn check(&self, input: &PuzzleInput) -> Result<(), ParseError> {
+        return self.check_with(input, self.params);
+    }
+
+    pub fn check_with(&self, input: &PuzzleInput, params: &[usize]) -> Result<(), ParseError> {
+        let _day = day_span(format_args!("day{:02}", self.day)).entered();
+        return parse_span().in_scope(|| (self.check)(input, params));
+    }
+
+    /// Solve with the default params for the real input.
+    pub fn solve(&self, part: Part, input: &PuzzleInput) -> String {
+        return self.solve_with(part, input, self.params);
+    }
+
+    pub fn solve_with(&self, part: Part, input: &PuzzleInput, params: &[usize]) -> String {
+        let _day = day_span(format_args!("day{:02}", self.day)).entered();
+        let _part = part_span(part).entered();
+        return match part {
+            Part::A => (self.part_a)(input, params),
+            Part::B => (self.part_b)(input, params),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_part() {
+        assert_eq!(format!("{}", Part::A), "a");
+        assert_eq!(format!("{}", Part::B), "b");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_wrong_shape() {
+        let input = PuzzleInput::parse(InputShape::Lines, "foo\nbar");
+        input.groups();
+    }
+
+    #[test]
+    fn test_solution_name() {
+        let solution = Solution {
+            day: 7,
+            shape: InputShape::Text,
+            params: &[1],
+            check: |input, _| match input.text().is_empty() {
+                true => Err(ParseError::new("no input")),
+                false => Ok(()),
+            },
+            part_a: |input, _| input.text().len().to_string(),
+            part_b: |_, p| p[0].to_string(),
+        };
+        assert_eq!(solution.name(), "day07");
+        let input = solution.parse("abc");
+        assert_eq!(solution.solve(Part::A, &input), "3");
+        assert_eq!(solution.solve(Part::B, &input), "1");
+        assert_eq!(solution.solve_with(Part::B, &input, &[2]), "2");
+        assert_eq!(solution.check(&input), Ok(()));
+        assert!(solution.check(&solution.parse("")).is_err());
+    }
+}
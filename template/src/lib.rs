@@ -1,26 +1,22 @@
 extern crate filelib;
 
 pub use filelib::load_no_blanks;
-use log::info;
+use filelib::ParseError;
+use tracelib::debug;
+
+/// Turn input the puzzles can't handle into an error.
+pub fn check_input(string_list: &Vec<String>) -> Result<(), ParseError> {
+    return Ok(());
+}
 
 /// Foo
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "foo"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(template::puzzle_a(&vec1), 0);
-/// ```
+#[doc = filelib::fixture_doc!("template-1", a, |fixture| template::puzzle_a(&fixture.lines()))]
 pub fn puzzle_a(string_list: &Vec<String>) -> u32 {
     return 0;
 }
 
 /// Foo
-/// ```
-/// let vec1: Vec<String> = vec![
-///     "foo"
-/// ].iter().map(|s| s.to_string()).collect();
-/// assert_eq!(template::puzzle_b(&vec1), 0);
-/// ```
+#[doc = filelib::fixture_doc!("template-1", b, |fixture| template::puzzle_b(&fixture.lines()))]
 pub fn puzzle_b(string_list: &Vec<String>) -> u32 {
     return 0;
 }
@@ -34,7 +30,7 @@ pub fn puzzle_b(string_list: &Vec<String>) -> u32 {
 /// ```
 pub fn coverage_workaround(a: &Vec<u32>) -> u32 {
     if a.len() == 0 {
-        info!("Example logging of {:?}", a);
+        debug!("Example logging of {:?}", a);
         return 1;
     } else {
         return 2;
@@ -0,0 +1,161 @@
+//! Logging shared by every day and the runner.
+//!
+//! Days log through the macros re-exported here instead of depending on
+//! `tracing` themselves, so the compile time level limits live in one
+//! Cargo.toml: everything up to `trace` in debug builds, and only `warn` and
+//! `error` in release, where the rest is compiled out.
+//!
+//! Which modules actually log is picked at runtime with a filter like
+//! `aoc=info,day17=trace`, taken from `AOC_LOG`.
+use std::fmt::Display;
+use std::io::{self, IsTerminal};
+use tracing::Subscriber;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::EnvFilter;
+
+pub use tracing::{debug, enabled, error, info, trace, warn, Level, Span};
+pub use tracing_subscriber::filter::ParseError;
+
+/// Environment variable holding the filter for `init`
+pub const FILTER_ENV: &str = "AOC_LOG";
+
+/// Filter used when `AOC_LOG` is not set
+pub const DEFAULT_FILTER: &str = "warn";
+
+/// Target of the day, parse and part spans, `aoc=info` turns them on.
+///
+/// Each logs how long it took when it closes.
+pub const SPAN_TARGET: &str = "aoc";
+
+/// Turn a filter like `day17=trace,solutionlib=info` into an `EnvFilter`.
+/// ```
+/// assert!(tracelib::filter("day17=trace,warn").is_ok());
+/// assert!(tracelib::filter("day17=loudly").is_err());
+/// ```
+pub fn filter(directives: &str) -> Result<EnvFilter, ParseError> {
+    return EnvFilter::builder().parse(directives);
+}
+
+fn subscriber<W>(filter: EnvFilter, writer: W, ansi: bool) -> impl Subscriber + Send + Sync
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    return tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(writer)
+        .with_ansi(ansi)
+        .finish();
+}
+
+/// Log to stderr, filtered by `AOC_LOG`, or `warn` and above if it's unset.
+///
+/// A filter that doesn't parse is reported and the default used instead.
+pub fn init() {
+    let directives = std::env::var(FILTER_ENV).unwrap_or(DEFAULT_FILTER.to_string());
+    if let Err(e) = init_with(&directives) {
+        eprintln!("Ignoring {}={}: {}", FILTER_ENV, directives, e);
+        init_with(DEFAULT_FILTER).unwrap();
+    }
+}
+
+/// Log to stderr with the given filter.
+///
+/// Stdout is left to the answers. Calling this again once logging is set up
+/// does nothing, so tests can call it freely.
+pub fn init_with(directives: &str) -> Result<(), ParseError> {
+    let filter = filter(directives)?;
+    let _ = tracing::subscriber::set_global_default(subscriber(
+        filter,
+        io::stderr,
+        io::stderr().is_terminal(),
+    ));
+    return Ok(());
+}
+
+/// Span around everything done for one day, eg `day17`.
+///
+/// The name is only formatted if the span is enabled.
+pub fn day_span(day: impl Display) -> Span {
+    return tracing::info_span!(target: SPAN_TARGET, "day", day = %day);
+}
+
+/// Span around turning the input into what the parts take.
+pub fn parse_span() -> Span {
+    return tracing::info_span!(target: SPAN_TARGET, "parse");
+}
+
+/// Span around solving one part, `a` or `b`.
+pub fn part_span(part: impl Display) -> Span {
+    return tracing::info_span!(target: SPAN_TARGET, "part", part = %part);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            return Ok(buf.len());
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            return Ok(());
+        }
+    }
+
+    impl Buffer {
+        fn text(&self) -> String {
+            return String::from_utf8(self.0.lock().unwrap().clone()).unwrap();
+        }
+    }
+
+    fn capture(directives: &str, f: impl FnOnce()) -> String {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = subscriber(filter(directives).unwrap(), move || writer.clone(), false);
+        tracing::subscriber::with_default(subscriber, f);
+        return buffer.text();
+    }
+
+    mod noisy {
+        pub fn log() {
+            crate::trace!("noisy trace");
+        }
+    }
+
+    #[test]
+    fn test_per_module_filter() {
+        let text = capture("warn,tracelib::tests::noisy=trace", || {
+            info!("quiet info");
+            warn!("loud warn");
+            noisy::log();
+        });
+        assert!(!text.contains("quiet info"));
+        assert!(text.contains("loud warn"));
+        assert!(text.contains("noisy trace"));
+    }
+
+    #[test]
+    fn test_spans() {
+        let text = capture("aoc=info,tracelib=info", || {
+            let _day = day_span("day17").entered();
+            part_span("b").in_scope(|| info!(answer = 3, "solved"));
+        });
+        assert!(text.contains("day{day=day17}:part{part=b}: "));
+        assert!(text.contains("answer=3"));
+        // Closing each span logs how long it took
+        assert_eq!(text.matches("close").count(), 2);
+    }
+
+    #[test]
+    fn test_bad_filter() {
+        assert!(init_with("day17=loudly").is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use crate::error::VmError;
+use crate::machine::{InstructionSet, Word};
+
+/// Turn assembly, one instruction per line, into a program. Blank lines and
+/// anything after a `;` are skipped.
+/// ```
+/// use vmlib::threebit::Instruction;
+/// let program = vmlib::assemble::<Instruction>("adv 1 ; halve A\nout a\njnz 0").unwrap();
+/// assert_eq!(program, vec![0, 1, 5, 4, 3, 0]);
+/// assert!(vmlib::assemble::<Instruction>("adv 9").is_err());
+/// ```
+pub fn assemble<I: InstructionSet>(text: &str) -> Result<Vec<Word>, VmError> {
+    let mut program = vec![];
+    for (number, line) in text.lines().enumerate() {
+        let code = line.split(';').next().unwrap().trim();
+        if code.is_empty() {
+            continue;
+        }
+        let instruction: I = code
+            .parse()
+            .map_err(|reason| VmError::Assemble(number + 1, reason))?;
+        program.extend(instruction.encode());
+    }
+    return Ok(program);
+}
+
+/// Every instruction in a program with its address, read from the start.
+pub fn disassemble<I: InstructionSet>(program: &[Word]) -> Result<Vec<(usize, I)>, VmError> {
+    let mut result = vec![];
+    let mut ip = 0;
+    while ip < program.len() {
+        let instruction = I::decode(program, ip).map_err(|reason| VmError::Decode(ip, reason))?;
+        result.push((ip, instruction));
+        ip += instruction.encode().len();
+    }
+    return Ok(result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threebit::Instruction;
+
+    #[test]
+    fn test_round_trip() {
+        let program = vec![2, 4, 1, 1, 7, 5, 4, 6, 0, 3, 1, 4, 5, 5, 3, 0];
+        let listing = disassemble::<Instruction>(&program).unwrap();
+        assert_eq!(listing.len(), 8);
+        assert_eq!(listing[3].0, 6);
+        let text: Vec<String> = listing.iter().map(|(_, i)| i.to_string()).collect();
+        assert_eq!(
+            text,
+            vec!["bst a", "bxl 1", "cdv b", "bxc 6", "adv 3", "bxl 4", "out b", "jnz 0"]
+        );
+        assert_eq!(assemble::<Instruction>(&text.join("\n")), Ok(program));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            assemble::<Instruction>("\n; nothing\nout a\nfoo 1"),
+            Err(VmError::Assemble(4, "unknown instruction foo".to_string()))
+        );
+        assert!(matches!(
+            disassemble::<Instruction>(&[5, 4, 0]),
+            Err(VmError::Decode(2, _))
+        ));
+    }
+}
@@ -0,0 +1,23 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum VmError {
+    /// The words at this address aren't an instruction
+    Decode(usize, String),
+    /// A line of assembly that isn't an instruction, counted from 1
+    Assemble(usize, String),
+    /// Still running after this many steps
+    StepLimit(usize),
+}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            VmError::Decode(ip, reason) => write!(f, "bad instruction at {}: {}", ip, reason),
+            VmError::Assemble(line, reason) => write!(f, "line {}: {}", line, reason),
+            VmError::StepLimit(limit) => write!(f, "still running after {} steps", limit),
+        };
+    }
+}
+
+impl std::error::Error for VmError {}
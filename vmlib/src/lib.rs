@@ -0,0 +1,25 @@
+//! A small register machine, for the puzzles that hand you a program to run.
+//!
+//! An `InstructionSet` says how its instructions are decoded, encoded,
+//! written as assembly and executed. `Machine` does the rest for any of them:
+//! fetching, jumping, output, step limits and traces. `threebit` is the
+//! 3-bit computer from day17.
+
+mod asm;
+
+pub use crate::asm::assemble;
+pub use crate::asm::disassemble;
+
+mod error;
+
+pub use crate::error::VmError;
+
+mod machine;
+
+pub use crate::machine::Effect;
+pub use crate::machine::InstructionSet;
+pub use crate::machine::Machine;
+pub use crate::machine::Step;
+pub use crate::machine::Word;
+
+pub mod threebit;
@@ -0,0 +1,227 @@
+use crate::error::VmError;
+
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::str::FromStr;
+use tracelib::trace;
+
+/// One value in a program or a register.
+pub type Word = u64;
+
+/// What executing an instruction asks of the machine.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Effect {
+    /// Carry on with the next instruction
+    Next,
+    /// Emit a value, then carry on with the next instruction
+    Output(Word),
+    /// Carry on from this address
+    Jump(usize),
+}
+
+/// A machine's instructions: how they sit in a program, how they're written
+/// as assembly, and what they do to the registers.
+///
+/// `Display` writes one instruction the way `from_str` reads it back, eg
+/// `adv 3`, so `assemble` and `disassemble` come for free.
+pub trait InstructionSet: Copy + Debug + Display + FromStr<Err = String> {
+    /// The register file, eg `[Word; 3]`
+    type Registers: Copy + Debug + Default + Eq + AsRef<[Word]> + AsMut<[Word]>;
+
+    /// Names for the registers, in order, for traces and inputs.
+    const REGISTERS: &'static [char];
+
+    /// The instruction starting at `program[ip]`, which is always there.
+    fn decode(program: &[Word], ip: usize) -> Result<Self, String>;
+
+    /// The words `decode` reads this instruction back from.
+    fn encode(&self) -> Vec<Word>;
+
+    /// Update the registers, everything else goes through the `Effect`.
+    fn execute(&self, registers: &mut Self::Registers) -> Effect;
+}
+
+/// One executed instruction, with the registers as it left them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Step<I: InstructionSet> {
+    pub ip: usize,
+    pub instruction: I,
+    pub registers: I::Registers,
+    pub effect: Effect,
+}
+
+impl<I: InstructionSet> Display for Step<I> {
+    /// ```
+    /// use vmlib::threebit::Instruction;
+    /// use vmlib::Machine;
+    /// let mut machine: Machine<Instruction> = Machine::new(vec![5, 4], [10, 0, 0]);
+    /// let step = machine.step().unwrap().unwrap();
+    /// assert_eq!(step.to_string(), "0: out a  A=10 B=0 C=0  out 2");
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} ", self.ip, self.instruction)?;
+        for (name, value) in I::REGISTERS.iter().zip(self.registers.as_ref()) {
+            write!(f, " {}={}", name, value)?;
+        }
+        return match self.effect {
+            Effect::Next => Ok(()),
+            Effect::Output(value) => write!(f, "  out {}", value),
+            Effect::Jump(target) => write!(f, "  jump {}", target),
+        };
+    }
+}
+
+/// Runs a program for any `InstructionSet`. It halts once the instruction
+/// pointer is past the end of the program.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Machine<I: InstructionSet> {
+    program: Vec<Word>,
+    pub registers: I::Registers,
+    pub ip: usize,
+    pub output: Vec<Word>,
+    steps: usize,
+    instructions: PhantomData<I>,
+}
+
+impl<I: InstructionSet> Machine<I> {
+    pub fn new(program: Vec<Word>, registers: I::Registers) -> Machine<I> {
+        return Machine {
+            program: program,
+            registers: registers,
+            ip: 0,
+            output: vec![],
+            steps: 0,
+            instructions: PhantomData,
+        };
+    }
+
+    pub fn program(&self) -> &[Word] {
+        return &self.program;
+    }
+
+    /// Instructions executed so far.
+    pub fn steps(&self) -> usize {
+        return self.steps;
+    }
+
+    pub fn is_halted(&self) -> bool {
+        return self.ip >= self.program.len();
+    }
+
+    /// Start the same program again with new registers.
+    pub fn reset(&mut self, registers: I::Registers) {
+        self.registers = registers;
+        self.ip = 0;
+        self.output.clear();
+        self.steps = 0;
+    }
+
+    /// Execute one instruction, `None` if already halted.
+    pub fn step(&mut self) -> Result<Option<Step<I>>, VmError> {
+        if self.is_halted() {
+            return Ok(None);
+        }
+        let ip = self.ip;
+        let instruction =
+            I::decode(&self.program, ip).map_err(|reason| VmError::Decode(ip, reason))?;
+        let effect = instruction.execute(&mut self.registers);
+        self.ip = match effect {
+            Effect::Jump(target) => target,
+            Effect::Next | Effect::Output(_) => ip + instruction.encode().len(),
+        };
+        if let Effect::Output(value) = effect {
+            self.output.push(value);
+        }
+        self.steps += 1;
+        trace!(%instruction, ip, registers = ?self.registers, "handled");
+        return Ok(Some(Step {
+            ip: ip,
+            instruction: instruction,
+            registers: self.registers,
+            effect: effect,
+        }));
+    }
+
+    /// Run until halted, giving up after `limit` more instructions.
+    pub fn run(&mut self, limit: usize) -> Result<(), VmError> {
+        for _ in 0..limit {
+            if self.step()?.is_none() {
+                return Ok(());
+            }
+        }
+        if self.is_halted() {
+            return Ok(());
+        }
+        return Err(VmError::StepLimit(limit));
+    }
+
+    /// Like `run`, keeping every step.
+    pub fn trace(&mut self, limit: usize) -> Result<Vec<Step<I>>, VmError> {
+        let mut steps = vec![];
+        for _ in 0..limit {
+            match self.step()? {
+                Some(step) => steps.push(step),
+                None => return Ok(steps),
+            }
+        }
+        if self.is_halted() {
+            return Ok(steps);
+        }
+        return Err(VmError::StepLimit(limit));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threebit::Instruction;
+
+    fn example() -> Machine<Instruction> {
+        return Machine::new(vec![0, 1, 5, 4, 3, 0], [729, 0, 0]);
+    }
+
+    #[test]
+    fn test_run() {
+        let mut machine = example();
+        machine.run(1000).unwrap();
+        assert!(machine.is_halted());
+        assert_eq!(machine.output, vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+        assert_eq!(machine.registers, [0, 0, 0]);
+        assert_eq!(machine.steps(), 30);
+        assert_eq!(machine.step(), Ok(None));
+    }
+
+    #[test]
+    fn test_step_limit() {
+        let mut machine = example();
+        assert_eq!(machine.run(10), Err(VmError::StepLimit(10)));
+        assert_eq!(machine.steps(), 10);
+        machine.run(20).unwrap();
+        machine.reset([729, 0, 0]);
+        assert_eq!(machine.run(30), Ok(()));
+        assert_eq!(machine.output.len(), 10);
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut machine = example();
+        let steps = machine.trace(1000).unwrap();
+        assert_eq!(steps.len(), 30);
+        assert_eq!(steps[0].instruction, "adv 1".parse().unwrap());
+        assert_eq!(steps[0].registers, [364, 0, 0]);
+        assert_eq!(steps[1].effect, Effect::Output(4));
+        assert_eq!(steps[2].effect, Effect::Jump(0));
+        assert_eq!(steps[29].effect, Effect::Next);
+        assert_eq!(steps[2].to_string(), "4: jnz 0  A=364 B=0 C=0  jump 0");
+        assert_eq!(steps[1].to_string(), "2: out a  A=364 B=0 C=0  out 4");
+    }
+
+    #[test]
+    fn test_decode_error() {
+        let mut machine: Machine<Instruction> = Machine::new(vec![5, 4, 0, 7], [1, 0, 0]);
+        assert!(machine.step().unwrap().is_some());
+        assert!(matches!(machine.step(), Err(VmError::Decode(2, _))));
+        let mut machine: Machine<Instruction> = Machine::new(vec![5], [1, 0, 0]);
+        assert!(matches!(machine.run(10), Err(VmError::Decode(0, _))));
+    }
+}
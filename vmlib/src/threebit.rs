@@ -0,0 +1,314 @@
+//! The 3-bit computer from day17: three registers, eight opcodes, and every
+//! instruction an opcode word followed by an operand word.
+use crate::machine::{Effect, InstructionSet, Word};
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub const A: usize = 0;
+pub const B: usize = 1;
+pub const C: usize = 2;
+
+/// A combo operand, 0 to 3 as themselves and 4 to 6 for the registers.
+/// 7 is reserved.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Combo {
+    Literal(Word),
+    Register(usize),
+}
+
+impl Combo {
+    pub fn value(&self, registers: &[Word; 3]) -> Word {
+        return match *self {
+            Combo::Literal(value) => value,
+            Combo::Register(r) => registers[r],
+        };
+    }
+
+    fn decode(word: Word) -> Result<Combo, String> {
+        return match word {
+            0..=3 => Ok(Combo::Literal(word)),
+            4..=6 => Ok(Combo::Register(word as usize - 4)),
+            _ => Err(format!("combo operand {} is reserved", word)),
+        };
+    }
+
+    fn encode(&self) -> Word {
+        return match *self {
+            Combo::Literal(value) => value,
+            Combo::Register(r) => r as Word + 4,
+        };
+    }
+}
+
+impl Display for Combo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return match *self {
+            Combo::Literal(value) => write!(f, "{}", value),
+            Combo::Register(r) => write!(f, "{}", ["a", "b", "c"][r]),
+        };
+    }
+}
+
+impl FromStr for Combo {
+    type Err = String;
+
+    /// `0` to `3`, or a register, as `a` or its encoding `4`.
+    fn from_str(s: &str) -> Result<Combo, String> {
+        return match s {
+            "a" => Ok(Combo::Register(A)),
+            "b" => Ok(Combo::Register(B)),
+            "c" => Ok(Combo::Register(C)),
+            _ => Combo::decode(literal(s)?),
+        };
+    }
+}
+
+fn literal(s: &str) -> Result<Word, String> {
+    return match s.parse() {
+        Ok(value @ 0..=7) => Ok(value),
+        _ => Err(format!("{} is not a 3 bit operand", s)),
+    };
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Instruction {
+    /// A = A / 2^combo
+    Adv(Combo),
+    /// B = B xor literal
+    Bxl(Word),
+    /// B = combo % 8
+    Bst(Combo),
+    /// Jump to the literal unless A is 0
+    Jnz(Word),
+    /// B = B xor C, the operand is read but ignored
+    Bxc(Word),
+    /// Output combo % 8
+    Out(Combo),
+    /// B = A / 2^combo
+    Bdv(Combo),
+    /// C = A / 2^combo
+    Cdv(Combo),
+}
+
+const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+impl Instruction {
+    pub fn opcode(&self) -> Word {
+        return match self {
+            Instruction::Adv(_) => 0,
+            Instruction::Bxl(_) => 1,
+            Instruction::Bst(_) => 2,
+            Instruction::Jnz(_) => 3,
+            Instruction::Bxc(_) => 4,
+            Instruction::Out(_) => 5,
+            Instruction::Bdv(_) => 6,
+            Instruction::Cdv(_) => 7,
+        };
+    }
+
+    fn operand(&self) -> String {
+        return match self {
+            Instruction::Adv(op)
+            | Instruction::Bst(op)
+            | Instruction::Out(op)
+            | Instruction::Bdv(op)
+            | Instruction::Cdv(op) => op.to_string(),
+            Instruction::Bxl(lit) | Instruction::Jnz(lit) | Instruction::Bxc(lit) => {
+                lit.to_string()
+            }
+        };
+    }
+}
+
+/// A / 2^combo, which is a shift, so shifting everything out gives 0.
+fn dv(op: Combo, registers: &[Word; 3]) -> Word {
+    let shift = op.value(registers);
+    return registers[A].checked_shr(shift as u32).unwrap_or(0);
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(
+            f,
+            "{} {}",
+            MNEMONICS[self.opcode() as usize],
+            self.operand()
+        );
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Instruction, String> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let [mnemonic, operand] = words[..] else {
+            return Err(format!("expected an instruction and an operand, got {}", s));
+        };
+        let mnemonic = mnemonic.to_lowercase();
+        let Some(opcode) = MNEMONICS.iter().position(|&m| m == mnemonic) else {
+            return Err(format!("unknown instruction {}", mnemonic));
+        };
+        let operand = operand.to_lowercase();
+        // bxl, jnz and bxc take a literal, the rest a combo
+        let word = match opcode {
+            1 | 3 | 4 => literal(&operand)?,
+            _ => operand.parse::<Combo>()?.encode(),
+        };
+        return Instruction::decode(&[opcode as Word, word], 0);
+    }
+}
+
+impl InstructionSet for Instruction {
+    type Registers = [Word; 3];
+
+    const REGISTERS: &'static [char] = &['A', 'B', 'C'];
+
+    fn decode(program: &[Word], ip: usize) -> Result<Instruction, String> {
+        let opcode = program[ip];
+        let Some(&operand) = program.get(ip + 1) else {
+            return Err(format!("opcode {} has no operand", opcode));
+        };
+        if operand > 7 {
+            return Err(format!("{} is not a 3 bit operand", operand));
+        }
+        return match opcode {
+            0 => Ok(Instruction::Adv(Combo::decode(operand)?)),
+            1 => Ok(Instruction::Bxl(operand)),
+            2 => Ok(Instruction::Bst(Combo::decode(operand)?)),
+            3 => Ok(Instruction::Jnz(operand)),
+            4 => Ok(Instruction::Bxc(operand)),
+            5 => Ok(Instruction::Out(Combo::decode(operand)?)),
+            6 => Ok(Instruction::Bdv(Combo::decode(operand)?)),
+            7 => Ok(Instruction::Cdv(Combo::decode(operand)?)),
+            _ => Err(format!("{} is not a 3 bit opcode", opcode)),
+        };
+    }
+
+    fn encode(&self) -> Vec<Word> {
+        let operand = match *self {
+            Instruction::Adv(op)
+            | Instruction::Bst(op)
+            | Instruction::Out(op)
+            | Instruction::Bdv(op)
+            | Instruction::Cdv(op) => op.encode(),
+            Instruction::Bxl(lit) | Instruction::Jnz(lit) | Instruction::Bxc(lit) => lit,
+        };
+        return vec![self.opcode(), operand];
+    }
+
+    fn execute(&self, registers: &mut [Word; 3]) -> Effect {
+        match *self {
+            Instruction::Adv(op) => registers[A] = dv(op, registers),
+            // ^ here is XOR
+            Instruction::Bxl(lit) => registers[B] ^= lit,
+            Instruction::Bst(op) => registers[B] = op.value(registers) % 8,
+            Instruction::Jnz(lit) => {
+                if registers[A] != 0 {
+                    return Effect::Jump(lit as usize);
+                }
+            }
+            Instruction::Bxc(_) => registers[B] ^= registers[C],
+            Instruction::Out(op) => return Effect::Output(op.value(registers) % 8),
+            Instruction::Bdv(op) => registers[B] = dv(op, registers),
+            Instruction::Cdv(op) => registers[C] = dv(op, registers),
+        }
+        return Effect::Next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execute(instruction: &str, registers: &mut [Word; 3]) -> Effect {
+        let instruction: Instruction = instruction.parse().unwrap();
+        return instruction.execute(registers);
+    }
+
+    #[test]
+    fn test_adv() {
+        let mut registers = [225, 0, 0];
+        assert_eq!(execute("adv 1", &mut registers), Effect::Next);
+        assert_eq!(registers, [112, 0, 0]);
+        registers[B] = 70;
+        execute("adv b", &mut registers);
+        assert_eq!(registers[A], 0);
+    }
+
+    #[test]
+    fn test_bxl() {
+        let mut registers = [0, 225, 0];
+        execute("bxl 2", &mut registers);
+        assert_eq!(registers[B], 227);
+    }
+
+    #[test]
+    fn test_bst() {
+        let mut registers = [225, 0, 0];
+        execute("bst a", &mut registers);
+        assert_eq!(registers[B], 225 % 8);
+    }
+
+    #[test]
+    fn test_jnz() {
+        let mut registers = [0, 0, 0];
+        assert_eq!(execute("jnz 1", &mut registers), Effect::Next);
+        registers[A] = 20;
+        assert_eq!(execute("jnz 1", &mut registers), Effect::Jump(1));
+    }
+
+    #[test]
+    fn test_bxc() {
+        let mut registers = [0, 225, 2];
+        execute("bxc 2", &mut registers);
+        assert_eq!(registers, [0, 227, 2]);
+    }
+
+    #[test]
+    fn test_out() {
+        let mut registers = [0, 225, 0];
+        assert_eq!(execute("out b", &mut registers), Effect::Output(1));
+        registers[B] = 92;
+        assert_eq!(execute("out b", &mut registers), Effect::Output(4));
+        assert_eq!(registers[B], 92);
+    }
+
+    #[test]
+    fn test_bdv() {
+        let mut registers = [225, 0, 0];
+        execute("bdv 1", &mut registers);
+        assert_eq!(registers, [225, 112, 0]);
+    }
+
+    #[test]
+    fn test_cdv() {
+        let mut registers = [225, 0, 0];
+        execute("cdv 1", &mut registers);
+        assert_eq!(registers, [225, 0, 112]);
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            Instruction::decode(&[0, 5], 0),
+            Ok(Instruction::Adv(Combo::Register(B)))
+        );
+        assert_eq!(Instruction::decode(&[1, 7], 0), Ok(Instruction::Bxl(7)));
+        assert!(Instruction::decode(&[0, 7], 0).is_err());
+        assert!(Instruction::decode(&[8, 0], 0).is_err());
+        assert!(Instruction::decode(&[0, 8], 0).is_err());
+        assert!(Instruction::decode(&[3], 0).is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("ADV 4".parse(), Ok(Instruction::Adv(Combo::Register(A))));
+        assert_eq!("out  c".parse(), Ok(Instruction::Out(Combo::Register(C))));
+        assert!("bxl a".parse::<Instruction>().is_err());
+        assert!("adv 7".parse::<Instruction>().is_err());
+        assert!("adv".parse::<Instruction>().is_err());
+        assert_eq!(Instruction::Cdv(Combo::Literal(3)).to_string(), "cdv 3");
+    }
+}
@@ -0,0 +1,86 @@
+use filelib::{parse_number, remove_blanks, split_lines_by_blanks, split_pair};
+use gridlib::{Grid, GridCoordinate};
+
+/// Days whose input is the grid itself, one row per line.
+const GRID_DAYS: [u32; 7] = [4, 6, 8, 10, 12, 16, 20];
+
+fn to_grid(lines: &[String]) -> Option<Grid<char>> {
+    // Cells are chars, so rows are measured in chars rather than bytes
+    let width = lines.first()?.chars().count();
+    if lines.iter().any(|l| l.chars().count() != width) {
+        return None;
+    }
+    let values = lines.iter().flat_map(|l| l.chars()).collect();
+    return Some(Grid::new(width, lines.len(), values));
+}
+
+/// day18's memory space, with the bytes that have fallen so far as `#`.
+fn fallen_bytes(lines: &[String], params: &[usize]) -> Option<Grid<char>> {
+    let [width, height, fallen] = params.try_into().ok()?;
+    let mut values = vec!['.'; width.checked_mul(height)?];
+    for line in lines.iter().take(fallen) {
+        let (x, y) = split_pair(line, ",").ok()?;
+        let coord = GridCoordinate::new(parse_number(x).ok()?, parse_number(y).ok()?);
+        if coord.x >= width || coord.y >= height {
+            return None;
+        }
+        values[coord.x + coord.y * width] = '#';
+    }
+    return Some(Grid::new(width, height, values));
+}
+
+/// The input drawn with `grid_strings`, for days that have a grid.
+///
+/// `None` for other days, or an input that doesn't make a grid.
+/// ```
+/// assert_eq!(wasm::grid_lines(18, "1,0\n", &[3, 2, 1]), Some(vec![".#.".to_string(), "...".to_string()]));
+/// assert_eq!(wasm::grid_lines(1, "3   4\n", &[]), None);
+/// ```
+pub fn grid_lines(day: u32, input: &str, params: &[usize]) -> Option<Vec<String>> {
+    let grid = match day {
+        d if GRID_DAYS.contains(&d) => to_grid(&remove_blanks(input))?,
+        // The warehouse, before the moves
+        15 => to_grid(split_lines_by_blanks(input).first()?)?,
+        18 => fallen_bytes(&remove_blanks(input), params)?,
+        _ => return None,
+    };
+    return Some(grid.grid_strings());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_days() {
+        let input = "#.#\n.S.\n";
+        assert_eq!(
+            grid_lines(16, input, &[]),
+            Some(vec!["#.#".to_string(), ".S.".to_string()])
+        );
+        assert_eq!(grid_lines(16, "#.#\n.S\n", &[]), None);
+        assert_eq!(grid_lines(16, "", &[]), None);
+        assert_eq!(
+            grid_lines(16, "é\né\n", &[]),
+            Some(vec!["é".to_string(), "é".to_string()])
+        );
+        assert_eq!(grid_lines(16, "é\nab\n", &[]), None);
+    }
+
+    #[test]
+    fn test_warehouse() {
+        let input = "###\n#@#\n###\n\n<^\n";
+        assert_eq!(grid_lines(15, input, &[]).unwrap()[1], "#@#");
+    }
+
+    #[test]
+    fn test_fallen_bytes() {
+        assert_eq!(
+            grid_lines(18, "0,0\n1,1\n", &[2, 2, 2]),
+            Some(vec!["#.".to_string(), ".#".to_string()])
+        );
+        assert_eq!(grid_lines(18, "5,0\n", &[2, 2, 1]), None);
+        assert_eq!(grid_lines(18, "0,0\n", &[2, 2]), None);
+        assert_eq!(grid_lines(18, "0,0\n", &[usize::MAX, 2, 1]), None);
+    }
+}
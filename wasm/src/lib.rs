@@ -0,0 +1,144 @@
+//! Every day's solver for the browser, through `wasm-bindgen`.
+//!
+//! `www/index.html` is the page that uses it, build the package for it with
+//! `make web`. The plain functions do the work so they can be tested natively,
+//! the `#[wasm_bindgen]` ones only convert to what JavaScript can take.
+use solutionlib::{find_solution, solutions, Part};
+use wasm_bindgen::prelude::*;
+
+mod grid;
+
+pub use crate::grid::grid_lines;
+
+/// Solve one part of a day, with its real input's params if none are given.
+///
+/// The input goes through the day's `check_input` first, so input it can't
+/// parse comes back as an error instead of a panic, which would leave the
+/// module unusable until the page is reloaded.
+///
+/// Parts are solved one at a time because some examples are only meant for
+/// one part, and never finish on the other.
+pub fn solve_part(day: u32, part: &str, input: &str, params: &[usize]) -> Result<String, String> {
+    let Some(solution) = find_solution(day) else {
+        return Err(format!("Day {} isn't solved", day));
+    };
+    let part = match part {
+        "a" => Part::A,
+        "b" => Part::B,
+        _ => return Err(format!("Unknown part {}, expected a or b", part)),
+    };
+    let params = if params.is_empty() {
+        solution.params
+    } else {
+        params
+    };
+    if params.len() != solution.params.len() {
+        return Err(format!(
+            "Day {} takes {} params, got {}",
+            day,
+            solution.params.len(),
+            params.len()
+        ));
+    }
+    let input = solution.parse(input);
+    solution
+        .check_with(&input, params)
+        .map_err(|e| e.to_string())?;
+    return Ok(solution.solve_with(part, &input, params));
+}
+
+fn to_usize(params: Vec<u32>) -> Vec<usize> {
+    return params.into_iter().map(|p| p as usize).collect();
+}
+
+/// Show the Rust panic message in the console when a solver gives up on an input.
+#[wasm_bindgen(start)]
+pub fn start() {
+    console_error_panic_hook::set_once();
+}
+
+/// Every day that can be solved, in order.
+#[wasm_bindgen]
+pub fn days() -> Vec<u32> {
+    return solutions().iter().map(|s| s.day).collect();
+}
+
+/// The params a day uses for its real input, eg the grid size for day 14.
+#[wasm_bindgen]
+pub fn default_params(day: u32) -> Vec<u32> {
+    return match find_solution(day) {
+        Some(solution) => solution.params.iter().map(|&p| p as u32).collect(),
+        None => vec![],
+    };
+}
+
+/// Solve part `a` or `b`, an empty `params` uses the real input's.
+#[wasm_bindgen]
+pub fn solve(day: u32, part: &str, input: &str, params: Vec<u32>) -> Result<String, JsError> {
+    return solve_part(day, part, input, &to_usize(params)).map_err(|e| JsError::new(&e));
+}
+
+/// The input drawn as a grid, for the days that have one.
+#[wasm_bindgen]
+pub fn grid_view(day: u32, input: &str, params: Vec<u32>) -> Option<String> {
+    let params = to_usize(params);
+    let params = if params.is_empty() {
+        find_solution(day)?.params.to_vec()
+    } else {
+        params
+    };
+    return grid_lines(day, input, &params).map(|lines| lines.join("\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filelib::load_fixture;
+
+    fn examples() -> String {
+        return format!("{}/../examples", env!("CARGO_MANIFEST_DIR"));
+    }
+
+    fn example(name: &str) -> String {
+        return filelib::load(&format!("{}/{}.txt", examples(), name));
+    }
+
+    #[test]
+    fn test_solve_part() {
+        let input = example("day01-1");
+        assert_eq!(solve_part(1, "a", &input, &[]), Ok("11".to_string()));
+        assert_eq!(solve_part(1, "b", &input, &[]), Ok("31".to_string()));
+        assert!(solve_part(1, "c", &input, &[]).is_err());
+    }
+
+    #[test]
+    fn test_solve_part_params() {
+        let fixture = load_fixture(&examples(), "day14-1");
+        let params = fixture.params("a").unwrap();
+        let answer = solve_part(14, "a", &example("day14-1"), &params);
+        assert_eq!(answer.unwrap(), fixture.answer("a").unwrap());
+        assert!(solve_part(14, "a", &example("day14-1"), &[7]).is_err());
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(solve_part(1, "a", "nonsense", &[]).is_err());
+        assert!(solve_part(5, "b", "1|2", &[]).is_err());
+        assert_eq!(
+            solve_part(1, "a", &example("day01-1"), &[]),
+            Ok("11".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        assert!(solve_part(26, "a", "", &[]).is_err());
+        assert_eq!(default_params(26), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_days() {
+        assert_eq!(days(), (1..=24).collect::<Vec<u32>>());
+        assert_eq!(default_params(18), vec![71, 71, 1024]);
+    }
+}
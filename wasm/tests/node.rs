@@ -0,0 +1,37 @@
+//! The `wasm-bindgen` exports as JavaScript sees them, run under Node with
+//! `make node-test`.
+#![cfg(target_arch = "wasm32")]
+
+use wasm::{days, default_params, grid_view, solve};
+use wasm_bindgen_test::*;
+
+const DAY01: &str = "3 4\n4 3\n2 5\n1 3\n3 9\n3 3\n";
+
+#[wasm_bindgen_test]
+fn test_days() {
+    assert_eq!(days().len(), 24);
+    assert_eq!(default_params(14), vec![103, 101]);
+}
+
+#[wasm_bindgen_test]
+fn test_solve() {
+    assert_eq!(solve(1, "a", DAY01, vec![]).unwrap(), "11");
+    assert_eq!(solve(1, "b", DAY01, vec![]).unwrap(), "31");
+    assert!(solve(26, "a", DAY01, vec![]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_bad_input() {
+    // An error, not a panic, so the module still works afterwards
+    assert!(solve(1, "a", "nonsense", vec![]).is_err());
+    assert_eq!(solve(1, "a", DAY01, vec![]).unwrap(), "11");
+}
+
+#[wasm_bindgen_test]
+fn test_grid_view() {
+    assert_eq!(
+        grid_view(18, "1,0\n", vec![3, 2, 1]),
+        Some(".#.\n...".to_string())
+    );
+    assert_eq!(grid_view(1, DAY01, vec![]), None);
+}
@@ -0,0 +1,46 @@
+// Built by `make web` into ./pkg
+import init, { days, default_params, grid_view, solve } from "./pkg/wasm.js";
+
+const $ = (id) => document.getElementById(id);
+
+// Space separated numbers, an empty box uses the day's defaults
+function params() {
+  const text = $("params").value.trim();
+  return text === "" ? new Uint32Array() : Uint32Array.from(text.split(/\s+/), Number);
+}
+
+// Give the page a chance to draw before a slow part starts
+const nextFrame = () => new Promise((resolve) => setTimeout(resolve, 0));
+
+async function run() {
+  const day = Number($("day").value);
+  const input = $("input").value;
+  $("error").textContent = "";
+  try {
+    $("grid").textContent = grid_view(day, input, params()) ?? "";
+  } catch (e) {
+    $("grid").textContent = "";
+    $("error").textContent = `Grid: ${e.message ?? e}`;
+  }
+  for (const part of ["a", "b"]) {
+    $(part).textContent = "...";
+    await nextFrame();
+    try {
+      $(part).textContent = solve(day, part, input, params());
+    } catch (e) {
+      // Input check_input rejects, or a solver panic with the details in the console
+      $(part).textContent = "";
+      $("error").textContent = `Part ${part}: ${e.message ?? e}`;
+    }
+  }
+}
+
+await init();
+for (const day of days()) {
+  $("day").add(new Option(`Day ${day}`, day));
+}
+$("day").addEventListener("change", () => {
+  $("params").placeholder = default_params(Number($("day").value)).join(" ");
+});
+$("day").dispatchEvent(new Event("change"));
+$("solve").addEventListener("click", run);